@@ -0,0 +1,227 @@
+//! Suggesting missing indexes
+//!
+//! SQLite's own index advisor (the `.expert` command of the shell) isn't available through `rusqlite`, so [`Database::advise_indexes`] works from what SQLite tells about the schema and query plans instead:
+//! - Foreign keys whose columns aren't covered by an index: deleting or updating a referenced row has to scan the whole referencing table to check the constraint.
+//! - Queries passed to [`Database::advise_indexes_for`] whose [query plan](https://sqlite.org/eqp.html) contains a full `SCAN` of a table, with the columns of that table used in the query's `WHERE` clause as the suggested index.
+//!
+//! The statements generated for [`HasKey`](crate::HasKey) only ever look up rows by their primary key, so they never need another index.
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Author, Book);
+//!
+//! #[derive(Table)]
+//! struct Author {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//! #[derive(Table)]
+//! struct Book {
+//! 	#[key]
+//! 	id: Id,
+//! 	author: Ref<Author>,
+//! 	title: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! let advice = db.advise_indexes()?;
+//! assert_eq!(advice.len(), 1);
+//! assert_eq!(advice[0].table, "book");
+//! assert_eq!(advice[0].columns, ["author"]);
+//! db.execute_batch(&advice[0].create_index_sql())?;
+//! assert!(db.advise_indexes()?.is_empty());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema
+};
+
+/// Suggested index on a [`Table`](crate::Table)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexAdvice {
+	/// Name of the [`Table`](crate::Table), as in [`Table::NAME`](crate::Table::NAME)
+	pub table: &'static str,
+	/// Names of the columns to index, in order
+	pub columns: Vec<String>,
+	pub reason: AdviceReason
+}
+
+/// Why an [`IndexAdvice`] was given
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdviceReason {
+	/// The columns are a foreign key referencing the table
+	ForeignKey {
+		references: String
+	},
+	/// The query scans the entire table
+	Scan {
+		query: String
+	}
+}
+
+impl IndexAdvice {
+	/// `CREATE INDEX` statement for the suggested index
+	///
+	/// The index is named like those of [`Table::INDEXES`](crate::Table::INDEXES).
+	pub fn create_index_sql(&self) -> String {
+		format!(
+			"CREATE INDEX {table}_{names}_index ON {table} ( {columns} );",
+			table = self.table,
+			names = self.columns.join("_"),
+			columns = self.columns.join(", ")
+		)
+	}
+}
+
+impl fmt::Display for IndexAdvice {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.create_index_sql())?;
+		match &self.reason {
+			AdviceReason::ForeignKey {references} =>
+				write!(f, " -- foreign key referencing {references}"),
+			AdviceReason::Scan {query} =>
+				write!(f, " -- table scanned by: {query}")
+		}
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Suggest indexes for foreign keys that aren't covered by one
+	pub fn advise_indexes(&self) -> SqlResult<Vec<IndexAdvice>> {
+		self.advise_indexes_for(&[])
+	}
+	/// Suggest indexes for foreign keys, and for the `queries` that scan entire tables
+	pub fn advise_indexes_for(&self, queries: &[&str])
+		-> SqlResult<Vec<IndexAdvice>>
+	{
+		let mut advice = Vec::new();
+		for def in S::DEFINITIONS {
+			let indexes = self.indexed_columns(def.name)?;
+			for (columns, references) in self.foreign_keys(def.name)? {
+				if !is_covered(&columns, &indexes) {
+					advice.push(IndexAdvice {
+						table: def.name,
+						columns,
+						reason: AdviceReason::ForeignKey {references}
+					});
+				}
+			}
+		}
+		for query in queries {
+			for table in self.scanned_tables(query)? {
+				let Some(def) = S::DEFINITIONS.iter().find(|def| def.name == table)
+					else {continue};
+				let columns = where_columns(query, &self.column_names(def.name)?);
+				if columns.is_empty() {
+					continue;
+				}
+				let indexes = self.indexed_columns(def.name)?;
+				let suggestion = IndexAdvice {
+					table: def.name,
+					columns,
+					reason: AdviceReason::Scan {query: query.to_string()}
+				};
+				if !is_covered(&suggestion.columns, &indexes)
+					&& !advice.contains(&suggestion)
+				{
+					advice.push(suggestion);
+				}
+			}
+		}
+		Ok(advice)
+	}
+
+	fn column_names(&self, table: &str) -> SqlResult<Vec<String>> {
+		self.query_all_with("SELECT name FROM pragma_table_info(?)", &table)
+	}
+	/// Columns of every index on the table (including the primary key), in order
+	fn indexed_columns(&self, table: &str) -> SqlResult<Vec<Vec<String>>> {
+		let index_names: Vec<String> =
+			self.query_all_with("SELECT name FROM pragma_index_list(?)", &table)?;
+		let mut indexes = Vec::with_capacity(index_names.len() + 1);
+		for name in index_names {
+			indexes.push(self.query_all_with(
+				"SELECT name FROM pragma_index_info(?) ORDER BY seqno",
+				&name
+			)?);
+		}
+		// an INTEGER PRIMARY KEY is the rowid and has no separate index
+		let primary_key: Vec<(String, String)> = self.query_all_with(
+			"SELECT name, type FROM pragma_table_info(?) WHERE pk > 0 ORDER BY pk",
+			&table
+		)?;
+		if let [(name, ty)] = primary_key.as_slice() {
+			if ty.eq_ignore_ascii_case("INTEGER") {
+				indexes.push(vec![name.clone()]);
+			}
+		}
+		Ok(indexes)
+	}
+	/// Columns and referenced table of each foreign key of the table
+	fn foreign_keys(&self, table: &str) -> SqlResult<Vec<(Vec<String>, String)>> {
+		let rows: Vec<(i64, String, String)> = self.query_all_with(
+			"SELECT id, \"table\", \"from\" FROM pragma_foreign_key_list(?) \
+			ORDER BY id, seq",
+			&table
+		)?;
+		let mut keys: Vec<(i64, Vec<String>, String)> = Vec::new();
+		for (id, references, column) in rows {
+			match keys.last_mut() {
+				Some((last_id, columns, _)) if *last_id == id => columns.push(column),
+				_ => keys.push((id, vec![column], references))
+			}
+		}
+		Ok(keys.into_iter()
+			.map(|(_id, columns, references)| (columns, references))
+			.collect())
+	}
+	/// Names of the tables that the query plan scans entirely
+	fn scanned_tables(&self, query: &str) -> SqlResult<Vec<String>> {
+		// parameters of the query are left unbound, i.e. NULL
+		let plan: Vec<(i64, i64, i64, String)> =
+			self.query_all(&format!("EXPLAIN QUERY PLAN {query}"))?;
+		Ok(plan.iter()
+			.filter_map(|(_id, _parent, _notused, detail)| detail.strip_prefix("SCAN "))
+			.filter(|rest| !rest.contains(" USING "))
+			.map(|rest| rest.split_whitespace().next().unwrap_or(rest).to_string())
+			.collect())
+	}
+}
+
+/// Whether the leading columns of any of the indexes are the `columns` (in any order)
+fn is_covered(columns: &[String], indexes: &[Vec<String>]) -> bool {
+	indexes.iter().any(|index| {
+		index.len() >= columns.len()
+			&& index[..columns.len()].iter().all(|c| columns.contains(c))
+	})
+}
+
+/// The `columns` that appear (as words) in the `WHERE` clause of the query, in order of appearance
+fn where_columns(query: &str, columns: &[String]) -> Vec<String> {
+	let upper = query.to_ascii_uppercase();
+	let Some(start) = upper.find(" WHERE ") else {return Vec::new()};
+	let end = [" GROUP BY ", " ORDER BY ", " LIMIT "].iter()
+		.filter_map(|clause| upper[start..].find(clause))
+		.min()
+		.map_or(query.len(), |len| start + len);
+	let mut found = Vec::new();
+	let words = query[start..end]
+		.split(|c: char| !(c.is_alphanumeric() || c == '_'));
+	for word in words {
+		if let Some(column) = columns.iter().find(|c| c.as_str() == word) {
+			if !found.contains(column) {
+				found.push(column.clone());
+			}
+		}
+	}
+	found
+}
@@ -0,0 +1,347 @@
+//! [`AsyncDatabase`]: a [`Database`] running on a dedicated background thread
+//!
+//! This module is only available with the `async` feature.
+//!
+//! SQLite calls block, so a [`Database`] can't be used directly from `async` code without stalling the executor.
+//! Instead, the [`AsyncDatabase`] moves the [`Database`] onto its own thread and sends it jobs, the results of which can be `.await`ed.
+//! This doesn't depend on any particular async runtime.
+//!
+//! For workloads that upsert the same few rows over and over (e.g. syncing state), [`AsyncDatabase::upsert_coalesced`] combines writes:
+//! upserts of a table that are still waiting for the background thread are collapsed, so only the last entry for each key is written, and all of them in a single transaction.
+
+use std::any::{
+	Any,
+	TypeId
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{
+	Arc,
+	Mutex,
+	MutexGuard,
+	PoisonError
+};
+use std::sync::mpsc::{
+	self,
+	Sender
+};
+use std::task::{
+	Context,
+	Poll,
+	Waker
+};
+use std::thread;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::ffi;
+
+use crate::{
+	Database,
+	Entry,
+	HasKey,
+	RowId,
+	Schema
+};
+use crate::meta::tuple::{
+	marker,
+	CloneFromRef
+};
+
+type Job<S> = Box<dyn FnOnce(&mut Database<S>) + Send>;
+/// [`Batch`]es of coalesced upserts that haven't been flushed yet, by the [`TypeId`] of their table
+type Pending = HashMap<TypeId, Box<dyn Any + Send>>;
+
+/// Handle to a [`Database`] that lives on a background thread
+///
+/// All methods send a job to the background thread and return a future that resolves once the job has run.
+/// Jobs run one after another in the order they were sent.
+///
+/// The background thread stops once the [`AsyncDatabase`] is dropped and all previously sent jobs have run, closing the [`Database`].
+#[derive(Debug)]
+pub struct AsyncDatabase<S: Schema> {
+	jobs: Sender<Job<S>>,
+	/// See [`AsyncDatabase::upsert_coalesced`]
+	pending: Arc<Mutex<Pending>>
+}
+
+/// Upserts of one table that are combined into a single job
+struct Batch<T: HasKey> {
+	/// The latest entry for each key, and the replies to all upserts of that key
+	entries: Vec<(T, Vec<Reply<usize>>)>,
+	/// Index into `entries` by key
+	keys: HashMap<T::Key, usize>,
+	/// Whether the job already took the entries, so no more can be added
+	flushed: bool
+}
+
+impl<S: Schema + Send + 'static> AsyncDatabase<S> {
+	/// Move the [`Database`] onto a new background thread
+	pub fn new(mut db: Database<S>) -> Self {
+		let (jobs, queue) = mpsc::channel::<Job<S>>();
+		thread::spawn(move || {
+			while let Ok(job) = queue.recv() {
+				job(&mut db);
+			}
+		});
+		Self {
+			jobs,
+			pending: Default::default()
+		}
+	}
+
+	/// Run a closure with the [`Database`] on the background thread
+	///
+	/// This is what all the other methods use, so it can be used for anything they don't cover.
+	pub fn call<F, R>(&self, f: F) -> impl Future<Output = SqlResult<R>>
+		where F: FnOnce(&mut Database<S>) -> SqlResult<R> + Send + 'static,
+			R: Send + 'static
+	{
+		let (reply, response) = oneshot();
+		// coalesced upserts sent after this job must not be written before it
+		let mut pending = lock(&self.pending);
+		pending.clear();
+		// if the background thread stopped, `reply` is dropped with the job
+		let _ = self.jobs.send(Box::new(move |db: &mut Database<S>| {
+			reply.send(f(db))
+		}));
+		response
+	}
+
+	pub fn get_all<T>(&self) -> impl Future<Output = SqlResult<Vec<T>>>
+		where T: Entry + Send + 'static
+	{
+		self.call(|db| db.get_all::<T>())
+	}
+	pub fn get<T>(&self, key: <T as HasKey>::Key)
+		-> impl Future<Output = SqlResult<Option<T>>>
+		where T: Entry + HasKey + Send + 'static,
+			<T as HasKey>::Key: Send
+	{
+		self.call(move |db| db.get::<T>(key))
+	}
+	/// Insert the entry and set its id, see [`Database::create`]
+	///
+	/// Since the entry has to be moved to the background thread, it is returned with its new id.
+	pub fn create<T>(&self, mut entry: T) -> impl Future<Output = SqlResult<T>>
+		where
+			T: Entry + HasKey<Marker = marker::One> + Send + 'static,
+			T::Key: RowId
+	{
+		self.call(move |db| db.create(&mut entry).map(|()| entry))
+	}
+	pub fn insert<T>(&self, entry: T) -> impl Future<Output = SqlResult<usize>>
+		where T: Entry + Send + 'static
+	{
+		self.call(move |db| db.insert(&entry))
+	}
+	pub fn upsert<T>(&self, entry: T) -> impl Future<Output = SqlResult<usize>>
+		where T: HasKey + Entry + Send + 'static
+	{
+		self.call(move |db| db.upsert(&entry))
+	}
+	/// Upsert the entry, combined with other upserts of the same table that haven't been written yet
+	///
+	/// If an upsert of an entry with the same key is still waiting for the background thread, the entry simply replaces it, and both futures resolve with the result of writing this one.
+	/// All waiting upserts of the table are written together in a single transaction: if one of them fails, none of them are written, and all of their futures resolve with that error.
+	///
+	/// Writes are still ordered: the waiting upserts are written before any job sent after them with another method, and upserts sent after such a job aren't combined with those before it.
+	pub fn upsert_coalesced<T>(&self, entry: T) -> impl Future<Output = SqlResult<usize>>
+		where
+			T: HasKey + Entry + Send + 'static,
+			T::Key: CloneFromRef<T::Marker> + Eq + Hash + Send
+	{
+		let (reply, response) = oneshot();
+		let mut pending = lock(&self.pending);
+		let current = pending.get(&TypeId::of::<T>())
+			.and_then(|batch| batch.downcast_ref::<Arc<Mutex<Batch<T>>>>());
+		if let Some(batch) = current {
+			let mut batch = lock(batch);
+			if !batch.flushed {
+				batch.add(entry, reply);
+				return response;
+			}
+		}
+
+		let batch = Arc::new(Mutex::new(Batch {
+			entries: Vec::new(),
+			keys: HashMap::new(),
+			flushed: false
+		}));
+		lock(&batch).add(entry, reply);
+		pending.insert(TypeId::of::<T>(), Box::new(batch.clone()));
+		let _ = self.jobs.send(Box::new(move |db: &mut Database<S>| {
+			flush(db, &batch)
+		}));
+		response
+	}
+	pub fn update<T>(&self, entry: T) -> impl Future<Output = SqlResult<usize>>
+		where T: HasKey + Entry + Send + 'static
+	{
+		self.call(move |db| db.update(&entry))
+	}
+	pub fn delete<T>(&self, key: <T as HasKey>::Key)
+		-> impl Future<Output = SqlResult<bool>>
+		where T: Entry + HasKey + Send + 'static,
+			<T as HasKey>::Key: Send
+	{
+		self.call(move |db| db.delete::<T>(&key))
+	}
+}
+
+impl<S: Schema + Send + 'static> From<Database<S>> for AsyncDatabase<S> {
+	fn from(db: Database<S>) -> Self {
+		Self::new(db)
+	}
+}
+
+/*
+ *	COALESCING
+ */
+
+impl<T: HasKey> Batch<T>
+	where T::Key: CloneFromRef<T::Marker> + Eq + Hash
+{
+	fn add(&mut self, entry: T, reply: Reply<usize>) {
+		let key = T::Key::clone_from_ref(entry.get_key());
+		match self.keys.get(&key) {
+			Some(&idx) => {
+				let (latest, replies) = &mut self.entries[idx];
+				*latest = entry;
+				replies.push(reply);
+			},
+			None => {
+				self.keys.insert(key, self.entries.len());
+				self.entries.push((entry, vec![reply]));
+			}
+		}
+	}
+}
+
+/// Write all entries of the batch in a transaction, and reply to every upsert
+///
+/// If any of them fails, the transaction is rolled back and every upsert gets the error.
+fn flush<S: Schema, T: HasKey + Entry>(db: &mut Database<S>, batch: &Mutex<Batch<T>>) {
+	let entries = {
+		let mut batch = lock(batch);
+		batch.flushed = true;
+		batch.keys.clear();
+		std::mem::take(&mut batch.entries)
+	};
+	let results = db.transaction().and_then(|tx| {
+		let mut results = Vec::with_capacity(entries.len());
+		for (entry, _replies) in &entries {
+			// returning early drops the transaction, rolling it back
+			results.push(tx.upsert(entry)?);
+		}
+		tx.commit().map(|()| results)
+	});
+	match results {
+		Ok(results) => for ((_entry, replies), result) in entries.into_iter().zip(results) {
+			for reply in replies {
+				reply.send(Ok(result));
+			}
+		},
+		Err(e) => for (_entry, replies) in entries {
+			for reply in replies {
+				reply.send(Err(copy_error(&e)));
+			}
+		}
+	}
+}
+
+/// [`Error`] isn't `Clone`, so this keeps the SQLite error code & message of the errors that can occur here
+fn copy_error(e: &Error) -> Error {
+	match e {
+		Error::SqliteFailure(code, message) => Error::SqliteFailure(*code, message.clone()),
+		other => Error::SqliteFailure(
+			ffi::Error::new(ffi::SQLITE_ERROR),
+			Some(other.to_string())
+		)
+	}
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+	mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/*
+ *	ONESHOT CHANNEL
+ */
+
+#[derive(Debug)]
+struct Slot<R> {
+	value: Option<R>,
+	closed: bool,
+	waker: Option<Waker>
+}
+
+/// Sending half, owned by the job
+struct Reply<R> {
+	slot: Arc<Mutex<Slot<SqlResult<R>>>>
+}
+/// Receiving half, returned to the caller
+struct Response<R> {
+	slot: Arc<Mutex<Slot<SqlResult<R>>>>
+}
+
+fn oneshot<R>() -> (Reply<R>, Response<R>) {
+	let slot = Arc::new(Mutex::new(Slot {
+		value: None,
+		closed: false,
+		waker: None
+	}));
+	(Reply { slot: slot.clone() }, Response { slot })
+}
+
+impl<R> Reply<R> {
+	fn send(self, value: SqlResult<R>) {
+		if let Ok(mut slot) = self.slot.lock() {
+			slot.value = Some(value);
+		}
+		// dropping `self` wakes the receiver
+	}
+}
+
+impl<R> Drop for Reply<R> {
+	fn drop(&mut self) {
+		if let Ok(mut slot) = self.slot.lock() {
+			slot.closed = true;
+			if let Some(waker) = slot.waker.take() {
+				waker.wake();
+			}
+		}
+	}
+}
+
+impl<R> Future for Response<R> {
+	type Output = SqlResult<R>;
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut slot = match self.slot.lock() {
+			Ok(slot) => slot,
+			Err(_poisoned) => return Poll::Ready(Err(stopped()))
+		};
+		if let Some(value) = slot.value.take() {
+			Poll::Ready(value)
+		}
+		else if slot.closed {
+			// the job was dropped without running, e.g. because it panicked
+			Poll::Ready(Err(stopped()))
+		}
+		else {
+			slot.waker = Some(cx.waker().clone());
+			Poll::Pending
+		}
+	}
+}
+
+fn stopped() -> Error {
+	Error::SqliteFailure(
+		ffi::Error::new(ffi::SQLITE_MISUSE),
+		Some("background database thread stopped".to_string())
+	)
+}
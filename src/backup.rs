@@ -0,0 +1,115 @@
+//! Copying databases with SQLite's [online backup API](https://sqlite.org/backup.html)
+//!
+//! A backup copies the database page by page, while it stays open: between the steps, other connections (and this one) can keep using it.
+//! If the database is written to during a backup by another connection, the backup starts over, so it's always a consistent snapshot.
+//!
+//! [`Database::backup_to`] snapshots a live database into a file, e.g. for nightly backups, and [`Database::restore_from`] replaces its contents with those of such a file.
+//!
+//!```
+//! use liter::{database, Table};
+//!
+//! #[database]
+//! struct Db (Entry);
+//!
+//! #[derive(Table)]
+//! struct Entry {
+//! 	text: String
+//! }
+//!
+//! let path = std::env::temp_dir().join("liter_backup_doctest.db");
+//! # let _ = std::fs::remove_file(&path);
+//! let mut db = Db::create_in_memory()?;
+//! db.insert(&Entry { text: "kept".to_string() })?;
+//!
+//! let mut remaining = Vec::new();
+//! db.backup_to(&path, |progress| remaining.push(progress.remaining))?;
+//! assert_eq!(remaining.last(), Some(&0));
+//!
+//! db.insert(&Entry { text: "lost".to_string() })?;
+//! db.restore_from(&path)?;
+//! assert_eq!(db.get_all::<Entry>()?.len(), 1);
+//! # std::fs::remove_file(&path).unwrap();
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use rusqlite::{
+	Connection,
+	OpenFlags,
+	Result as SqlResult
+};
+use rusqlite::backup::{
+	Backup,
+	Progress,
+	StepResult
+};
+
+use crate::{
+	Database,
+	Schema
+};
+
+impl<S: Schema> Database<S> {
+	/// Copy the database into a new in-memory database
+	///
+	/// This is useful for tests, which can start out from a prepared fixture file and then change the copy without touching the file.
+	///
+	///```
+	/// # use liter::{database, Table};
+	/// # #[database]
+	/// # struct Db (Item);
+	/// # #[derive(Table)]
+	/// # struct Item { name: String }
+	/// let fixture = Db::create_in_memory()?;
+	/// fixture.insert(&Item { name: "fixture".to_string() })?;
+	///
+	/// let copy = fixture.clone_to_memory()?;
+	/// copy.insert(&Item { name: "test".to_string() })?;
+	/// assert_eq!(copy.get_all::<Item>()?.len(), 2);
+	/// assert_eq!(fixture.get_all::<Item>()?.len(), 1);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	pub fn clone_to_memory(&self) -> SqlResult<Self> {
+		let mut memory = Connection::open_in_memory()?;
+		run(&Backup::new(&self.connection, &mut memory)?, |_progress| {})?;
+		Self::from_connection(memory)
+	}
+	/// Copy the database into the file at the path, replacing its contents (or creating it)
+	///
+	/// `progress` is called after each step of [`PAGES_PER_STEP`] pages.
+	pub fn backup_to<F>(&self, path: &Path, progress: F) -> SqlResult<()>
+		where F: FnMut(Progress)
+	{
+		let mut file = Connection::open(path)?;
+		run(&Backup::new(&self.connection, &mut file)?, progress)
+	}
+	/// Replace the contents of the database with those of the database file at the path, e.g. one written by [`backup_to`](Self::backup_to)
+	///
+	/// The file has to have the same schema, which isn't checked.
+	pub fn restore_from(&mut self, path: &Path) -> SqlResult<()> {
+		let file = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+		run(&Backup::new(&file, &mut self.connection)?, |_progress| {})
+	}
+}
+
+/// Number of pages copied in each step of a backup, between which the source database is unlocked
+pub const PAGES_PER_STEP: i32 = 128;
+
+/// How long to wait before retrying a step that failed because the database was locked
+const BUSY_PAUSE: Duration = Duration::from_millis(10);
+
+fn run<F: FnMut(Progress)>(backup: &Backup<'_, '_>, mut progress: F) -> SqlResult<()> {
+	loop {
+		match backup.step(PAGES_PER_STEP)? {
+			StepResult::Done => break,
+			StepResult::Busy | StepResult::Locked => thread::sleep(BUSY_PAUSE),
+			_ => {}
+		}
+		progress(backup.progress());
+	}
+	progress(backup.progress());
+	Ok(())
+}
@@ -0,0 +1,76 @@
+//! Building databases at compile time, for embedding them with [`Database::open_from_bytes`](crate::Database::open_from_bytes)
+//!
+//! [`generate`] is meant to be called from a build script, which creates a database with static data (e.g. a dictionary) in `OUT_DIR` that the application then embeds with [`include_bytes!`].
+//! Since a build script can't use the types of the crate it builds, the schema is given by its `CREATE` SQL, e.g. [`Schema::CREATE`](crate::Schema::CREATE) of a [`database`](crate::database) defined in a crate shared by both.
+//!
+//!```
+//! // in a crate shared by the application & its build script
+//! use liter::{database, Table};
+//!
+//! #[database]
+//! pub struct Dictionary (Word);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! pub struct Word {
+//! 	#[key]
+//! 	pub word: String,
+//! 	pub meaning: String
+//! }
+//!
+//! // build.rs
+//! use liter::Schema;
+//!
+//! # let out_dir = std::env::temp_dir();
+//! // let out_dir = std::env::var("OUT_DIR").unwrap();
+//! let out_path = std::path::Path::new(&out_dir).join("dictionary.db");
+//! liter::build::generate(Dictionary::CREATE, |conn| {
+//! 	conn.execute("INSERT INTO word VALUES ('liter', 'a unit of volume')", [])?;
+//! 	Ok(())
+//! }, &out_path)?;
+//!
+//! // the application
+//! use liter::Database;
+//!
+//! // let bytes: &'static [u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dictionary.db"));
+//! # let bytes: &'static [u8] = std::fs::read(&out_path).unwrap().leak();
+//! # std::fs::remove_file(&out_path).unwrap();
+//! let db = Database::<Dictionary>::open_from_bytes(bytes)?;
+//! assert_eq!(
+//! 	db.get::<Word>("liter".to_string())?.unwrap().meaning,
+//! 	"a unit of volume"
+//! );
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rusqlite::{
+	Connection,
+	Error,
+	Result as SqlResult
+};
+
+/// Create a database from its `CREATE` SQL, fill it with `seed` and write it to `out_path`
+///
+/// The database is built in memory and then written out by `VACUUM INTO`, so the file is compact and never in WAL mode, which [`Database::open_from_bytes`](crate::Database::open_from_bytes) requires.
+/// An existing file at `out_path` is replaced.
+pub fn generate<F>(create_sql: &str, seed: F, out_path: &Path) -> SqlResult<()>
+	where F: FnOnce(&Connection) -> SqlResult<()>
+{
+	let connection = Connection::open_in_memory()?;
+	connection.pragma_update(None, "foreign_keys", "on")?;
+	connection.execute_batch(create_sql)?;
+	seed(&connection)?;
+
+	match fs::remove_file(out_path) {
+		Err(e) if e.kind() != io::ErrorKind::NotFound =>
+			return Err(Error::ToSqlConversionFailure(e.into())),
+		_ => {}
+	}
+	let out_path = out_path.to_str()
+		.ok_or_else(|| Error::InvalidPath(out_path.to_path_buf()))?;
+	connection.execute("VACUUM INTO ?", [out_path])?;
+	Ok(())
+}
@@ -0,0 +1,199 @@
+//! Custom collations, i.e. how text is compared & sorted
+//!
+//! A [`Collation`] names a comparison function, which [`register`] (or [`Database::create_collation_typed`]) registers on a connection.
+//! Columns use it through the [`Collated`] wrapper, or by setting [`Column::COLLATION`](crate::Column::COLLATION) (or [`ValueDef::collate`](crate::value::ValueDef::collate)) to its name.
+//!
+//! Collations are registered per connection, and have to be registered before a table using them is accessed.
+//! So register them in the [`Schema::on_open`](crate::Schema::on_open) function, which runs for each connection before it's used (`#[database]` can't set it, so this takes a manual implementation of [`Schema`](crate::Schema)).
+//! In debug builds, opening a database checks that every collation used by the schema is registered, instead of failing on the first query using it.
+//!
+//!```
+//! use std::cmp::Ordering;
+//! use liter::{Database, Id, Schema, Table};
+//! use liter::collation::{self, Collated, Collation};
+//! use liter::table::TableDef;
+//! use liter::util::construe;
+//!
+//! /// Compares numbers in text by their value, so "v10" comes after "v9"
+//! struct Natural;
+//! impl Collation for Natural {
+//! 	const NAME: &'static str = "natural";
+//! 	fn compare(a: &str, b: &str) -> Ordering {
+//! 		let key = |s: &str| {
+//! 			let digits = s.trim_start_matches(|c: char| !c.is_ascii_digit());
+//! 			(s.len() - digits.len(), digits.parse::<u64>().ok(), s.to_string())
+//! 		};
+//! 		key(a).cmp(&key(b))
+//! 	}
+//! }
+//!
+//! struct Db;
+//! impl Schema for Db {
+//! 	type Tables = (Download, );
+//! 	const DEFINITIONS: &'static [TableDef] = &[Download::DEFINITION];
+//! 	const CREATE: &'static str = construe!(
+//! 		&str => liter::schema::define(&[Download::CREATE_TABLE])
+//! 	);
+//! 	fn on_open(connection: &rusqlite::Connection) -> rusqlite::Result<()> {
+//! 		collation::register::<Natural>(connection)
+//! 	}
+//! }
+//!
+//! #[derive(Table)]
+//! struct Download {
+//! 	#[key]
+//! 	id: Id,
+//! 	version: Collated<Natural>
+//! }
+//!
+//! let db = Database::<Db>::create_in_memory()?;
+//! for version in ["v10", "v9", "v1"] {
+//! 	db.insert(&Download { id: Id::NULL, version: Collated::new(version.to_string()) })?;
+//! }
+//! let sorted: Vec<String> = db.query("SELECT version FROM download ORDER BY version", &())?;
+//! assert_eq!(sorted, ["v1", "v9", "v10"]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use rusqlite::{
+	ffi,
+	Connection,
+	Error,
+	Result as SqlResult,
+	ToSql
+};
+use rusqlite::types::{
+	FromSql,
+	FromSqlResult,
+	ToSqlOutput,
+	ValueRef
+};
+
+use crate::{
+	Column,
+	Database,
+	Schema
+};
+use crate::column::Affinity;
+use crate::types::{
+	FromSql2,
+	ToSql2
+};
+use crate::value::{
+	Check,
+	NestedValueDef,
+	ValueDef
+};
+
+/// Comparison function for text, registered under a name
+pub trait Collation {
+	/// Name of the collation, as used in `COLLATE …`
+	const NAME: &'static str;
+	fn compare(a: &str, b: &str) -> Ordering;
+}
+
+/// Register the collation on the connection, e.g. in [`Schema::on_open`](crate::Schema::on_open)
+pub fn register<C: Collation>(connection: &Connection) -> SqlResult<()> {
+	connection.create_collation(C::NAME, C::compare)
+}
+
+impl<S: Schema> Database<S> {
+	/// Register the collation on this database's connection
+	///
+	/// Prefer [`register`]ing it in [`Schema::on_open`](crate::Schema::on_open) if the schema uses it, since that runs before the tables are accessed.
+	pub fn create_collation_typed<C: Collation>(&self) -> SqlResult<()> {
+		register::<C>(self)
+	}
+}
+
+/// Text (or another [`Column`] `T`) compared with the collation `C`
+///
+/// This sets the collation of the column to [`C::NAME`](Collation::NAME).
+pub struct Collated<C: Collation, T = String>(pub T, PhantomData<fn() -> C>);
+
+impl<C: Collation, T> Collated<C, T> {
+	pub fn new(value: T) -> Self {
+		Self(value, PhantomData)
+	}
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<C: Collation, T: Clone> Clone for Collated<C, T> {
+	fn clone(&self) -> Self {
+		Self::new(self.0.clone())
+	}
+}
+impl<C: Collation, T: std::fmt::Debug> std::fmt::Debug for Collated<C, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+/// Compares with `==` in Rust, not with the collation
+impl<C: Collation, T: PartialEq> PartialEq for Collated<C, T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<C: Collation, T: FromSql> FromSql for Collated<C, T> {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		T::column_result(value).map(Self::new)
+	}
+}
+impl<C: Collation, T: ToSql> ToSql for Collated<C, T> {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		self.0.to_sql()
+	}
+}
+impl<C: Collation, T: FromSql> FromSql2 for Collated<C, T> {}
+impl<C: Collation, T: ToSql> ToSql2 for Collated<C, T> {}
+
+impl<C: Collation, T: Column> Column for Collated<C, T> {
+	const AFFINITY: Affinity = T::AFFINITY;
+	const CHECKS: &'static [Check] = T::CHECKS;
+	const COLLATION: Option<&'static str> = Some(C::NAME);
+}
+
+/// Check that every collation used by the schema's tables is registered on the connection
+///
+/// SQLite itself only notices a missing collation when preparing a statement that uses it.
+pub(crate) fn check_registered<S: Schema>(connection: &Connection) -> SqlResult<()> {
+	let mut used = Vec::new();
+	for def in S::DEFINITIONS {
+		for (_name, value) in def.values {
+			collect_collations(value, &mut used);
+		}
+	}
+	if used.is_empty() {
+		return Ok(());
+	}
+	let mut stmt = connection.prepare("SELECT name FROM pragma_collation_list")?;
+	let registered = stmt.query_map([], |row| row.get::<_, String>(0))?
+		.collect::<SqlResult<Vec<_>>>()?;
+	// this includes the built-in ones, like NOCASE
+	let is_registered = |name: &str| registered.iter()
+		.any(|known| known.eq_ignore_ascii_case(name));
+	match used.into_iter().find(|name| !is_registered(name)) {
+		Some(missing) => Err(Error::SqliteFailure(
+			ffi::Error::new(ffi::SQLITE_ERROR),
+			Some(format!("no such collation sequence: {missing}"))
+		)),
+		None => Ok(())
+	}
+}
+
+fn collect_collations(def: &ValueDef, used: &mut Vec<&'static str>) {
+	match &def.inner {
+		NestedValueDef::Column(_) => {},
+		NestedValueDef::ColumnWith(_, clauses) => used.extend(clauses.collation),
+		NestedValueDef::Value(inner) => collect_collations(inner, used),
+		NestedValueDef::Values(values) => for (_name, inner) in values.iter() {
+			collect_collations(inner, used);
+		}
+	}
+}
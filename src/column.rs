@@ -1,24 +1,33 @@
 //! Data primitives -- a [`Column`] defined by [`Affinity`] & [`Check`]s
 
+use std::fmt;
+use std::marker::PhantomData;
+
 use construe::StrConstrue;
 use rusqlite::types::{
 	FromSql,
 	ToSql
 };
 
+use crate::Table;
+use crate::table::str_eq;
 use crate::value::{
 	Check,
+	ColumnClauses,
 	StrChain
 };
 use crate::types::{
 	FromSql2,
 	ToSql2
 };
+use crate::types::flags::push_int;
 
 pub trait Column: FromSql + ToSql + FromSql2 + ToSql2 {
 	const AFFINITY: Affinity;
 	const NULLABLE: bool = false;
 	const CHECKS: &'static [Check] = &[];
+	/// Name of the collation used for comparing values of this column, if not the default (`BINARY`)
+	const COLLATION: Option<&'static str> = None;
 
 	const DEFINITION: ColumnDef = ColumnDef {
 		affinity: Self::AFFINITY,
@@ -27,19 +36,27 @@ pub trait Column: FromSql + ToSql + FromSql2 + ToSql2 {
 	};
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ColumnDef {
 	pub affinity: Affinity,
 	pub nullable: bool,
 	pub checks: &'static [Check]
 }
 
+/// Type of a [`Column`]
+///
+/// In [`STRICT`](https://sqlite.org/stricttables.html) tables (the default), this is enforced: values of any other type are rejected.
+/// In non-strict tables (see [`Table::STRICT`](crate::Table::STRICT)), SQLite only converts values to this type where possible and otherwise stores them as they are, so [`FromSql`] can encounter values of other types.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Affinity {
 	Integer,
 	Real,
 	Text,
 	Blob,
+	/// Values of any type, which are stored exactly as they are (see [`AnyValue`](crate::types::any::AnyValue))
+	///
+	/// This is only meaningful in strict tables: in non-strict ones, a column declared `ANY` has `NUMERIC` affinity.
+	Any,
 }
 
 impl Affinity {
@@ -49,6 +66,7 @@ impl Affinity {
 			Affinity::Real => "REAL",
 			Affinity::Text => "TEXT",
 			Affinity::Blob => "BLOB",
+			Affinity::Any => "ANY",
 		}
 	}
 }
@@ -61,11 +79,12 @@ impl ColumnDef {
 	/// Write out the [`Column`] SQL defintion
 	///
 	/// ```sql
-	/// column_name INTEGER NOT NULL
+	/// column_name TEXT NOT NULL COLLATE NOCASE
 	/// ```
 	pub(crate) const fn push_sql<const N: usize>(
 		&self,
 		name: &StrChain,
+		clauses: &ColumnClauses,
 		mut sc: StrConstrue<N>)
 		-> StrConstrue<N>
 	{
@@ -75,19 +94,191 @@ impl ColumnDef {
 		if !self.nullable {
 			sc = sc.push_str(" NOT NULL");
 		}
+		if let Some(collation) = clauses.collation {
+			sc = sc.push_str(" COLLATE ").push_str(collation);
+		}
 		let mut checks = self.checks;
-		while let [Check::Sql(check), rest @ ..] = checks {
+		while let [check, rest @ ..] = checks {
 			checks = rest;
 			sc = sc.push_str(" CHECK ( ");
-			sc = name.join(sc, "_");
-			sc = sc.push_str(" ");
-			sc = sc.push_str(check);
+			match check {
+				Check::Sql(check) => {
+					sc = name.join(sc, "_");
+					sc = sc.push_str(" ");
+					sc = sc.push_str(check);
+				},
+				Check::Length(length) => {
+					sc = sc.push_str("length(");
+					sc = name.join(sc, "_");
+					sc = sc.push_str(") = ");
+					sc = push_int(sc, *length as i64);
+				},
+				Check::Regex(pattern) => {
+					sc = name.join(sc, "_");
+					sc = sc.push_str(" REGEXP ");
+					sc = push_quoted(sc, pattern);
+				}
+			}
 			sc = sc.push_str(" ) ");
 		}
+		if let Some(default) = clauses.default {
+			sc = sc.push_str(" DEFAULT (").push_str(default).push_str(")");
+		}
+		if let Some(generated) = clauses.generated {
+			sc = sc.push_str(" GENERATED ALWAYS AS (")
+				.push_str(generated.sql)
+				.push_str(match generated.stored {
+					true => ") STORED",
+					false => ") VIRTUAL"
+				});
+		}
 		sc
 	}
 }
 
+/// Push the text as an SQL string literal, i.e. in single quotes, doubling those inside of it
+const fn push_quoted<const N: usize>(mut sc: StrConstrue<N>, text: &str) -> StrConstrue<N> {
+	sc = sc.push_str("'");
+	let bytes = text.as_bytes();
+	let (mut start, mut idx) = (0, 0);
+	while idx < bytes.len() {
+		if bytes[idx] == b'\'' {
+			// split after the quote, which is ASCII, so both parts are valid UTF-8
+			let (part, _) = bytes.split_at(idx + 1);
+			let (_, part) = part.split_at(start);
+			sc = sc.push_str(match std::str::from_utf8(part) {
+				Ok(part) => part,
+				Err(_) => panic!("invalid UTF-8")
+			});
+			sc = sc.push_str("'");
+			start = idx + 1;
+		}
+		idx += 1;
+	}
+	let (_, rest) = bytes.split_at(start);
+	sc = sc.push_str(match std::str::from_utf8(rest) {
+		Ok(rest) => rest,
+		Err(_) => panic!("invalid UTF-8")
+	});
+	sc.push_str("'")
+}
+
+/*
+ *	TYPED HANDLES
+ */
+
+/// Typed handle to a column of the table `T`, which holds values of type `V`
+///
+/// Handles are declared with [`columns!`](crate::columns), which checks their names against [`Table::ALL_COLUMNS`] at compile-time.
+/// Mixing up the tables or the types of values is a type error, e.g. when [`set`](crate::UpdateWhere::set)ting a column to a value with [`eq`](Self::eq).
+pub struct TypedColumn<T, V> {
+	name: &'static str,
+	types: PhantomData<fn() -> (T, V)>
+}
+
+impl<T: Table, V> TypedColumn<T, V> {
+	/// Handle to the column named `name`
+	///
+	/// Panics (at compile-time, when used in a `const`) if `name` isn't one of the [`Table::ALL_COLUMNS`] of `T`.
+	pub const fn new(name: &'static str) -> Self {
+		let mut columns = T::ALL_COLUMNS;
+		while let [column, rest @ ..] = columns {
+			if str_eq(column, name) {
+				return Self { name, types: PhantomData };
+			}
+			columns = rest;
+		}
+		panic!("not a column of the table")
+	}
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+}
+
+impl<T, V: ToSql + 'static> TypedColumn<T, V> {
+	/// Assign the value to the column
+	pub fn eq(self, value: V) -> Assign<T> {
+		Assign {
+			column: self.name,
+			value: Box::new(value),
+			table: PhantomData
+		}
+	}
+}
+
+impl<T, V> Clone for TypedColumn<T, V> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<T, V> Copy for TypedColumn<T, V> {}
+impl<T, V> fmt::Debug for TypedColumn<T, V> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("TypedColumn").field(&self.name).finish()
+	}
+}
+
+/// Value assigned to a column of the table `T`, created with [`TypedColumn::eq`]
+pub struct Assign<T> {
+	pub(crate) column: &'static str,
+	pub(crate) value: Box<dyn ToSql>,
+	table: PhantomData<fn() -> T>
+}
+
+/// Declare [`TypedColumn`] handles to columns of a [`Table`]
+///
+/// This generates a struct with a public handle for each of the given columns, named & typed like the fields of the table, and an associated `columns()` function on the table returning it.
+/// Naming a column that the table doesn't have fails to compile.
+/// Any attributes (like doc comments) are applied to the struct.
+///
+///```
+/// use liter::{columns, Table};
+///
+/// #[derive(Table)]
+/// struct User {
+/// 	name: String,
+/// 	active: bool
+/// }
+/// columns!(struct UserColumns for User { active: bool });
+///
+/// assert_eq!(User::columns().active.name(), "active");
+///```
+///
+///```compile_fail
+/// # use liter::{columns, Table};
+/// # #[derive(Table)]
+/// # struct User { name: String }
+/// columns!(struct UserColumns for User { email: String });
+/// # let _ = User::columns();
+///```
+#[macro_export]
+macro_rules! columns {
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident for $table:ty {
+			$( $column:ident : $ty:ty ),+ $(,)?
+		}
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			$( pub $column: $crate::column::TypedColumn<$table, $ty> ),+
+		}
+		impl $table {
+			/// Typed handles to columns of the table
+			$vis const fn columns() -> $name {
+				$name {
+					$( $column: const {
+						$crate::column::TypedColumn::new(::std::stringify!($column))
+					} ),+
+				}
+			}
+		}
+	};
+}
+
+#[doc(inline)]
+pub use columns;
+
 /*
  *	COLUMNS
  */
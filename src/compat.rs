@@ -0,0 +1,138 @@
+//! Reading & writing databases created by newer versions of an application
+//!
+//! The statements generated for [`Entry`] & [`HasKey`] use `SELECT *` and `INSERT … VALUES` without column names, so they break as soon as a table has a column the [`Table`] doesn't know about, e.g. because a newer version of the application added it.
+//! The `…_by_name` methods here name the [`Table`]'s columns instead, so other columns are ignored when fetching and left to their defaults (or `NULL`) when inserting.
+//!
+//! It also works the other way around, for databases created by older versions of an application that lack some of the [`Table`]'s columns:
+//! those are fetched as `NULL`, which fields of type [`OrDefault`] turn into [`Default::default()`], and left out when inserting.
+//!
+//!```
+//! use liter::{database, Table};
+//! use liter::compat::OrDefault;
+//!
+//! #[database]
+//! struct Db (Item);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Item {
+//! 	name: String,
+//! 	stock: OrDefault<u32>
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! // what a newer version of the application might do
+//! db.execute_batch("ALTER TABLE item ADD COLUMN color TEXT")?;
+//! // what an older version of the application didn't have yet
+//! db.execute_batch("ALTER TABLE item DROP COLUMN stock")?;
+//!
+//! db.insert_by_name(&Item { name: "chair".to_string(), stock: OrDefault(4) })?;
+//! assert_eq!(db.get_all_by_name::<Item>()?, [Item { name: "chair".to_string(), stock: OrDefault(0) }]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Bind,
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	Schema,
+	Table,
+	Value
+};
+use crate::types::Fetcher;
+use crate::value::ValueDef;
+
+/// [`Value`] that is fetched as [`Default::default()`] from `NULL`s, e.g. a column missing from a database created by an older version of the application
+///
+/// Its columns are defined like `T`'s, so they are still `NOT NULL` (unless `T` is an [`Option`]) in a new database.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrDefault<T>(pub T);
+
+impl<T: Value + Default> Value for OrDefault<T> {
+	const DEFINITION: ValueDef = T::DEFINITION;
+	type References = T::References;
+}
+
+impl<T: Bind> Bind for OrDefault<T> {
+	const COLUMNS: usize = T::COLUMNS;
+	fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+		binder.bind(&self.0)
+	}
+}
+
+impl<T: Fetch + Default> Fetch for OrDefault<T> {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		T::try_fetch(fetcher).map(|value| Self(value.unwrap_or_default()))
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		// like for an `Option`, `NULL`s can always be fetched
+		Self::fetch(fetcher).map(Some)
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Like [`get_all`](Self::get_all), but ignoring columns that aren't part of the [`Table`]
+	pub fn get_all_by_name<T: Table + Entry>(&self) -> SqlResult<Vec<T>> {
+		self.query_all(&self.select_by_name::<T>()?)
+	}
+	/// Like [`get`](Self::get), but ignoring columns that aren't part of the [`Table`]
+	pub fn get_by_name<T>(&self, key: &<T as HasKey>::Key) -> SqlResult<Option<T>>
+		where T: Table + Entry + HasKey
+	{
+		let key_filter = T::KEY_COLUMNS.iter()
+			.map(|column| format!("\"{column}\" = ?"))
+			.collect::<Vec<_>>()
+			.join(" AND ");
+		let sql = format!("{} WHERE {key_filter}", self.select_by_name::<T>()?);
+		let mut stmt = self.prepare(&sql)?;
+		Binder::make(&mut stmt).bind(key)?;
+		let mut rows = stmt.raw_query();
+		rows.next()?
+			.map(T::from_row)
+			.transpose()
+	}
+	/// Like [`insert`](Self::insert), but leaving columns that aren't part of the [`Table`] to their defaults
+	///
+	/// Values of columns that are missing from the database are left out.
+	pub fn insert_by_name<T: Table + Entry>(&self, entry: &T) -> SqlResult<usize> {
+		let existing = self.existing_columns(T::NAME)?;
+		let (columns, params): (Vec<_>, Vec<_>) = T::ALL_COLUMNS.iter()
+			.enumerate()
+			.filter(|(_idx, column)| existing.iter().any(|e| e == *column))
+			.map(|(idx, column)| (
+				format!("\"{column}\""),
+				format!("?{}", idx + 1) // params 1-based
+			))
+			.unzip();
+		let sql = format!(
+			"INSERT INTO \"{}\" ({}) VALUES ({})",
+			T::NAME,
+			columns.join(", "),
+			params.join(", ")
+		);
+		let mut stmt = self.prepare(&sql)?;
+		Binder::make_partial(&mut stmt).bind(entry)?;
+		stmt.raw_execute()
+	}
+
+	/// `SELECT "a", "b", … FROM "table"` with the [`Table`]'s columns in order
+	///
+	/// Columns that are missing from the database are selected as `NULL`, which [`OrDefault`] fields fetch as [`Default::default()`].
+	fn select_by_name<T: Table>(&self) -> SqlResult<String> {
+		let existing = self.existing_columns(T::NAME)?;
+		let columns = T::ALL_COLUMNS.iter()
+			.map(|column| match existing.iter().any(|e| e == column) {
+				true => format!("\"{column}\""),
+				false => format!("NULL AS \"{column}\"")
+			})
+			.collect::<Vec<_>>();
+		Ok(format!("SELECT {} FROM \"{}\"", columns.join(", "), T::NAME))
+	}
+	fn existing_columns(&self, table: &str) -> SqlResult<Vec<String>> {
+		self.query_all_with("SELECT name FROM pragma_table_info(?)", &table)
+	}
+}
@@ -0,0 +1,264 @@
+//! Testing durability assumptions by simulating crashes
+//!
+//! This module is only available with the `crashsim` feature, which is meant to be enabled for tests only.
+//!
+//! A [`CrashSim`] runs a workload against copies of a fixture database over and over, "crashing" at each of the workload's [`Crash::point`]s in turn.
+//! A crash is simulated by copying the database file, together with its rollback journal or WAL, as it is at that moment, while the connection is still open.
+//! The copy is then opened (which makes SQLite recover from the journal or WAL, like it would after a crash) and checked:
+//! - `PRAGMA integrity_check` & `PRAGMA foreign_key_check` must not report any problems,
+//! - every [`Table`](crate::Table) of the [`Schema`] must still be readable,
+//! - and the invariants checked by your own `verify` function must hold.
+//!
+//! The copy only contains what SQLite has handed to the operating system at that point, so this simulates the process dying rather than an actual power loss, which could also lose data the OS hasn't flushed.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//! use liter::crashsim::CrashSim;
+//!
+//! #[database]
+//! struct Db (Account);
+//!
+//! #[derive(Table)]
+//! struct Account {
+//! 	#[key]
+//! 	id: Id,
+//! 	balance: i64
+//! }
+//!
+//! let fixture = std::env::temp_dir().join("liter_crashsim_doctest.db");
+//! let _ = std::fs::remove_file(&fixture);
+//! let db = Db::init(&fixture)?;
+//! db.insert(&Account { id: Id::from_i64(1), balance: 100 })?;
+//! db.insert(&Account { id: Id::from_i64(2), balance: 0 })?;
+//! drop(db);
+//!
+//! let tested = CrashSim::<Db>::new(&fixture).run(
+//! 	|db, crash| {
+//! 		let tx = db.transaction()?;
+//! 		tx.execute("UPDATE account SET balance = balance - 10 WHERE id = 1", &())?;
+//! 		crash.point()?;
+//! 		tx.execute("UPDATE account SET balance = balance + 10 WHERE id = 2", &())?;
+//! 		crash.point()?;
+//! 		tx.commit()?;
+//! 		crash.point()
+//! 	},
+//! 	// money is never created or destroyed
+//! 	|db| {
+//! 		let total: i64 = db.query_one("SELECT sum(balance) FROM account")?;
+//! 		assert_eq!(total, 100);
+//! 		Ok(())
+//! 	}
+//! ).unwrap();
+//! assert_eq!(tested, 3);
+//! # std::fs::remove_file(&fixture).unwrap();
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::{
+	Path,
+	PathBuf
+};
+use std::process;
+use std::time::{
+	SystemTime,
+	UNIX_EPOCH
+};
+
+use rusqlite::{
+	ffi,
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Database,
+	Schema
+};
+
+/// Files that make up the on-disk state of a database
+const SUFFIXES: [&str; 3] = ["", "-journal", "-wal"];
+
+/// Runs a workload with simulated crashes, see the [module documentation](self)
+#[derive(Debug)]
+pub struct CrashSim<S: Schema> {
+	fixture: PathBuf,
+	schema: PhantomData<S>
+}
+
+/// Handle passed to the workload to mark the points at which a crash is simulated
+#[derive(Debug)]
+pub struct Crash {
+	/// Index of the point to crash at, or `None` to only count the points
+	target: Option<usize>,
+	seen: usize,
+	database: PathBuf,
+	snapshot: PathBuf
+}
+
+/// A simulated crash after which the database didn't check out
+#[derive(Debug)]
+pub struct CrashFailure {
+	/// Index of the [`Crash::point`] at which the crash was simulated, `None` if the workload failed without crashing
+	pub point: Option<usize>,
+	pub error: Error
+}
+
+impl<S: Schema> CrashSim<S> {
+	/// Simulate crashes starting from a copy of the database file at `fixture` each time
+	pub fn new(fixture: &Path) -> Self {
+		Self { fixture: fixture.to_path_buf(), schema: PhantomData }
+	}
+	/// Run the workload once for each of its crash points, crashing there and then checking the database
+	///
+	/// Returns the number of crash points that were tested.
+	pub fn run<W, V>(&self, workload: W, verify: V) -> Result<usize, CrashFailure>
+		where
+			W: Fn(&mut Database<S>, &mut Crash) -> SqlResult<()>,
+			V: Fn(&Database<S>) -> SqlResult<()>
+	{
+		let dir = temp_dir();
+		let result = self.run_in(&dir, workload, verify);
+		let _ = fs::remove_dir_all(&dir);
+		result
+	}
+
+	fn run_in<W, V>(&self, dir: &Path, workload: W, verify: V)
+		-> Result<usize, CrashFailure>
+		where
+			W: Fn(&mut Database<S>, &mut Crash) -> SqlResult<()>,
+			V: Fn(&Database<S>) -> SqlResult<()>
+	{
+		let fail = |point| move |error| CrashFailure { point, error };
+		fs::create_dir_all(dir).map_err(io_error).map_err(fail(None))?;
+
+		// dry run: count the crash points
+		let mut crash = self.prepare(dir, None).map_err(fail(None))?;
+		let mut db = Database::open(&crash.database).map_err(fail(None))?;
+		workload(&mut db, &mut crash).map_err(fail(None))?;
+		let points = crash.seen;
+		drop(db);
+
+		for point in 0..points {
+			let mut crash = self.prepare(dir, Some(point)).map_err(fail(Some(point)))?;
+			let mut db = Database::open(&crash.database).map_err(fail(Some(point)))?;
+			match workload(&mut db, &mut crash) {
+				Err(e) if is_crash(&e) => {},
+				Err(e) => return Err(fail(Some(point))(e)),
+				// the workload has to take the same path every time
+				Ok(()) => return Err(fail(Some(point))(Error::SqliteFailure(
+					ffi::Error::new(ffi::SQLITE_MISUSE),
+					Some("workload finished without reaching the crash point".to_string())
+				)))
+			}
+			drop(db);
+
+			let recovered = Database::open(&crash.snapshot).map_err(fail(Some(point)))?;
+			check(&recovered)
+				.and_then(|_| verify(&recovered))
+				.map_err(fail(Some(point)))?;
+		}
+		Ok(points)
+	}
+	/// Fresh copy of the fixture to run the workload on
+	fn prepare(&self, dir: &Path, target: Option<usize>) -> SqlResult<Crash> {
+		let run = target.map_or("dry".to_string(), |point| point.to_string());
+		let database = dir.join(format!("run-{run}.db"));
+		let snapshot = dir.join(format!("crash-{run}.db"));
+		for suffix in SUFFIXES {
+			remove_if_exists(&with_suffix(&database, suffix))?;
+			remove_if_exists(&with_suffix(&snapshot, suffix))?;
+		}
+		fs::copy(&self.fixture, &database).map_err(io_error)?;
+		Ok(Crash { target, seen: 0, database, snapshot })
+	}
+}
+
+impl Crash {
+	/// Possibly crash here
+	///
+	/// When it's this point's turn, the database files are copied as they are and a (special) error is returned, which the workload should pass on with `?`.
+	pub fn point(&mut self) -> SqlResult<()> {
+		let point = self.seen;
+		self.seen += 1;
+		if self.target != Some(point) {
+			return Ok(());
+		}
+		for suffix in SUFFIXES {
+			let file = with_suffix(&self.database, suffix);
+			if file.exists() {
+				fs::copy(&file, with_suffix(&self.snapshot, suffix))
+					.map_err(io_error)?;
+			}
+		}
+		Err(crashed())
+	}
+}
+
+impl fmt::Display for CrashFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.point {
+			Some(point) => write!(f, "after crash at point {point}: {}", self.error),
+			None => write!(f, "without crashing: {}", self.error)
+		}
+	}
+}
+impl std::error::Error for CrashFailure {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.error)
+	}
+}
+
+/// Check that the recovered database is intact and every table can be read
+fn check<S: Schema>(db: &Database<S>) -> SqlResult<()> {
+	let integrity: Vec<String> = db.query_all("PRAGMA integrity_check")?;
+	if integrity != ["ok"] {
+		return Err(corrupt(format!("integrity check failed: {integrity:?}")));
+	}
+	let violations: Vec<(String, Option<i64>, String, i64)> =
+		db.query_all("PRAGMA foreign_key_check")?;
+	if !violations.is_empty() {
+		return Err(corrupt(format!("foreign key violations: {violations:?}")));
+	}
+	for def in S::DEFINITIONS {
+		db.query_all::<i64>(&format!("SELECT count(*) FROM {}", def.name))?;
+	}
+	Ok(())
+}
+
+fn crashed() -> Error {
+	Error::SqliteFailure(
+		ffi::Error::new(ffi::SQLITE_ABORT),
+		Some("simulated crash".to_string())
+	)
+}
+fn is_crash(error: &Error) -> bool {
+	matches!(error, Error::SqliteFailure(_, Some(msg)) if msg == "simulated crash")
+}
+fn corrupt(msg: String) -> Error {
+	Error::SqliteFailure(ffi::Error::new(ffi::SQLITE_CORRUPT), Some(msg))
+}
+fn io_error(error: io::Error) -> Error {
+	Error::ToSqlConversionFailure(error.into())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+	let mut path = path.as_os_str().to_owned();
+	path.push(suffix);
+	PathBuf::from(path)
+}
+fn remove_if_exists(path: &Path) -> SqlResult<()> {
+	match fs::remove_file(path) {
+		Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io_error(e)),
+		_ => Ok(())
+	}
+}
+fn temp_dir() -> PathBuf {
+	let nanos = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |since| since.as_nanos());
+	std::env::temp_dir().join(format!("liter-crashsim-{}-{nanos}", process::id()))
+}
@@ -0,0 +1,291 @@
+//! Fetching any [`Deserialize`] type from rows, by column name
+//!
+//! This is for ad-hoc queries (e.g. reports) whose rows aren't worth a [`Table`](crate::Table) or a [`Fetch`](crate::Fetch) impl of their own.
+//! Each row is deserialized as a map from column names to values, so a struct's fields are matched to the columns by name, regardless of their order.
+//! Rows can also be deserialized as tuples or sequences, in which case the columns are taken in order.
+//!
+//!```
+//! use serde::Deserialize;
+//! use liter::{database, Table};
+//!
+//! #[database]
+//! struct Db (Sale);
+//!
+//! #[derive(Table)]
+//! struct Sale {
+//! 	product: String,
+//! 	amount: u32
+//! }
+//!
+//! #[derive(Deserialize, Debug, PartialEq)]
+//! struct Total {
+//! 	product: String,
+//! 	total: u64
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Sale { product: "tea".to_string(), amount: 3 })?;
+//! db.insert(&Sale { product: "tea".to_string(), amount: 4 })?;
+//!
+//! let totals: Vec<Total> = db.query_as(
+//! 	"SELECT sum(amount) AS total, product FROM sale GROUP BY product",
+//! 	&()
+//! )?;
+//! assert_eq!(totals, [Total { product: "tea".to_string(), total: 7 }]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult,
+	Row
+};
+use rusqlite::types::{
+	Type,
+	ValueRef
+};
+use serde::de::{
+	self,
+	Deserialize,
+	DeserializeOwned,
+	DeserializeSeed,
+	IntoDeserializer,
+	MapAccess,
+	SeqAccess,
+	Visitor
+};
+
+use crate::{
+	Bind,
+	Binder,
+	Database,
+	Schema
+};
+
+/// Error while deserializing a row, wrapped in an [`Error::FromSqlConversionFailure`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeError(String);
+
+impl fmt::Display for DeError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+impl std::error::Error for DeError {}
+impl de::Error for DeError {
+	fn custom<T: fmt::Display>(msg: T) -> Self {
+		Self(msg.to_string())
+	}
+}
+
+/// Deserialize a row by column name
+pub fn from_row<T: DeserializeOwned>(row: &Row<'_>) -> SqlResult<T> {
+	T::deserialize(RowDeserializer { row })
+		.map_err(|err| Error::FromSqlConversionFailure(
+			0,
+			Type::Null,
+			Box::new(err)
+		))
+}
+
+impl<S: Schema> Database<S> {
+	/// Run a query and deserialize each of the resulting rows by column name
+	pub fn query_as<T, P>(&self, sql: &str, params: &P) -> SqlResult<Vec<T>>
+		where T: DeserializeOwned, P: Bind
+	{
+		let mut stmt = self.prepare(sql)?;
+		Binder::make(&mut stmt).bind(params)?;
+		let mut items = Vec::new();
+		let mut rows = stmt.raw_query();
+		while let Some(row) = rows.next()? {
+			items.push(from_row(row)?);
+		}
+		Ok(items)
+	}
+}
+
+struct RowDeserializer<'r, 'stmt> {
+	row: &'r Row<'stmt>
+}
+
+struct Columns<'r, 'stmt> {
+	row: &'r Row<'stmt>,
+	names: Vec<&'r str>,
+	idx: usize
+}
+
+struct ValueDeserializer<'r> {
+	value: ValueRef<'r>
+}
+
+impl<'r, 'stmt> Columns<'r, 'stmt> {
+	fn new(row: &'r Row<'stmt>) -> Self {
+		let names = row.as_ref().column_names();
+		Self { row, names, idx: 0 }
+	}
+	fn next_value(&mut self) -> Result<ValueDeserializer<'r>, DeError> {
+		let value = self.row.get_ref(self.idx).map_err(de::Error::custom)?;
+		self.idx += 1;
+		Ok(ValueDeserializer { value })
+	}
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'_, '_> {
+	type Error = DeError;
+
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		self.deserialize_map(visitor)
+	}
+	fn deserialize_map<V: Visitor<'de>>(self, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		visitor.visit_map(Columns::new(self.row))
+	}
+	fn deserialize_struct<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_fields: &'static [&'static str],
+		visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		self.deserialize_map(visitor)
+	}
+	fn deserialize_seq<V: Visitor<'de>>(self, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		visitor.visit_seq(Columns::new(self.row))
+	}
+	fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		self.deserialize_seq(visitor)
+	}
+	fn deserialize_tuple_struct<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_len: usize,
+		visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		self.deserialize_seq(visitor)
+	}
+
+	serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct enum
+		identifier ignored_any
+	}
+}
+
+impl<'de> MapAccess<'de> for Columns<'_, '_> {
+	type Error = DeError;
+
+	fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K)
+		-> Result<Option<K::Value>, DeError>
+	{
+		match self.names.get(self.idx) {
+			Some(&name) => seed.deserialize(name.into_deserializer()).map(Some),
+			None => Ok(None)
+		}
+	}
+	fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V)
+		-> Result<V::Value, DeError>
+	{
+		seed.deserialize(self.next_value()?)
+	}
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.names.len() - self.idx)
+	}
+}
+
+impl<'de> SeqAccess<'de> for Columns<'_, '_> {
+	type Error = DeError;
+
+	fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T)
+		-> Result<Option<T::Value>, DeError>
+	{
+		match self.idx < self.names.len() {
+			true => seed.deserialize(self.next_value()?).map(Some),
+			false => Ok(None)
+		}
+	}
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.names.len() - self.idx)
+	}
+}
+
+impl ValueDeserializer<'_> {
+	fn text(&self) -> Result<Option<&str>, DeError> {
+		match self.value {
+			ValueRef::Text(text) => std::str::from_utf8(text)
+				.map(Some)
+				.map_err(de::Error::custom),
+			_ => Ok(None)
+		}
+	}
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_> {
+	type Error = DeError;
+
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		match self.value {
+			ValueRef::Null => visitor.visit_unit(),
+			ValueRef::Integer(int) => visitor.visit_i64(int),
+			ValueRef::Real(real) => visitor.visit_f64(real),
+			ValueRef::Text(text) => visitor.visit_str(
+				std::str::from_utf8(text).map_err(de::Error::custom)?
+			),
+			ValueRef::Blob(blob) => visitor.visit_bytes(blob)
+		}
+	}
+	fn deserialize_bool<V: Visitor<'de>>(self, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		match self.value {
+			ValueRef::Integer(int) => visitor.visit_bool(int != 0),
+			_ => self.deserialize_any(visitor)
+		}
+	}
+	fn deserialize_option<V: Visitor<'de>>(self, visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		match self.value {
+			ValueRef::Null => visitor.visit_none(),
+			_ => visitor.visit_some(self)
+		}
+	}
+	fn deserialize_newtype_struct<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		visitor.visit_newtype_struct(self)
+	}
+	// unit variants can be stored as their name
+	fn deserialize_enum<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V)
+		-> Result<V::Value, DeError>
+	{
+		match self.text()? {
+			Some(variant) => visitor.visit_enum(variant.into_deserializer()),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	serde::forward_to_deserialize_any! {
+		i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+		identifier ignored_any
+	}
+}
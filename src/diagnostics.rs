@@ -0,0 +1,221 @@
+//! Diagnosing lock contention, i.e. `SQLITE_BUSY` errors
+//!
+//! Each [`Database`] keeps track of its writes: how long they took, where they were called from & whether they failed because the database was locked.
+//! [`Database::lock_diagnostics`] reports these together with the journal mode & WAL size, to find the writes that hold the lock for too long.
+//!
+//! Writes are the [`insert`](Database::insert), [`update`](Database::update), etc. methods, [`execute`](Database::execute), and [`Transaction`](crate::Transaction)s (from `BEGIN` to `COMMIT`/`ROLLBACK`).
+//! The writes of methods that are generic over a [`Table`], like [`delete_where`](Database::delete_where), are also counted per table.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Item);
+//!
+//! #[derive(Table)]
+//! struct Item {
+//! 	#[key]
+//! 	id: Id,
+//! 	data: u64
+//! }
+//!
+//! let mut db = Db::create_in_memory()?;
+//! db.insert(&Item { id: Id::from_i64(1), data: 1 })?;
+//! let tx = db.transaction()?;
+//! tx.update(&Item { id: Id::from_i64(1), data: 2 })?;
+//! tx.commit()?;
+//!
+//! let diagnostics = db.lock_diagnostics()?;
+//! assert_eq!(diagnostics.journal_mode, "memory");
+//! assert_eq!(diagnostics.busy_errors, 0);
+//! let operations: Vec<_> = diagnostics.recent_writes.iter()
+//! 	.map(|write| write.operation)
+//! 	.collect();
+//! assert_eq!(operations, ["insert", "update", "transaction"]);
+//!
+//! db.delete_where::<Item, _>("data > ?", &1)?;
+//! let diagnostics = db.lock_diagnostics()?;
+//! assert_eq!(diagnostics.tables.len(), 1);
+//! assert_eq!(diagnostics.tables[0].table, "item");
+//! assert_eq!(diagnostics.tables[0].writes, 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::collections::{
+	BTreeMap,
+	VecDeque
+};
+use std::fs;
+use std::panic::Location;
+use std::time::{
+	Duration,
+	Instant
+};
+
+use rusqlite::{
+	Error,
+	ErrorCode,
+	Result as SqlResult
+};
+
+use crate::{
+	Database,
+	Schema,
+	Table
+};
+
+/// How many of the most recent writes are kept
+const RECENT_WRITES: usize = 16;
+
+/// Report of [`Database::lock_diagnostics`]
+#[derive(Clone, Debug)]
+pub struct LockDiagnostics {
+	/// `PRAGMA journal_mode`, e.g. `delete` or `wal`
+	pub journal_mode: String,
+	/// Size of the WAL file in bytes, if there is one
+	pub wal_size: Option<u64>,
+	/// Number of writes that failed with `SQLITE_BUSY`
+	pub busy_errors: u64,
+	/// The write that took the longest so far
+	pub longest_write: Option<WriteRecord>,
+	/// The most recent writes, oldest first
+	pub recent_writes: Vec<WriteRecord>,
+	/// Writes per [`Table`], ordered by table name
+	pub tables: Vec<TableWrites>
+}
+
+/// A single write (or transaction) on a [`Database`]
+#[derive(Clone, Copy, Debug)]
+pub struct WriteRecord {
+	/// Name of the method, e.g. `"insert"`, or `"transaction"`
+	pub operation: &'static str,
+	/// Name of the [`Table`] written to, if the method is generic over it
+	pub table: Option<&'static str>,
+	/// Where the method was called
+	pub caller: &'static Location<'static>,
+	pub duration: Duration,
+	/// Whether it failed, in which case nothing was written (a [`Transaction`](crate::Transaction) whose `COMMIT` failed is rolled back)
+	pub failed: bool,
+	/// Whether it failed with `SQLITE_BUSY`
+	pub busy: bool
+}
+
+/// Writes to a single [`Table`], see [`LockDiagnostics::tables`]
+#[derive(Clone, Copy, Debug)]
+pub struct TableWrites {
+	/// [`Table::NAME`]
+	pub table: &'static str,
+	/// Number of writes, including the failed ones
+	pub writes: u64,
+	/// Number of writes that failed
+	pub failed: u64,
+	/// Number of writes that failed with `SQLITE_BUSY`
+	pub busy_errors: u64,
+	/// Time spent in all the writes
+	pub duration: Duration
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct WriteStats {
+	busy_errors: u64,
+	longest: Option<WriteRecord>,
+	recent: VecDeque<WriteRecord>,
+	tables: BTreeMap<&'static str, TableWrites>
+}
+
+impl WriteStats {
+	pub(crate) fn record(&mut self, record: WriteRecord) {
+		if record.busy {
+			self.busy_errors += 1;
+		}
+		if let Some(table) = record.table {
+			let writes = self.tables.entry(table).or_insert(TableWrites {
+				table,
+				writes: 0,
+				failed: 0,
+				busy_errors: 0,
+				duration: Duration::ZERO
+			});
+			writes.writes += 1;
+			writes.failed += record.failed as u64;
+			writes.busy_errors += record.busy as u64;
+			writes.duration += record.duration;
+		}
+		if self.longest.map_or(true, |longest| longest.duration < record.duration) {
+			self.longest = Some(record);
+		}
+		if self.recent.len() == RECENT_WRITES {
+			self.recent.pop_front();
+		}
+		self.recent.push_back(record);
+	}
+}
+
+pub(crate) fn is_busy<T>(result: &SqlResult<T>) -> bool {
+	matches!(
+		result,
+		Err(Error::SqliteFailure(e, _)) if e.code == ErrorCode::DatabaseBusy
+	)
+}
+
+impl<S: Schema> Database<S> {
+	/// Report on the journal & the writes made through this [`Database`]
+	pub fn lock_diagnostics(&self) -> SqlResult<LockDiagnostics> {
+		let journal_mode: String = self.query_one("PRAGMA journal_mode")?;
+		let wal_size = self.connection.path()
+			.filter(|path| !path.is_empty())
+			.and_then(|path| fs::metadata(format!("{path}-wal")).ok())
+			.map(|metadata| metadata.len());
+		let stats = self.writes.borrow();
+		Ok(LockDiagnostics {
+			journal_mode,
+			wal_size,
+			busy_errors: stats.busy_errors,
+			longest_write: stats.longest,
+			recent_writes: stats.recent.iter().copied().collect(),
+			tables: stats.tables.values().copied().collect()
+		})
+	}
+
+	/// Run a write, recording it for [`lock_diagnostics`](Self::lock_diagnostics)
+	#[track_caller]
+	pub(crate) fn track_write<T>(
+		&self,
+		operation: &'static str,
+		write: impl FnOnce() -> SqlResult<T>)
+		-> SqlResult<T>
+	{
+		self.track(operation, None, write)
+	}
+	/// Run a write to the table `W`, recording it for [`lock_diagnostics`](Self::lock_diagnostics) as well as for the table
+	#[track_caller]
+	pub(crate) fn track_table_write<W: Table, T>(
+		&self,
+		operation: &'static str,
+		write: impl FnOnce() -> SqlResult<T>)
+		-> SqlResult<T>
+	{
+		self.track(operation, Some(W::NAME), write)
+	}
+	#[track_caller]
+	fn track<T>(
+		&self,
+		operation: &'static str,
+		table: Option<&'static str>,
+		write: impl FnOnce() -> SqlResult<T>)
+		-> SqlResult<T>
+	{
+		let caller = Location::caller();
+		let start = Instant::now();
+		let result = write();
+		self.writes.borrow_mut().record(WriteRecord {
+			operation,
+			table,
+			caller,
+			duration: start.elapsed(),
+			failed: result.is_err(),
+			busy: is_busy(&result)
+		});
+		result
+	}
+}
@@ -0,0 +1,143 @@
+//! Eager loading: getting an entry together with the entries it [`Ref`](crate::Ref)erences
+//!
+//! [`Database::get_with`] gets an entry and the targets of its references in a single query, by `LEFT JOIN`ing the referenced tables, instead of one more query per reference.
+//! The referenced tables are given as the [`Targets`]: a single [`Table`], or a tuple of them.
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Artist, Label, Album);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Artist {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Label {
+//! 	#[key]
+//! 	name: String
+//! }
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Album {
+//! 	#[key]
+//! 	id: Id,
+//! 	artist: Ref<Artist>,
+//! 	label: Option<Ref<Label>>,
+//! 	title: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! let mut artist = Artist { id: Id::NULL, name: "Nina Simone".to_string() };
+//! db.create(&mut artist)?;
+//! let mut album = Album {
+//! 	id: Id::NULL,
+//! 	artist: Ref::make_ref(&artist),
+//! 	label: None,
+//! 	title: "Pastel Blues".to_string()
+//! };
+//! db.create(&mut album)?;
+//!
+//! let (fetched, (by, label)) = db.get_with::<Album, (Artist, Label)>(&album.id)?.unwrap();
+//! assert_eq!(fetched, album);
+//! assert_eq!(by, Some(artist));
+//! assert_eq!(label, None);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use liter_derive::impl_tuple;
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+use crate::table;
+
+/// [`Table`]s whose entries are loaded by [`Database::get_with`]: a single table, or a tuple of them
+///
+/// Do not implement this trait manually, it is implemented for all suitable types.
+pub trait Targets {
+	/// What the targets are fetched as: an `Option` of each table's entry, which is `None` if the reference is `NULL`
+	type Fetched: Fetch;
+	/// Push the name & key columns of each table
+	#[doc(hidden)]
+	fn push_tables(tables: &mut Vec<(&'static str, &'static [&'static str])>);
+}
+
+impl<T: Table + Entry + HasKey> Targets for T {
+	type Fetched = Option<T>;
+	fn push_tables(tables: &mut Vec<(&'static str, &'static [&'static str])>) {
+		tables.push((T::NAME, T::KEY_COLUMNS));
+	}
+}
+
+#[impl_tuple(2..=16)]
+impl Targets for Each!(T) where Every!(T => T: Table + Entry + HasKey): '_ {
+	type Fetched = Each!(T => Option<T>);
+	fn push_tables(tables: &mut Vec<(&'static str, &'static [&'static str])>) {
+		Each!(T => tables.push((T::NAME, T::KEY_COLUMNS)));
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Get an entry by its primary key, together with the entries of the [`Targets`] it references
+	///
+	/// `T` has to contain exactly one reference to each of the targets (see [`children_of`](Self::children_of)), otherwise this fails with [`Error::InvalidQuery`].
+	pub fn get_with<T, R>(&self, key: &T::Key) -> SqlResult<Option<(T, R::Fetched)>>
+		where
+			T: Table + Entry + HasKey + PartOf<S>,
+			R: Targets
+	{
+		let mut targets = Vec::new();
+		R::push_tables(&mut targets);
+
+		let mut select = vec!["t.*".to_string()];
+		let mut joins = Vec::new();
+		for (idx, (name, key_columns)) in targets.into_iter().enumerate() {
+			let references = table::referencing_columns(&T::DEFINITION, T::ALL_COLUMNS, name);
+			let [columns] = references[..] else {
+				return Err(Error::InvalidQuery);
+			};
+			select.push(format!("r{idx}.*"));
+			joins.push(format!(
+				"LEFT JOIN \"{name}\" AS r{idx} ON ({key}) = ({columns})",
+				key = qualified(&format!("r{idx}"), key_columns),
+				columns = qualified("t", columns)
+			));
+		}
+		let sql = format!(
+			"SELECT {select} FROM \"{table}\" AS t {joins} WHERE ({key}) = ({params})",
+			select = select.join(", "),
+			table = T::NAME,
+			joins = joins.join(" "),
+			key = qualified("t", T::KEY_COLUMNS),
+			params = vec!["?"; T::KEY_COLUMNS.len()].join(", ")
+		);
+
+		let mut stmt = self.prepare(&sql)?;
+		Binder::make(&mut stmt).bind(key)?;
+		let mut rows = stmt.raw_query();
+		rows.next()?
+			.map(<(T, R::Fetched)>::from_row)
+			.transpose()
+	}
+}
+
+fn qualified(table: &str, columns: &[&str]) -> String {
+	columns.iter()
+		.map(|column| format!("{table}.{column}"))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
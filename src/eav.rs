@@ -0,0 +1,193 @@
+//! Entity-attribute-value storage for user-defined custom fields
+//!
+//! Some data can't be modeled as static columns, e.g. custom fields that users of an application define themselves.
+//! The [`attributes!`] macro generates a side table for a [`Table`] (the entity), which stores any number of named attributes per entry as [`AnyValue`]s.
+//! Attributes are deleted along with their entry.
+//!
+//!```
+//! use liter::{attributes, database, Id, Table};
+//!
+//! #[derive(Table)]
+//! struct Product {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//! attributes!(struct ProductAttribute(Product));
+//!
+//! #[database]
+//! struct Db (Product, ProductAttribute);
+//!
+//! let db = Db::create_in_memory()?;
+//! let mut product = Product { id: Id::NULL, name: "Chair".to_string() };
+//! db.create(&mut product)?;
+//!
+//! db.set_attr(&product, "color", &"red")?;
+//! db.set_attr(&product, "legs", &4)?;
+//! db.set_attr(&product, "color", &"blue")?;
+//!
+//! assert_eq!(db.get_attr(&product, "color")?.unwrap().get::<String>()?, "blue");
+//! let names: Vec<String> = db.get_attrs(&product)?.into_iter().map(|(name, _)| name).collect();
+//! assert_eq!(names, ["color", "legs"]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//!```
+
+use rusqlite::Result as SqlResult;
+use rusqlite::types::ToSql;
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+use crate::meta::tuple::CloneFromRef;
+use crate::types::any::AnyValue;
+
+/// [`Table`] storing the attributes of the entries of its [`Entity`](Self::Entity), generated by [`attributes!`]
+///
+/// Its columns are the key columns of the entity, followed by the attribute's `name` (which together make up the primary key), and its `value`.
+pub trait AttributeTable: Table + Entry + HasKey {
+	type Entity: Table + HasKey;
+}
+
+/// [`Table`] with an [`AttributeTable`], implemented by [`attributes!`]
+pub trait HasAttributes: Table + HasKey {
+	type Attributes: AttributeTable<Entity = Self>;
+}
+
+impl<S: Schema> Database<S> {
+	/// Set the attribute of the entry, replacing its previous value
+	#[track_caller]
+	pub fn set_attr<E, V>(&self, entity: &E, name: &str, value: &V) -> SqlResult<()>
+		where
+			E: HasAttributes,
+			E::Key: CloneFromRef<E::Marker>,
+			E::Attributes: PartOf<S>,
+			V: ToSql + ?Sized
+	{
+		let value = AnyValue::new(value)?;
+		self.track_table_write::<E::Attributes, _>("set_attr", || {
+			let mut stmt = self.prepare(E::Attributes::UPSERT)?;
+			let mut binder = Binder::make(&mut stmt);
+			binder.bind(&E::Key::clone_from_ref(entity.get_key()))?;
+			binder.bind(name)?;
+			binder.bind(&value)?;
+			stmt.raw_execute()
+		})?;
+		Ok(())
+	}
+	/// Get the attribute of the entry, if it's set
+	pub fn get_attr<E>(&self, entity: &E, name: &str) -> SqlResult<Option<AnyValue>>
+		where
+			E: HasAttributes,
+			E::Key: CloneFromRef<E::Marker>,
+			E::Attributes: PartOf<S>
+	{
+		let sql = format!(
+			"SELECT value FROM {} WHERE ({}) = ({}) AND name = ?",
+			E::Attributes::NAME,
+			entity_columns::<E::Attributes>().join(", "),
+			params::<E::Attributes>()
+		);
+		let mut stmt = self.prepare(&sql)?;
+		let mut binder = Binder::make(&mut stmt);
+		binder.bind(&E::Key::clone_from_ref(entity.get_key()))?;
+		binder.bind(name)?;
+		let mut rows = stmt.raw_query();
+		rows.next()?
+			.map(AnyValue::from_row)
+			.transpose()
+	}
+	/// Get all attributes of the entry, ordered by their names
+	pub fn get_attrs<E>(&self, entity: &E) -> SqlResult<Vec<(String, AnyValue)>>
+		where
+			E: HasAttributes,
+			E::Key: CloneFromRef<E::Marker>,
+			E::Attributes: PartOf<S>
+	{
+		let sql = format!(
+			"SELECT name, value FROM {} WHERE ({}) = ({}) ORDER BY name",
+			E::Attributes::NAME,
+			entity_columns::<E::Attributes>().join(", "),
+			params::<E::Attributes>()
+		);
+		self.query_all_with(&sql, &E::Key::clone_from_ref(entity.get_key()))
+	}
+	/// Remove the attribute of the entry, returning whether it was set
+	#[track_caller]
+	pub fn remove_attr<E>(&self, entity: &E, name: &str) -> SqlResult<bool>
+		where
+			E: HasAttributes,
+			E::Key: CloneFromRef<E::Marker>,
+			E::Attributes: PartOf<S>
+	{
+		self.track_table_write::<E::Attributes, _>("remove_attr", || {
+			let mut stmt = self.prepare(E::Attributes::DELETE)?;
+			let mut binder = Binder::make(&mut stmt);
+			binder.bind(&E::Key::clone_from_ref(entity.get_key()))?;
+			binder.bind(name)?;
+			stmt.raw_execute()
+		}).map(|changed| changed > 0)
+	}
+}
+
+/// The key columns referencing the entity, i.e. all but the last (`name`) one
+fn entity_columns<A: AttributeTable>() -> &'static [&'static str] {
+	A::KEY_COLUMNS.split_last()
+		.map_or(&[], |(_name, entity)| entity)
+}
+
+fn params<A: AttributeTable>() -> String {
+	vec!["?"; entity_columns::<A>().len()].join(", ")
+}
+
+/// Define an [`AttributeTable`] for the given entity [`Table`]
+///
+/// This generates a struct with the public fields `entity: Ref<…>`, `name: String` and `value: AnyValue`, and implements [`Table`](crate::Table), [`Entry`](crate::Entry) & [`HasKey`](crate::HasKey) for it like `#[derive(Table)]` would.
+/// `entity` and `name` make up the primary key, and the attributes are deleted along with their entity.
+/// Any attributes (like doc comments or other derives) are applied to the struct.
+///
+///```
+/// # use liter::{attributes, Id, Table};
+/// # #[derive(Table)]
+/// # struct Customer { #[key] id: Id }
+/// attributes!(
+///     /// Custom fields of customers
+///     pub struct CustomerField(Customer)
+/// );
+///```
+#[macro_export]
+macro_rules! attributes {
+	($(#[$attr:meta])* $vis:vis struct $name:ident ( $entity:ty ) $(;)?) => {
+		$(#[$attr])*
+		$vis struct $name {
+			pub entity: $crate::Ref<$entity>,
+			pub name: ::std::string::String,
+			pub value: $crate::types::any::AnyValue
+		}
+		$crate::table::table_impls!($name {
+			key: [
+				entity: $crate::Ref<$entity> = <$crate::Ref<$entity> as $crate::Value>::DEFINITION
+					.on_delete($crate::value::FkConflictAction::Cascade),
+				name: ::std::string::String = <::std::string::String as $crate::Value>::DEFINITION
+			],
+			other: [
+				value: $crate::types::any::AnyValue = <$crate::types::any::AnyValue as $crate::Value>::DEFINITION
+			]
+		});
+		impl $crate::eav::AttributeTable for $name {
+			type Entity = $entity;
+		}
+		impl $crate::eav::HasAttributes for $entity {
+			type Attributes = $name;
+		}
+	};
+}
+
+#[doc(inline)]
+pub use attributes;
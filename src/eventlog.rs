@@ -0,0 +1,215 @@
+//! Append-only [`EventLog`]s with monotonic sequence numbers and typed replay
+//!
+//! An event log is an ordinary [`Table`] generated by the [`event_log!`] macro, which stores a sequence number and an event [`Value`].
+//! Optionally, the state that results from applying events can be stored in a snapshot table generated by [`snapshot_table!`], so that replaying only has to start from the latest snapshot.
+//!
+//!```
+//! use liter::{database, Value};
+//! use liter::{event_log, snapshot_table};
+//!
+//! #[derive(Value, Debug, PartialEq)]
+//! enum Event {
+//!     Deposit(u64),
+//!     Withdraw(u64)
+//! }
+//!
+//! event_log!(struct Ledger(Event));
+//! snapshot_table!(struct Balance(u64));
+//!
+//! #[database]
+//! struct Db (Ledger, Balance);
+//!
+//! let db = Db::create_in_memory()?;
+//! let log = db.event_log::<Ledger>();
+//!
+//! log.append(&Event::Deposit(100))?;
+//! let seq = log.append(&Event::Withdraw(30))?;
+//! log.save_snapshot::<Balance>(seq, &70)?;
+//! log.append(&Event::Deposit(5))?;
+//!
+//! let (from, mut balance) = log.latest_snapshot::<Balance>()?.unwrap_or((0, 0));
+//! log.replay_from(from, |_seq, event| match event {
+//!     Event::Deposit(n) => balance += n,
+//!     Event::Withdraw(n) => balance -= n
+//! })?;
+//! assert_eq!(balance, 75);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::marker::PhantomData;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	Id,
+	Schema,
+	Table,
+	Value
+};
+use crate::meta::PartOf;
+use crate::table::HasSingleKey;
+
+/// [`Table`] storing the events of an [`EventLog`], generated by [`event_log!`]
+///
+/// Its columns are the sequence number `seq` (the primary key) followed by the columns of the event.
+pub trait EventTable: Table + Entry + HasSingleKey<Id> {
+	type Event: Value;
+}
+
+/// [`Table`] storing snapshots of the state of an [`EventLog`], generated by [`snapshot_table!`]
+///
+/// Its columns are the sequence number `seq` of the last event applied to the state (the primary key) followed by the columns of the state.
+pub trait SnapshotTable: Table + Entry + HasSingleKey<u64> {
+	type State: Value;
+}
+
+/// Handle to append to and replay an [`EventTable`], created by [`Database::event_log`]
+///
+/// Sequence numbers start at `1` and are strictly increasing, as long as the latest event is never deleted.
+#[derive(Debug)]
+pub struct EventLog<'db, S: Schema, L: EventTable> {
+	db: &'db Database<S>,
+	log: PhantomData<L>
+}
+
+impl<S: Schema> Database<S> {
+	pub fn event_log<L>(&self) -> EventLog<'_, S, L>
+		where L: EventTable + PartOf<S>
+	{
+		EventLog { db: self, log: PhantomData }
+	}
+}
+
+impl<S: Schema, L: EventTable> EventLog<'_, S, L> {
+	/// Append an event and return its sequence number
+	pub fn append(&self, event: &L::Event) -> SqlResult<u64> {
+		let mut stmt = self.db.prepare_cached(L::INSERT)?;
+		let mut binder = Binder::make(&mut stmt);
+		binder.bind(&Id::NULL)?; // assigned by SQLite
+		binder.bind(event)?;
+		stmt.raw_execute()?;
+		Ok(self.db.last_insert_rowid() as u64)
+	}
+	/// Call `apply` with every event after (not including) the sequence number `after`, in order
+	///
+	/// Use `0` to replay all events.
+	/// Returns the sequence number of the last event, or `after` if there were none.
+	pub fn replay_from<F>(&self, after: u64, mut apply: F) -> SqlResult<u64>
+		where F: FnMut(u64, L::Event)
+	{
+		let mut stmt = self.db.prepare(&format!(
+			"SELECT * FROM {} WHERE seq > ? ORDER BY seq",
+			L::NAME
+		))?;
+		Binder::make(&mut stmt).bind(&(after as i64))?;
+		let mut rows = stmt.raw_query();
+		let mut last = after;
+		while let Some(row) = rows.next()? {
+			let (seq, event): (i64, L::Event) = Fetch::from_row(row)?;
+			last = seq as u64;
+			apply(last, event);
+		}
+		Ok(last)
+	}
+	/// Sequence number of the latest event, `0` if there are none
+	pub fn last_seq(&self) -> SqlResult<u64> {
+		let sql = format!("SELECT coalesce(max(seq), 0) FROM {}", L::NAME);
+		self.db.query_one::<i64>(&sql).map(|seq| seq as u64)
+	}
+	/// Delete all events up to (and including) the sequence number `until`
+	///
+	/// This is meant for compacting the log after a snapshot was saved.
+	/// The latest event is always kept, since SQLite would reuse its sequence number otherwise: this fails if `until` is at least [`last_seq`](Self::last_seq).
+	pub fn truncate(&self, until: u64) -> SqlResult<usize> {
+		let last = self.last_seq()?;
+		if last > 0 && until >= last {
+			return Err(Error::ToSqlConversionFailure(format!(
+				"tried to truncate the event log up to {until}, which would delete the latest event {last}"
+			).into()));
+		}
+		self.db.execute_bulk(
+			&format!("DELETE FROM {} WHERE seq <= ?", L::NAME),
+			&(until as i64)
+		)
+	}
+
+	/// Store the state after applying all events up to (and including) `seq`
+	pub fn save_snapshot<P>(&self, seq: u64, state: &P::State) -> SqlResult<()>
+		where P: SnapshotTable + PartOf<S>
+	{
+		let mut stmt = self.db.prepare_cached(P::UPSERT)?;
+		let mut binder = Binder::make(&mut stmt);
+		binder.bind(&seq)?;
+		binder.bind(state)?;
+		stmt.raw_execute()?;
+		Ok(())
+	}
+	/// Latest snapshot and the sequence number to continue replaying from
+	pub fn latest_snapshot<P>(&self) -> SqlResult<Option<(u64, P::State)>>
+		where P: SnapshotTable + PartOf<S>
+	{
+		let sql = format!("SELECT * FROM {} ORDER BY seq DESC LIMIT 1", P::NAME);
+		self.db.query_all::<(u64, P::State)>(&sql)
+			.map(|mut snapshots| snapshots.pop())
+	}
+}
+
+/// Define an [`EventTable`] for the given event [`Value`]
+///
+/// This generates a struct with the public fields `seq: Id` and `event`, as well as `#[derive(Table)]` for it.
+/// Any attributes (like doc comments or other derives) are applied to the struct.
+///
+///```
+/// # use liter::event_log;
+/// event_log!(
+///     /// Log of temperature readings
+///     #[derive(Debug)]
+///     pub struct Readings(f64)
+/// );
+///```
+#[macro_export]
+macro_rules! event_log {
+	($(#[$attr:meta])* $vis:vis struct $name:ident ( $event:ty ) $(;)?) => {
+		$(#[$attr])*
+		#[derive($crate::Table)]
+		$vis struct $name {
+			#[key]
+			pub seq: $crate::Id,
+			pub event: $event
+		}
+		impl $crate::eventlog::EventTable for $name {
+			type Event = $event;
+		}
+	};
+}
+
+/// Define a [`SnapshotTable`] for the given state [`Value`]
+///
+/// This generates a struct with the public fields `seq: u64` and `state`, as well as `#[derive(Table)]` for it.
+#[macro_export]
+macro_rules! snapshot_table {
+	($(#[$attr:meta])* $vis:vis struct $name:ident ( $state:ty ) $(;)?) => {
+		$(#[$attr])*
+		#[derive($crate::Table)]
+		$vis struct $name {
+			#[key]
+			pub seq: u64,
+			pub state: $state
+		}
+		impl $crate::eventlog::SnapshotTable for $name {
+			type State = $state;
+		}
+	};
+}
+
+#[doc(inline)]
+pub use event_log;
+#[doc(inline)]
+pub use snapshot_table;
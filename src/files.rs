@@ -0,0 +1,248 @@
+//! Chunked file storage: streaming files into and out of the database
+//!
+//! This module is only available with the `files` feature.
+//!
+//! Files are split into chunks that are stored as separate rows, so neither writing nor reading a file requires loading it into memory as a whole.
+//! A SHA-256 hash of the contents is stored once a file has been written completely, and checked once it has been read completely.
+//!
+//! To use it, add [`StoredFile`] and [`FileChunk`] to your [`database`](crate::database):
+//!```
+//! use std::io::{Read, Write};
+//! use liter::database;
+//! use liter::files::{FileChunk, StoredFile};
+//!
+//! #[database]
+//! struct Db (StoredFile, FileChunk);
+//!
+//! let db = Db::create_in_memory()?;
+//!
+//! let mut writer = db.write_file("hello.txt")?;
+//! writer.write_all(b"Hello, world!").unwrap();
+//! let file = writer.finish()?;
+//!
+//! let mut contents = String::new();
+//! db.read_file(file.id)?.unwrap().read_to_string(&mut contents).unwrap();
+//! assert_eq!(contents, "Hello, world!");
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::io;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use sha2::{
+	Digest,
+	Sha256
+};
+
+use crate::{
+	Database,
+	HasKey,
+	Id,
+	Ref,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// Default size of a [`FileChunk`] in bytes (64 KiB)
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Metadata of a file stored in the database
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+pub struct StoredFile {
+	#[key]
+	pub id: Id,
+	pub name: String,
+	/// Size of the complete file in bytes
+	pub size: u64,
+	/// SHA-256 hash of the contents, this is `None` until the file has been written completely
+	pub sha256: Option<[u8; 32]>
+}
+
+/// A single chunk of a [`StoredFile`]'s contents
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+pub struct FileChunk {
+	#[key]
+	pub file: Ref<StoredFile>,
+	/// Position of the chunk in the file, starting at `0`
+	#[key]
+	pub seq: u32,
+	pub data: Vec<u8>
+}
+
+/// Streams a new [`StoredFile`] into the database, created by [`Database::write_file`]
+///
+/// Call [`finish`](Self::finish) once everything was written, otherwise the file stays incomplete.
+#[derive(Debug)]
+pub struct FileWriter<'db, S: Schema> {
+	db: &'db Database<S>,
+	file: StoredFile,
+	chunk_size: usize,
+	buffer: Vec<u8>,
+	seq: u32,
+	hasher: Sha256
+}
+
+/// Streams the contents of a [`StoredFile`] out of the database, created by [`Database::read_file`]
+///
+/// If the hash of the contents doesn't match the stored hash, the final `read` returns an [`io::ErrorKind::InvalidData`] error.
+#[derive(Debug)]
+pub struct FileReader<'db, S: Schema> {
+	db: &'db Database<S>,
+	file: StoredFile,
+	chunk: Vec<u8>,
+	position: usize,
+	seq: u32,
+	hasher: Sha256,
+	verified: bool
+}
+
+impl<S: Schema> Database<S>
+	where StoredFile: PartOf<S>,
+		FileChunk: PartOf<S>
+{
+	/// Start writing a new file
+	pub fn write_file(&self, name: &str) -> SqlResult<FileWriter<'_, S>> {
+		let mut file = StoredFile {
+			id: Id::NULL,
+			name: name.to_string(),
+			size: 0,
+			sha256: None
+		};
+		self.create(&mut file)?;
+		Ok(FileWriter {
+			db: self,
+			file,
+			chunk_size: DEFAULT_CHUNK_SIZE,
+			buffer: Vec::new(),
+			seq: 0,
+			hasher: Sha256::new()
+		})
+	}
+	/// Start reading a completely written file
+	///
+	/// Returns `None` if there is no such file, and an error if it is incomplete.
+	pub fn read_file(&self, id: Id) -> SqlResult<Option<FileReader<'_, S>>> {
+		let Some(file) = self.get::<StoredFile>(id)? else {
+			return Ok(None);
+		};
+		if file.sha256.is_none() {
+			return Err(Error::ToSqlConversionFailure(format!(
+				"file {:?} was not written completely",
+				file.name
+			).into()));
+		}
+		Ok(Some(FileReader {
+			db: self,
+			file,
+			chunk: Vec::new(),
+			position: 0,
+			seq: 0,
+			hasher: Sha256::new(),
+			verified: false
+		}))
+	}
+	/// Delete a file and all its chunks
+	///
+	/// Both are deleted inside a savepoint, so a file is never left without some of its chunks.
+	pub fn delete_file(&self, id: Id) -> SqlResult<bool> {
+		self.in_savepoint("liter_delete_file", || {
+			self.execute_bulk(
+				&format!("DELETE FROM {} WHERE file = ?", FileChunk::NAME),
+				&id
+			)?;
+			self.delete::<StoredFile>(&id)
+		})
+	}
+}
+
+impl<S: Schema> FileWriter<'_, S> {
+	/// Set the size of the chunks (in bytes) the file is split into
+	pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+		assert!(chunk_size > 0, "chunk size must not be 0");
+		self.chunk_size = chunk_size;
+		self
+	}
+	/// Write out the remaining buffered bytes and store the hash
+	pub fn finish(mut self) -> SqlResult<StoredFile> {
+		if !self.buffer.is_empty() {
+			self.write_chunk()?;
+		}
+		self.file.sha256 = Some(self.hasher.finalize_reset().into());
+		self.db.update(&self.file)?;
+		Ok(self.file)
+	}
+
+	fn write_chunk(&mut self) -> SqlResult<()> {
+		let size = self.buffer.len().min(self.chunk_size);
+		let data: Vec<u8> = self.buffer.drain(..size).collect();
+		self.hasher.update(&data);
+		self.file.size += data.len() as u64;
+		self.db.insert(&FileChunk {
+			file: self.file.make_ref(),
+			seq: self.seq,
+			data
+		})?;
+		self.seq += 1;
+		Ok(())
+	}
+}
+
+impl<S: Schema> io::Write for FileWriter<'_, S> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.buffer.extend_from_slice(buf);
+		while self.buffer.len() >= self.chunk_size {
+			self.write_chunk().map_err(io::Error::other)?;
+		}
+		Ok(buf.len())
+	}
+	/// Does nothing: only full chunks are written before [`finish`](FileWriter::finish)
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+impl<S: Schema> FileReader<'_, S> {
+	pub fn file(&self) -> &StoredFile {
+		&self.file
+	}
+}
+
+impl<S: Schema> io::Read for FileReader<'_, S> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if self.position == self.chunk.len() {
+			let key = (self.file.make_ref(), self.seq);
+			match self.db.get::<FileChunk>(key).map_err(io::Error::other)? {
+				Some(chunk) => {
+					self.hasher.update(&chunk.data);
+					self.chunk = chunk.data;
+					self.position = 0;
+					self.seq += 1;
+				},
+				None => {
+					// end of file: verify the contents once
+					if !self.verified {
+						self.verified = true;
+						let hash: [u8; 32] = self.hasher.finalize_reset().into();
+						if Some(hash) != self.file.sha256 {
+							return Err(io::Error::new(
+								io::ErrorKind::InvalidData,
+								"file contents do not match the stored hash"
+							));
+						}
+					}
+					return Ok(0);
+				}
+			}
+		}
+		let count = buf.len().min(self.chunk.len() - self.position);
+		buf[..count].copy_from_slice(
+			&self.chunk[self.position..self.position + count]
+		);
+		self.position += count;
+		Ok(count)
+	}
+}
@@ -0,0 +1,240 @@
+//! Full-text search with SQLite's [FTS5](https://sqlite.org/fts5.html) extension
+//!
+//! [`Database::create_fts_index`] indexes some text columns of a [`Table`] in an FTS5 table named `{table}_fts`, which uses the table as its [external content](https://sqlite.org/fts5.html#external_content_tables).
+//! Triggers keep the index up to date when entries are inserted, updated or deleted, so it only has to be created once per database (it's not part of [`Schema::CREATE`]).
+//!
+//! [`Database::search`] then finds the entries matching an FTS5 query, best matches first according to `bm25()`.
+//! Each [`Hit`] can also carry an excerpt of one of the columns, made with `snippet()` or `highlight()`.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Article);
+//!
+//! #[derive(Table)]
+//! struct Article {
+//! 	#[key]
+//! 	id: Id,
+//! 	title: String,
+//! 	body: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.create_fts_index::<Article>(&["title", "body"])?;
+//! db.insert(&Article {
+//! 	id: Id::NULL,
+//! 	title: "Tides".to_string(),
+//! 	body: "The moon pulls the oceans, which causes the tides.".to_string()
+//! })?;
+//! db.insert(&Article {
+//! 	id: Id::NULL,
+//! 	title: "Craters".to_string(),
+//! 	body: "Most craters on the moon are from impacts.".to_string()
+//! })?;
+//!
+//! let hits = db.search::<Article>("tides")
+//! 	.highlight("body", "<b>", "</b>")
+//! 	.fetch()?;
+//! assert_eq!(hits.len(), 1);
+//! assert_eq!(hits[0].entry.title, "Tides");
+//! assert_eq!(
+//! 	hits[0].excerpt.as_deref(),
+//! 	Some("The moon pulls the oceans, which causes the <b>tides</b>.")
+//! );
+//!
+//! let hits = db.search::<Article>("moon").snippet("body", "[", "]", "…", 4).fetch()?;
+//! assert_eq!(hits.len(), 2);
+//! assert!(hits[0].rank <= hits[1].rank);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::marker::PhantomData;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// Name of the FTS5 table indexing the table `T`, i.e. `{table}_fts`
+pub fn index_name<T: Table>() -> String {
+	format!("{}_fts", T::NAME)
+}
+
+/// An entry matching a [`Search`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hit<T> {
+	pub entry: T,
+	/// Result of `bm25()`: the better the match, the lower (i.e. more negative) the rank
+	pub rank: f64,
+	/// Excerpt made by [`Search::snippet`] or [`Search::highlight`], `None` if neither was used
+	pub excerpt: Option<String>
+}
+
+enum Excerpt {
+	Highlight { column: String, open: String, close: String },
+	Snippet { column: String, open: String, close: String, ellipsis: String, tokens: u8 }
+}
+
+/// Builder for a full-text search, see [`Database::search`]
+#[must_use = "the search only runs when calling fetch()"]
+pub struct Search<'d, S: Schema, T> {
+	db: &'d Database<S>,
+	query: String,
+	excerpt: Option<Excerpt>,
+	weights: Vec<f64>,
+	limit: Option<u64>,
+	table: PhantomData<T>
+}
+
+impl<S: Schema> Database<S> {
+	/// Create the FTS5 index of some of the table's columns, if it doesn't exist yet
+	///
+	/// This also indexes the existing entries, and creates the triggers updating the index.
+	/// The columns have to be (text) columns of the table, otherwise this returns [`Error::InvalidColumnName`].
+	pub fn create_fts_index<T>(&self, columns: &[&str]) -> SqlResult<()>
+		where T: Table + PartOf<S>
+	{
+		if let Some(column) = columns.iter().find(|column| !T::ALL_COLUMNS.contains(column)) {
+			return Err(Error::InvalidColumnName(column.to_string()));
+		}
+		let index = index_name::<T>();
+		let exists: bool = self.query_one_with(
+			"SELECT EXISTS (SELECT 1 FROM sqlite_schema WHERE name = ?)",
+			&index.as_str()
+		)?;
+		if exists {
+			return Ok(());
+		}
+		let table = T::NAME;
+		let names = columns.join(", ");
+		let prefixed = |prefix: &str| columns.iter()
+			.map(|column| format!("{prefix}.{column}"))
+			.collect::<Vec<_>>()
+			.join(", ");
+		let (new, old) = (prefixed("new"), prefixed("old"));
+		self.execute_batch(&format!("
+			CREATE VIRTUAL TABLE \"{index}\" USING fts5({names}, content='{table}');
+			CREATE TRIGGER \"{index}_insert\" AFTER INSERT ON \"{table}\" BEGIN
+				INSERT INTO \"{index}\" (rowid, {names}) VALUES (new.rowid, {new});
+			END;
+			CREATE TRIGGER \"{index}_delete\" AFTER DELETE ON \"{table}\" BEGIN
+				INSERT INTO \"{index}\" (\"{index}\", rowid, {names}) VALUES ('delete', old.rowid, {old});
+			END;
+			CREATE TRIGGER \"{index}_update\" AFTER UPDATE ON \"{table}\" BEGIN
+				INSERT INTO \"{index}\" (\"{index}\", rowid, {names}) VALUES ('delete', old.rowid, {old});
+				INSERT INTO \"{index}\" (rowid, {names}) VALUES (new.rowid, {new});
+			END;
+			INSERT INTO \"{index}\" (\"{index}\") VALUES ('rebuild');
+		"))
+	}
+	/// Search the table's FTS5 index (see [`create_fts_index`](Self::create_fts_index)) with an [FTS5 query](https://sqlite.org/fts5.html#full_text_query_syntax)
+	pub fn search<T>(&self, query: &str) -> Search<'_, S, T>
+		where T: Table + Entry + PartOf<S>
+	{
+		Search {
+			db: self,
+			query: query.to_string(),
+			excerpt: None,
+			weights: Vec::new(),
+			limit: None,
+			table: PhantomData
+		}
+	}
+}
+
+impl<S: Schema, T: Table + Entry + PartOf<S>> Search<'_, S, T> {
+	/// Excerpt the column with `snippet()`: at most `tokens` tokens around the matches, with `ellipsis` where the text was cut
+	///
+	/// Matches are surrounded by `open` and `close`.
+	pub fn snippet(self, column: &str, open: &str, close: &str, ellipsis: &str, tokens: u8) -> Self {
+		Self {
+			excerpt: Some(Excerpt::Snippet {
+				column: column.to_string(),
+				open: open.to_string(),
+				close: close.to_string(),
+				ellipsis: ellipsis.to_string(),
+				tokens
+			}),
+			..self
+		}
+	}
+	/// Excerpt the whole column with `highlight()`, surrounding matches by `open` and `close`
+	pub fn highlight(self, column: &str, open: &str, close: &str) -> Self {
+		Self {
+			excerpt: Some(Excerpt::Highlight {
+				column: column.to_string(),
+				open: open.to_string(),
+				close: close.to_string()
+			}),
+			..self
+		}
+	}
+	/// Weigh matches in each of the indexed columns (in the order they were indexed) when ranking with `bm25()`, instead of equally
+	pub fn weights(self, weights: &[f64]) -> Self {
+		Self { weights: weights.to_vec(), ..self }
+	}
+	/// Only fetch the best `limit` hits
+	pub fn limit(self, limit: u64) -> Self {
+		Self { limit: Some(limit), ..self }
+	}
+
+	/// Run the search, returning the hits ordered by rank, best first
+	pub fn fetch(self) -> SqlResult<Vec<Hit<T>>> {
+		let index = index_name::<T>();
+		let weights: String = self.weights.iter()
+			.map(|weight| format!(", {weight:?}"))
+			.collect();
+		let excerpt = match &self.excerpt {
+			None => "NULL".to_string(),
+			Some(Excerpt::Highlight { column, .. }) => format!(
+				"highlight(\"{index}\", {}, ?, ?)",
+				self.column_index(&index, column)?
+			),
+			Some(Excerpt::Snippet { column, tokens, .. }) => format!(
+				"snippet(\"{index}\", {}, ?, ?, ?, {tokens})",
+				self.column_index(&index, column)?
+			)
+		};
+		let sql = format!(
+			"SELECT t.*, bm25(\"{index}\"{weights}) AS liter_rank, {excerpt} \
+			FROM \"{index}\" JOIN \"{table}\" AS t ON t.rowid = \"{index}\".rowid \
+			WHERE \"{index}\" MATCH ? ORDER BY liter_rank LIMIT ?",
+			table = T::NAME
+		);
+		let mut stmt = self.db.prepare(&sql)?;
+		let mut binder = Binder::make(&mut stmt);
+		match &self.excerpt {
+			None => {},
+			Some(Excerpt::Highlight { open, close, .. }) => binder.bind(&(open, close))?,
+			Some(Excerpt::Snippet { open, close, ellipsis, .. }) =>
+				binder.bind(&(open, close, ellipsis))?
+		}
+		binder.bind(&self.query)?;
+		// a negative limit means no limit
+		binder.bind(&self.limit.map_or(-1, |limit| limit as i64))?;
+		let mut hits = Vec::new();
+		let mut rows = stmt.raw_query();
+		while let Some(row) = rows.next()? {
+			let (entry, rank, excerpt) = Fetch::from_row(row)?;
+			hits.push(Hit { entry, rank, excerpt });
+		}
+		Ok(hits)
+	}
+
+	/// Index of the column in the FTS5 table, as used by `snippet()` & `highlight()`
+	fn column_index(&self, index: &str, column: &str) -> SqlResult<i64> {
+		self.db.query_first("SELECT cid FROM pragma_table_info(?) WHERE name = ?", &(index, column))?
+			.ok_or_else(|| Error::InvalidColumnName(column.to_string()))
+	}
+}
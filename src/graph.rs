@@ -0,0 +1,312 @@
+//! Loading entries together with everything they [`Ref`]erence, a few levels deep
+//!
+//! [`Database::load_graph`] fetches the root entries by their keys, then the entries they reference, then the entries *those* reference, and so on, up to a given depth.
+//! Each level takes one query per referenced table (per batch of a few hundred rows), using `IN` on the foreign key columns, instead of one query per [`Ref`].
+//! The result is a [`Graph`] that holds every loaded entry once, in which [`Ref`]s can be [`resolve`](Graph::resolve)d without touching the database again.
+//!
+//! The foreign key columns between the [`Schema`]'s tables are looked up with `pragma_foreign_key_list`.
+//! Each entry is only loaded once, so tables that reference themselves (like a tree of categories) or each other are fine: loading simply stops at `depth`, or when no new entries are found.
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Country, City, Street);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Country {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//! #[derive(Table, Debug, PartialEq)]
+//! struct City {
+//! 	#[key]
+//! 	id: Id,
+//! 	country: Ref<Country>,
+//! 	name: String
+//! }
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Street {
+//! 	#[key]
+//! 	id: Id,
+//! 	city: Ref<City>,
+//! 	name: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Country { id: Id::new(1), name: "Austria".to_string() })?;
+//! db.insert(&City { id: Id::new(1), country: Ref(Id::new(1)), name: "Vienna".to_string() })?;
+//! for (id, name) in [(1, "Ringstraße"), (2, "Mariahilfer Straße")] {
+//! 	db.insert(&Street { id: Id::new(id), city: Ref(Id::new(1)), name: name.to_string() })?;
+//! }
+//!
+//! let graph = db.load_graph::<Street>(&[Id::new(1), Id::new(2)], 2)?;
+//! for street in graph.roots() {
+//! 	let city = graph.resolve(&street.city).unwrap();
+//! 	let country = graph.resolve(&city.country).unwrap();
+//! 	assert_eq!(country.name, "Austria");
+//! }
+//! assert_eq!(graph.all::<City>().len(), 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::any::{
+	Any,
+	TypeId
+};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	Ref,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+use crate::meta::tuple::CloneFromRef;
+
+/// Maximum number of rows (or keys) whose references are loaded with a single query
+const BATCH: usize = 500;
+
+/// Entries loaded by [`Database::load_graph`], starting from roots of type `R`
+pub struct Graph<R> {
+	roots: Vec<i64>,
+	/// [`Nodes<T>`] for each type `T`
+	tables: HashMap<TypeId, Box<dyn Any>>,
+	root: PhantomData<R>
+}
+
+/// Loaded entries of one [`Table`], by `rowid`
+struct Nodes<T> {
+	entries: Vec<T>,
+	rowids: HashMap<i64, usize>
+}
+
+/// Rows loaded by one level of [`Database::load_graph`]: the table's name and the `rowid`s
+type Frontier = Vec<(&'static str, Vec<i64>)>;
+
+/// The [`Schema::Tables`] whose entries can be loaded into a [`Graph`]
+///
+/// Do not implement this trait manually, it is implemented for all suitable [`TableList`](crate::schema::TableList)s.
+pub trait Traverse<S: Schema> {
+	/// Load the entries referenced by the rows in `frontier`, adding the newly loaded rows to `next`
+	#[doc(hidden)]
+	fn load_referenced<R>(
+		db: &Database<S>,
+		graph: &mut Graph<R>,
+		frontier: &Frontier,
+		next: &mut Frontier)
+		-> SqlResult<()>;
+}
+
+impl<S: Schema, T> Traverse<S> for (T, )
+	where T: Table + Entry + 'static
+{
+	fn load_referenced<R>(
+		db: &Database<S>,
+		graph: &mut Graph<R>,
+		frontier: &Frontier,
+		next: &mut Frontier)
+		-> SqlResult<()>
+	{
+		let mut loaded = Vec::new();
+		for (from, rowids) in frontier {
+			load_referenced_from::<S, T, R>(db, graph, from, rowids, &mut loaded)?;
+		}
+		if !loaded.is_empty() {
+			next.push((T::NAME, loaded));
+		}
+		Ok(())
+	}
+}
+impl<S: Schema, T, L> Traverse<S> for (T, L)
+	where
+		T: Table + Entry + 'static,
+		L: Traverse<S>
+{
+	fn load_referenced<R>(
+		db: &Database<S>,
+		graph: &mut Graph<R>,
+		frontier: &Frontier,
+		next: &mut Frontier)
+		-> SqlResult<()>
+	{
+		<(T, )>::load_referenced(db, graph, frontier, next)?;
+		L::load_referenced(db, graph, frontier, next)
+	}
+}
+
+/// Load the entries of `T` that are referenced by the rows of the table `from`
+fn load_referenced_from<S: Schema, T: Table + Entry + 'static, R>(
+	db: &Database<S>,
+	graph: &mut Graph<R>,
+	from: &str,
+	rowids: &[i64],
+	loaded: &mut Vec<i64>)
+	-> SqlResult<()>
+{
+	let foreign_keys: Vec<(i64, String)> = db.query_all_with(
+		"SELECT id, \"from\" FROM pragma_foreign_key_list(?) \
+		WHERE \"table\" = ? ORDER BY id, seq",
+		&(from, T::NAME)
+	)?;
+	let mut columns_by_key: Vec<(i64, Vec<String>)> = Vec::new();
+	for (id, column) in foreign_keys {
+		match columns_by_key.last_mut() {
+			Some((last_id, columns)) if *last_id == id => columns.push(column),
+			_ => columns_by_key.push((id, vec![column]))
+		}
+	}
+
+	for (_id, columns) in columns_by_key {
+		for batch in rowids.chunks(BATCH) {
+			let sql = format!(
+				"SELECT *, rowid FROM {table} WHERE ({key}) IN \
+				(SELECT {columns} FROM {from} WHERE rowid IN ({params}))",
+				table = T::NAME,
+				key = T::KEY_COLUMNS.join(", "),
+				columns = columns.join(", "),
+				params = vec!["?"; batch.len()].join(", ")
+			);
+			let mut stmt = db.prepare(&sql)?;
+			let mut binder = Binder::make(&mut stmt);
+			for rowid in batch {
+				binder.bind(rowid)?;
+			}
+			graph.add_rows::<T>(stmt.raw_query(), loaded)?;
+		}
+	}
+	Ok(())
+}
+
+impl<S: Schema> Database<S> {
+	/// Load the entries with the given keys, and everything they reference up to `depth` levels deep
+	///
+	/// With a `depth` of `0` only the roots are loaded, with `1` also the entries they reference directly, and so on.
+	/// Keys that don't exist are skipped.
+	pub fn load_graph<R>(&self, keys: &[R::Key], depth: usize) -> SqlResult<Graph<R>>
+		where
+			R: Table + Entry + HasKey + PartOf<S> + 'static,
+			S::Tables: Traverse<S>
+	{
+		let mut graph = Graph {
+			roots: Vec::new(),
+			tables: HashMap::new(),
+			root: PhantomData
+		};
+		let key_params = vec![", ?"; R::KEY_COLUMNS.len()].concat();
+		let key_columns: Vec<String> = (0..R::KEY_COLUMNS.len())
+			.map(|idx| format!("liter_keys.k{idx}"))
+			.collect();
+		for batch in keys.chunks(BATCH) {
+			// joined with the numbered keys, so the roots come out in the order of `keys`
+			let sql = format!(
+				"WITH liter_keys(idx, {numbered}) AS (VALUES {params}) \
+				SELECT {table}.*, {table}.rowid FROM liter_keys JOIN {table} \
+				ON ({key}) = ({key_columns}) ORDER BY liter_keys.idx",
+				table = R::NAME,
+				numbered = (0..R::KEY_COLUMNS.len())
+					.map(|idx| format!("k{idx}"))
+					.collect::<Vec<_>>()
+					.join(", "),
+				params = (0..batch.len())
+					.map(|idx| format!("({idx}{key_params})"))
+					.collect::<Vec<_>>()
+					.join(", "),
+				key = R::KEY_COLUMNS.iter()
+					.map(|column| format!("{}.{column}", R::NAME))
+					.collect::<Vec<_>>()
+					.join(", "),
+				key_columns = key_columns.join(", ")
+			);
+			let mut stmt = self.prepare(&sql)?;
+			let mut binder = Binder::make(&mut stmt);
+			for key in batch {
+				binder.bind(key)?;
+			}
+			let mut loaded = Vec::new();
+			graph.add_rows::<R>(stmt.raw_query(), &mut loaded)?;
+			graph.roots.extend(&loaded);
+		}
+
+		let mut frontier = vec![(R::NAME, graph.roots.clone())];
+		for _level in 0..depth {
+			if frontier.is_empty() {
+				break;
+			}
+			let mut next = Vec::new();
+			S::Tables::load_referenced(self, &mut graph, &frontier, &mut next)?;
+			frontier = next;
+		}
+		Ok(graph)
+	}
+}
+
+impl<R: 'static> Graph<R> {
+	/// The root entries, in the order their keys were given
+	pub fn roots(&self) -> impl Iterator<Item = &R> {
+		let nodes = self.nodes::<R>();
+		self.roots.iter()
+			.filter_map(move |rowid| nodes.and_then(|nodes| nodes.get(*rowid)))
+	}
+}
+
+impl<R> Graph<R> {
+	/// All loaded entries of a [`Table`], including the roots if it's their table
+	pub fn all<T: 'static>(&self) -> &[T] {
+		self.nodes::<T>().map_or(&[], |nodes| &nodes.entries)
+	}
+	/// The loaded entry that the [`Ref`] points to, if it was loaded
+	pub fn resolve<T>(&self, reference: &Ref<T>) -> Option<&T>
+		where
+			T: HasKey + 'static,
+			T::Key: PartialEq + CloneFromRef<T::Marker>
+	{
+		self.all::<T>().iter()
+			.find(|entry| T::Key::clone_from_ref(entry.get_key()) == reference.0)
+	}
+
+	fn nodes<T: 'static>(&self) -> Option<&Nodes<T>> {
+		self.tables.get(&TypeId::of::<T>())
+			.and_then(|nodes| nodes.downcast_ref())
+	}
+	/// Add the entries from rows of `SELECT *, rowid`, pushing the `rowid`s of those that weren't loaded yet to `loaded`
+	fn add_rows<T: Table + Entry + 'static>(
+		&mut self,
+		mut rows: rusqlite::Rows<'_>,
+		loaded: &mut Vec<i64>)
+		-> SqlResult<()>
+	{
+		let nodes = self.tables.entry(TypeId::of::<T>())
+			.or_insert_with(|| Box::new(Nodes::<T> {
+				entries: Vec::new(),
+				rowids: HashMap::new()
+			}))
+			.downcast_mut::<Nodes<T>>()
+			.expect("nodes are stored under their own TypeId");
+		while let Some(row) = rows.next()? {
+			let rowid: i64 = row.get(T::ALL_COLUMNS.len())?;
+			if nodes.rowids.contains_key(&rowid) {
+				continue;
+			}
+			nodes.rowids.insert(rowid, nodes.entries.len());
+			nodes.entries.push(T::from_row(row)?);
+			loaded.push(rowid);
+		}
+		Ok(())
+	}
+}
+
+impl<T> Nodes<T> {
+	fn get(&self, rowid: i64) -> Option<&T> {
+		self.rowids.get(&rowid).map(|idx| &self.entries[*idx])
+	}
+}
@@ -0,0 +1,112 @@
+//! Safety net against accidentally changing (or deleting) a whole table
+//!
+//! With [`Database::require_where_for_bulk_ops`] (or [`OpenOptions::require_where_for_bulk_ops`](crate::OpenOptions::require_where_for_bulk_ops)), every statement run by [`execute`](Database::execute) (or [`delete_where`](Database::delete_where)) that changes more rows than the limit is rolled back and results in an [`Error::StatementChangedRows`].
+//! Statements that are meant to change many rows have to be run with [`execute_bulk`](Database::execute_bulk) instead.
+//!
+//! Each statement is run inside a savepoint, so it can be rolled back on its own, even inside a [`Transaction`](crate::Transaction).
+//! Only data manipulation statements (`INSERT`, `UPDATE`, `DELETE` & `REPLACE`) are guarded: others, like `VACUUM` or `PRAGMA foreign_keys`, don't work inside a savepoint and are run as they are.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//! use rusqlite::Error;
+//!
+//! #[database]
+//! struct Db (Item);
+//!
+//! #[derive(Table)]
+//! struct Item {
+//! 	#[key]
+//! 	id: Id,
+//! 	done: bool
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for id in 1..=3 {
+//! 	db.insert(&Item { id: Id::from_i64(id), done: false })?;
+//! }
+//! db.require_where_for_bulk_ops(Some(1));
+//!
+//! // forgot the WHERE
+//! let forgot = db.execute("UPDATE item SET done = 1", &());
+//! assert!(matches!(forgot, Err(Error::StatementChangedRows(3))));
+//! assert_eq!(db.query_one::<i64>("SELECT count(*) FROM item WHERE done")?, 0);
+//!
+//! db.execute("UPDATE item SET done = 1 WHERE id = ?", &Id::from_i64(1))?;
+//! db.execute_bulk("UPDATE item SET done = 1", &())?;
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::{
+	Error,
+	Result as SqlResult,
+	Statement
+};
+
+use crate::{
+	Bind,
+	Binder,
+	Database,
+	Schema
+};
+
+const SAVEPOINT_NAME: &str = "liter_bulk_guard";
+
+impl<S: Schema> Database<S> {
+	/// Reject statements run by [`execute`](Self::execute) that change more than `max_rows` rows, or stop doing so with `None`
+	pub fn require_where_for_bulk_ops(&self, max_rows: Option<usize>) {
+		self.bulk_limit.set(max_rows);
+	}
+	/// Like [`execute`](Self::execute), but allowed to change any number of rows
+	#[track_caller]
+	pub fn execute_bulk<T: Bind>(&self, sql: &str, params: &T) -> SqlResult<usize> {
+		self.track_write("execute_bulk", || {
+			let mut stmt = self.prepare(sql)?;
+			Binder::make(&mut stmt).bind(params)?;
+			stmt.raw_execute()
+		})
+	}
+
+	/// Execute the prepared (and bound) statement for the `sql`, rolling it back if it changed more rows than allowed by [`require_where_for_bulk_ops`](Self::require_where_for_bulk_ops)
+	pub(crate) fn guard_bulk(&self, sql: &str, stmt: &mut Statement<'_>)
+		-> SqlResult<usize>
+	{
+		let Some(max_rows) = self.bulk_limit.get() else {
+			return stmt.raw_execute();
+		};
+		if stmt.readonly() || !is_dml(sql) {
+			return stmt.raw_execute();
+		}
+		self.execute_batch(&format!("SAVEPOINT {SAVEPOINT_NAME}"))?;
+		let changed = match stmt.raw_execute() {
+			Ok(changed) if changed <= max_rows => {
+				self.execute_batch(&format!("RELEASE {SAVEPOINT_NAME}"))?;
+				return Ok(changed);
+			},
+			Ok(changed) => Err(Error::StatementChangedRows(changed)),
+			Err(e) => Err(e)
+		};
+		self.execute_batch(&format!(
+			"ROLLBACK TO {SAVEPOINT_NAME}; RELEASE {SAVEPOINT_NAME}"
+		))?;
+		changed
+	}
+}
+
+/// Whether the SQL is a data manipulation statement (possibly with a `WITH` clause), ignoring leading comments
+fn is_dml(sql: &str) -> bool {
+	let mut sql = sql.trim_start();
+	loop {
+		if let Some(rest) = sql.strip_prefix("--") {
+			sql = rest.split_once('\n').map_or("", |(_comment, rest)| rest).trim_start();
+		} else if let Some(rest) = sql.strip_prefix("/*") {
+			sql = rest.split_once("*/").map_or("", |(_comment, rest)| rest).trim_start();
+		} else {
+			break;
+		}
+	}
+	let keyword = sql.split(|c: char| !c.is_ascii_alphabetic())
+		.next()
+		.unwrap_or_default();
+	["INSERT", "UPDATE", "DELETE", "REPLACE", "WITH"].iter()
+		.any(|dml| keyword.eq_ignore_ascii_case(dml))
+}
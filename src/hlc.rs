@@ -0,0 +1,136 @@
+//! Hybrid logical clock for ordering events across processes
+//!
+//! An [`Hlc`] timestamp combines the wall-clock time in milliseconds with a counter, so it is both close to the real time and strictly increasing, even if the wall clock jumps backwards.
+//! The last timestamp handed out is stored in the [`ClockState`] table, which has to be part of your [`database`](crate::database), so all processes sharing the database file share one clock.
+//!
+//!```
+//! use liter::database;
+//! use liter::hlc::ClockState;
+//!
+//! #[database]
+//! struct Db (ClockState);
+//!
+//! let db = Db::create_in_memory()?;
+//! let first = db.now_hlc()?;
+//! let second = db.now_hlc()?;
+//! assert!(first < second);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlError,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+
+use crate::{
+	Column,
+	Database,
+	Schema,
+	Table
+};
+use crate::column::Affinity;
+use crate::meta::PartOf;
+
+/// Number of bits of the [`Hlc`] used by the counter
+const COUNTER_BITS: u32 = 16;
+
+/// Current time as UNIX timestamp in milliseconds, evaluated by SQLite
+const NOW_MS: &str = "CAST((julianday('now') - 2440587.5) * 86400000.0 AS INTEGER)";
+
+/// Hybrid logical clock timestamp
+///
+/// The upper 48 bits are the physical time in milliseconds since the UNIX epoch, the lower 16 bits a counter that is incremented when several timestamps are handed out in the same millisecond (or the clock went backwards).
+/// Timestamps are ordered by their physical time first, then by counter.
+///
+/// Stored as a single `INTEGER` column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hlc(u64);
+
+/// Last [`Hlc`] handed out by [`Database::now_hlc`]
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+#[check("clock = 0")]
+pub struct ClockState {
+	/// Always `0`, so that there is only one row
+	#[key]
+	pub clock: u8,
+	pub last: Hlc
+}
+
+impl Hlc {
+	pub const fn new(physical_ms: u64, counter: u16) -> Self {
+		Self((physical_ms << COUNTER_BITS) | counter as u64)
+	}
+	/// Milliseconds since the UNIX epoch
+	pub const fn physical_ms(self) -> u64 {
+		self.0 >> COUNTER_BITS
+	}
+	pub const fn counter(self) -> u16 {
+		self.0 as u16
+	}
+	pub const fn as_u64(self) -> u64 {
+		self.0
+	}
+	pub const fn from_u64(hlc: u64) -> Self {
+		Self(hlc)
+	}
+}
+
+impl fmt::Display for Hlc {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}.{:05}", self.physical_ms(), self.counter())
+	}
+}
+
+impl FromSql for Hlc {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		let hlc = i64::column_result(value)?;
+		u64::try_from(hlc)
+			.map(Self)
+			.map_err(|_| FromSqlError::OutOfRange(hlc))
+	}
+}
+impl ToSql for Hlc {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		self.0.to_sql()
+	}
+}
+crate::types::impl_from_to_sql_2!(Hlc);
+
+impl Column for Hlc {
+	const AFFINITY: Affinity = Affinity::Integer;
+}
+
+impl<S: Schema> Database<S>
+	where ClockState: PartOf<S>
+{
+	/// Hand out a new timestamp that is greater than all previous ones
+	///
+	/// The timestamp is the current time (according to SQLite) if that is greater than the last one, otherwise the last one incremented by one.
+	pub fn now_hlc(&self) -> SqlResult<Hlc> {
+		self.advance_hlc(Hlc(0))
+	}
+	/// Advance the clock past a timestamp received from elsewhere (e.g. another database during sync) and hand out a new timestamp
+	///
+	/// The returned timestamp is greater than both `remote` and all previously handed out ones.
+	pub fn observe_hlc(&self, remote: Hlc) -> SqlResult<Hlc> {
+		self.advance_hlc(remote)
+	}
+	fn advance_hlc(&self, remote: Hlc) -> SqlResult<Hlc> {
+		// ?1 = remote
+		let sql = format!(
+			"INSERT INTO {table} VALUES (0, max({NOW_MS} << {COUNTER_BITS}, ?1 + 1)) \
+			ON CONFLICT (clock) DO UPDATE SET \
+			last = max(excluded.last, last + 1) \
+			RETURNING last",
+			table = ClockState::NAME
+		);
+		self.query_one_with(&sql, &remote)
+	}
+}
@@ -0,0 +1,144 @@
+//! Cross-table consistency checks that are run on demand
+//!
+//! `CHECK` constraints can only look at a single row, so rules like "every order's total matches the sum of its items" can't be enforced by SQLite.
+//! An [`Invariant`] expresses such a rule as a query that returns the rows breaking it, so it holds when the query returns no rows.
+//! [`Database::check_invariants`] runs all of the [`Schema::INVARIANTS`](crate::Schema::INVARIANTS), e.g. as a periodic audit.
+//! `#[database]` can't set them, so this takes a manual implementation of [`Schema`].
+//! An invariant that isn't listed can be checked with [`Database::check_invariant`].
+//!
+//!```
+//! use liter::{Database, Id, Ref, Schema, Table};
+//! use liter::invariant::{Invariant, InvariantDef};
+//! use liter::table::TableDef;
+//! use liter::util::construe;
+//!
+//! struct Db;
+//! impl Schema for Db {
+//! 	type Tables = (Order, (Item, ));
+//! 	const DEFINITIONS: &'static [TableDef] = &[Order::DEFINITION, Item::DEFINITION];
+//! 	const CREATE: &'static str = construe!(
+//! 		&str => liter::schema::define(&[Order::CREATE_TABLE, Item::CREATE_TABLE])
+//! 	);
+//! 	const INVARIANTS: &'static [InvariantDef] = &[TotalsMatch::DEFINITION];
+//! }
+//!
+//! #[derive(Table)]
+//! struct Order {
+//! 	#[key]
+//! 	id: Id,
+//! 	total: u64
+//! }
+//! #[derive(Table)]
+//! struct Item {
+//! 	order: Ref<Order>,
+//! 	price: u64
+//! }
+//!
+//! struct TotalsMatch;
+//! impl Invariant for TotalsMatch {
+//! 	const NAME: &'static str = "order totals match their items";
+//! 	const SQL: &'static str = "SELECT o.id, o.total, sum(i.price) FROM \"order\" AS o \
+//! 		JOIN item AS i ON i.\"order\" = o.id \
+//! 		GROUP BY o.id HAVING o.total <> sum(i.price)";
+//! }
+//!
+//! let db = Database::<Db>::create_in_memory()?;
+//! db.insert(&Order { id: Id::new(1), total: 5 })?;
+//! db.insert(&Item { order: Ref(Id::new(1)), price: 2 })?;
+//! db.insert(&Item { order: Ref(Id::new(1)), price: 2 })?;
+//!
+//! let violations = db.check_invariants()?;
+//! assert_eq!(violations.len(), 1);
+//! assert_eq!(violations[0].rows, 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+use rusqlite::types::Value;
+
+use crate::{
+	Database,
+	Schema
+};
+
+/// How many of the rows breaking an invariant are kept as [`Violation::examples`]
+const EXAMPLES: usize = 10;
+
+/// Rule spanning (possibly) multiple tables, given by a query that returns the rows breaking it
+pub trait Invariant {
+	/// Description used in [`Violation`]s
+	const NAME: &'static str;
+	/// `SELECT` statement returning the rows that break the invariant, i.e. none if it holds
+	const SQL: &'static str;
+
+	const DEFINITION: InvariantDef = InvariantDef {
+		name: Self::NAME,
+		sql: Self::SQL
+	};
+}
+
+/// Definition of an [`Invariant`], as listed in [`Schema::INVARIANTS`](crate::Schema::INVARIANTS)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvariantDef {
+	pub name: &'static str,
+	pub sql: &'static str
+}
+
+/// An invariant that doesn't hold
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+	/// [`Invariant::NAME`]
+	pub invariant: &'static str,
+	/// Number of rows breaking the invariant
+	pub rows: usize,
+	/// The first few of those rows
+	pub examples: Vec<Vec<Value>>
+}
+
+impl fmt::Display for Violation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "invariant \"{}\" is broken by {} rows", self.invariant, self.rows)
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Check all of the [`Schema`]'s invariants, returning those that don't hold
+	pub fn check_invariants(&self) -> SqlResult<Vec<Violation>> {
+		let mut violations = Vec::new();
+		for invariant in S::INVARIANTS {
+			violations.extend(self.check_invariant_def(invariant)?);
+		}
+		Ok(violations)
+	}
+	/// Check a single invariant, which doesn't have to be registered with the [`Schema`]
+	pub fn check_invariant<I: Invariant>(&self) -> SqlResult<Option<Violation>> {
+		self.check_invariant_def(&I::DEFINITION)
+	}
+
+	fn check_invariant_def(&self, invariant: &InvariantDef)
+		-> SqlResult<Option<Violation>>
+	{
+		let mut stmt = self.prepare(invariant.sql)?;
+		let columns = stmt.column_count();
+		let mut rows = stmt.raw_query();
+		let mut count = 0;
+		let mut examples = Vec::new();
+		while let Some(row) = rows.next()? {
+			count += 1;
+			if examples.len() < EXAMPLES {
+				examples.push(
+					(0..columns)
+						.map(|idx| row.get(idx))
+						.collect::<SqlResult<_>>()?
+				);
+			}
+		}
+		Ok((count > 0).then_some(Violation {
+			invariant: invariant.name,
+			rows: count,
+			examples
+		}))
+	}
+}
@@ -0,0 +1,104 @@
+//! Joining tables along their [`Ref`](crate::Ref)erences
+//!
+//! [`Database::join`] gets each entry of one table together with the entry of another table that it references, with a single `JOIN`.
+//! It only compiles if the first table actually [`Ref`](crate::Ref)erences the second one (according to its [`Table::References`]), so joining unrelated tables is caught at compile-time.
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Block, Access);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Block {
+//! 	#[key]
+//! 	id: Id,
+//! 	hash: String
+//! }
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Access {
+//! 	#[key]
+//! 	id: Id,
+//! 	block: Ref<Block>,
+//! 	time: i64
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! let mut block = Block { id: Id::NULL, hash: "9f86d0".to_string() };
+//! db.create(&mut block)?;
+//! let access = Access { id: Id::new(1), block: Ref::make_ref(&block), time: 1700000000 };
+//! db.insert(&access)?;
+//!
+//! let pairs: Vec<(Access, Block)> = db.join()?;
+//! assert_eq!(pairs, [(access, block)]);
+//! // or, naming the tables
+//! assert_eq!(db.join::<Access, Block, _>()?.len(), 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+//!
+//! Joining tables the other way around doesn't compile:
+//!```compile_fail
+//! # use liter::{database, Id, Ref, Table};
+//! # #[database]
+//! # struct Db (Block, Access);
+//! # #[derive(Table)]
+//! # struct Block { #[key] id: Id }
+//! # #[derive(Table)]
+//! # struct Access { #[key] id: Id, block: Ref<Block> }
+//! # let db = Db::create_in_memory()?;
+//! let pairs: Vec<(Block, Access)> = db.join()?;
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Database,
+	Entry,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::{
+	Contains,
+	PartOf
+};
+use crate::table;
+
+impl<S: Schema> Database<S> {
+	/// Get all entries of `L` that reference an entry of `R`, together with that entry
+	///
+	/// Entries whose reference is `NULL` are skipped.
+	/// The index `I` is inferred, so the tables can either be named as `join::<L, R, _>()`, or inferred from the result.
+	/// If `L` references `R` more than once, the index can't be inferred, since it's not clear which reference to use.
+	pub fn join<L, R, I>(&self) -> SqlResult<Vec<(L, R)>>
+		where
+			L: Table + Entry + PartOf<S>,
+			L::References: Contains<R, I>,
+			R: Table + Entry + HasKey + PartOf<S>
+	{
+		let references = table::referencing_columns(&L::DEFINITION, L::ALL_COLUMNS, R::NAME);
+		// the bound on References already makes sure there is exactly one
+		let [columns] = references[..] else {
+			return Err(Error::InvalidQuery);
+		};
+		let sql = format!(
+			"SELECT l.*, r.* FROM \"{left}\" AS l JOIN \"{right}\" AS r ON ({key}) = ({columns})",
+			left = L::NAME,
+			right = R::NAME,
+			key = qualified("r", R::KEY_COLUMNS),
+			columns = qualified("l", columns)
+		);
+		self.query_all(&sql)
+	}
+}
+
+fn qualified(table: &str, columns: &[&str]) -> String {
+	columns.iter()
+		.map(|column| format!("{table}.{column}"))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
@@ -0,0 +1,226 @@
+//! Many-to-many relations through a junction table
+//!
+//! A junction table is an ordinary [`Table`] generated by the [`junction!`] macro, which has one [`Ref`](crate::Ref) to each of the two related tables.
+//! Together, the two references are its primary key, and rows are deleted along with either of the entries they relate.
+//!
+//! The macro implements [`Related`] in both directions, so the entries related to one on either side can be looked up with [`Database::related`].
+//!
+//!```
+//! use liter::{database, junction, Id, Table};
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Post {
+//! 	#[key]
+//! 	id: Id,
+//! 	title: String
+//! }
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Tag {
+//! 	#[key]
+//! 	name: String
+//! }
+//! junction!(struct PostTag(post: Post, tag: Tag));
+//!
+//! #[database]
+//! struct Db (Post, Tag, PostTag);
+//!
+//! let db = Db::create_in_memory()?;
+//! let mut post = Post { id: Id::NULL, title: "Hello".to_string() };
+//! db.create(&mut post)?;
+//! for name in ["rust", "sqlite"] {
+//! 	let tag = Tag { name: name.to_string() };
+//! 	db.insert(&tag)?;
+//! 	db.relate::<Post, Tag>(&post.id, &tag.name)?;
+//! }
+//!
+//! assert_eq!(db.related::<Post, Tag>(&post.id)?.len(), 2);
+//! assert_eq!(db.related::<Tag, Post>(&"rust".to_string())?, [post]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// [`Table`] relating entries of two tables, generated by [`junction!`]
+///
+/// Its columns are the key columns of the [`Left`](Self::Left) table's entry, followed by those of the [`Right`](Self::Right) one's.
+pub trait JunctionTable: Table + Entry + HasKey {
+	type Left: Table + Entry + HasKey;
+	type Right: Table + Entry + HasKey;
+}
+
+/// [`Table`] whose entries are related to entries of `T` through a [`JunctionTable`]
+///
+/// This is implemented by [`junction!`] for both of the related tables.
+pub trait Related<T: Table + Entry + HasKey>: Table + Entry + HasKey {
+	type Junction: JunctionTable;
+	/// Whether this is the [`Left`](JunctionTable::Left) table of the [`Junction`](Self::Junction)
+	const IS_LEFT: bool;
+}
+
+impl<S: Schema> Database<S> {
+	/// Get all entries of `B` that are related to the entry of `A` with the key
+	pub fn related<A, B>(&self, key: &A::Key) -> SqlResult<Vec<B>>
+		where
+			A: Related<B>,
+			B: Table + Entry + HasKey + PartOf<S>,
+			A::Junction: PartOf<S>
+	{
+		let (from, to) = junction_columns::<A, B>();
+		let sql = format!(
+			"SELECT {table}.* FROM {table} JOIN {junction} \
+			ON ({key}) = ({to}) WHERE ({from}) = ({params})",
+			table = B::NAME,
+			junction = A::Junction::NAME,
+			key = qualified(B::NAME, B::KEY_COLUMNS),
+			to = qualified(A::Junction::NAME, to),
+			from = qualified(A::Junction::NAME, from),
+			params = vec!["?"; from.len()].join(", ")
+		);
+		let mut stmt = self.prepare(&sql)?;
+		Binder::make(&mut stmt).bind(key)?;
+		let mut rows = stmt.raw_query();
+		let mut entries = Vec::new();
+		while let Some(row) = rows.next()? {
+			entries.push(B::from_row(row)?);
+		}
+		Ok(entries)
+	}
+	/// Relate the entry of `A` with the key `a` to the entry of `B` with the key `b`, returning whether they weren't related yet
+	#[track_caller]
+	pub fn relate<A, B>(&self, a: &A::Key, b: &B::Key) -> SqlResult<bool>
+		where
+			A: Related<B>,
+			B: Table + Entry + HasKey + PartOf<S>,
+			A::Junction: PartOf<S>
+	{
+		self.track_table_write::<A::Junction, _>("relate", || {
+			let mut stmt = self.prepare(A::Junction::UPSERT)?;
+			bind_pair::<A, B>(&mut Binder::make(&mut stmt), a, b)?;
+			stmt.raw_execute()
+		}).map(|changed| changed > 0)
+	}
+	/// Remove the relation between the entry of `A` with the key `a` and the entry of `B` with the key `b`, returning whether they were related
+	#[track_caller]
+	pub fn unrelate<A, B>(&self, a: &A::Key, b: &B::Key) -> SqlResult<bool>
+		where
+			A: Related<B>,
+			B: Table + Entry + HasKey + PartOf<S>,
+			A::Junction: PartOf<S>
+	{
+		self.track_table_write::<A::Junction, _>("unrelate", || {
+			let mut stmt = self.prepare(A::Junction::DELETE)?;
+			bind_pair::<A, B>(&mut Binder::make(&mut stmt), a, b)?;
+			stmt.raw_execute()
+		}).map(|changed| changed > 0)
+	}
+}
+
+/// Key columns of the junction table referencing `A` and `B`
+fn junction_columns<A, B>() -> (&'static [&'static str], &'static [&'static str])
+	where
+		A: Related<B>,
+		B: Table + Entry + HasKey
+{
+	let columns = A::Junction::KEY_COLUMNS;
+	let (left, right) = columns.split_at(
+		<<A::Junction as JunctionTable>::Left as Table>::KEY_COLUMNS.len()
+	);
+	match A::IS_LEFT {
+		true => (left, right),
+		false => (right, left)
+	}
+}
+
+/// Bind the keys in the order of the junction table's columns
+fn bind_pair<A, B>(binder: &mut Binder<'_, '_>, a: &A::Key, b: &B::Key)
+	-> SqlResult<()>
+	where
+		A: Related<B>,
+		B: Table + Entry + HasKey
+{
+	match A::IS_LEFT {
+		true => {
+			binder.bind(a)?;
+			binder.bind(b)
+		},
+		false => {
+			binder.bind(b)?;
+			binder.bind(a)
+		}
+	}
+}
+
+fn qualified(table: &str, columns: &[&str]) -> String {
+	columns.iter()
+		.map(|column| format!("{table}.{column}"))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+/// Define a [`JunctionTable`] relating two [`Table`]s
+///
+/// This generates a struct with a public `Ref` field for each of the tables (with the given names), and implements [`Table`], [`Entry`] & [`HasKey`] for it like `#[derive(Table)]` would.
+/// Both fields make up the primary key, and cascade on delete.
+/// Any attributes (like doc comments or other derives) are applied to the struct.
+///
+/// [`Related`] is implemented for both tables, which means that a table can't be related to itself with this macro.
+///
+///```
+/// # use liter::{junction, Id, Table};
+/// # #[derive(Table)]
+/// # struct Student { #[key] id: Id }
+/// # #[derive(Table)]
+/// # struct Course { #[key] id: Id }
+/// junction!(
+///     /// Which students are enrolled in which courses
+///     pub struct Enrollment(student: Student, course: Course)
+/// );
+///```
+#[macro_export]
+macro_rules! junction {
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident ( $left:ident : $l:ty , $right:ident : $r:ty $(,)? ) $(;)?
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			pub $left: $crate::Ref<$l>,
+			pub $right: $crate::Ref<$r>
+		}
+		$crate::table::table_impls!($name {
+			key: [
+				$left: $crate::Ref<$l> = <$crate::Ref<$l> as $crate::Value>::DEFINITION
+					.on_delete($crate::value::FkConflictAction::Cascade),
+				$right: $crate::Ref<$r> = <$crate::Ref<$r> as $crate::Value>::DEFINITION
+					.on_delete($crate::value::FkConflictAction::Cascade)
+			],
+			other: []
+		});
+		impl $crate::junction::JunctionTable for $name {
+			type Left = $l;
+			type Right = $r;
+		}
+		impl $crate::junction::Related<$r> for $l {
+			type Junction = $name;
+			const IS_LEFT: bool = true;
+		}
+		impl $crate::junction::Related<$l> for $r {
+			type Junction = $name;
+			const IS_LEFT: bool = false;
+		}
+	};
+}
+
+#[doc(inline)]
+pub use junction;
@@ -0,0 +1,169 @@
+//! Electing a single leader among processes sharing the database
+//!
+//! Like the [advisory locks](crate::lock), leadership of a role is a row in a table, the [`Leadership`] table, which has to be part of your [`database`](crate::database).
+//! Unlike a lock, leadership is a lease: the leader has to renew it with [`Election::heartbeat`] before it expires, otherwise any other candidate can take over.
+//! That way, a crashed leader is replaced after at most one lease.
+//!
+//!```
+//! use std::cell::Cell;
+//! use std::time::Duration;
+//! use liter::database;
+//! use liter::leader::{Election, Leadership};
+//!
+//! #[database]
+//! struct Db (Leadership);
+//!
+//! let db = Db::create_in_memory()?;
+//! let lease = Duration::from_secs(10);
+//! let gained = Cell::new(0);
+//!
+//! let mut first = Election::new(&db, "cron", lease)
+//! 	.on_gain(|| gained.set(gained.get() + 1));
+//! let mut second = Election::new(&db, "cron", lease);
+//!
+//! assert!(first.heartbeat()?);
+//! assert!(first.heartbeat()?);
+//! assert!(!second.heartbeat()?);
+//! first.resign()?;
+//! assert!(second.heartbeat()?);
+//! assert_eq!(gained.get(), 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+use std::time::Duration;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema,
+	Table
+};
+use crate::lock::new_owner;
+use crate::meta::PartOf;
+use crate::util::NOW;
+
+/// Current leader of a role
+#[derive(Table, Clone, Debug, PartialEq)]
+pub struct Leadership {
+	#[key]
+	pub role: String,
+	/// Unique identifier of the leading [`Election`]
+	pub leader: String,
+	/// UNIX timestamp after which the lease has expired
+	pub expires: f64
+}
+
+/// Candidate for the leadership of a role
+///
+/// Call [`heartbeat`](Self::heartbeat) regularly, more often than the lease duration, to take over leadership when it's free and keep it once gained.
+/// Leadership is given up when this is dropped.
+pub struct Election<'db, S: Schema> {
+	db: &'db Database<S>,
+	role: String,
+	candidate: String,
+	lease: Duration,
+	leading: bool,
+	on_gain: Option<Box<dyn FnMut() + 'db>>,
+	on_loss: Option<Box<dyn FnMut() + 'db>>
+}
+
+impl<'db, S: Schema> Election<'db, S>
+	where Leadership: PartOf<S>
+{
+	pub fn new(db: &'db Database<S>, role: &str, lease: Duration) -> Self {
+		Self {
+			db,
+			role: role.to_string(),
+			candidate: new_owner(),
+			lease,
+			leading: false,
+			on_gain: None,
+			on_loss: None
+		}
+	}
+	/// Callback for when leadership is gained by [`heartbeat`](Self::heartbeat)
+	pub fn on_gain(mut self, callback: impl FnMut() + 'db) -> Self {
+		self.on_gain = Some(Box::new(callback));
+		self
+	}
+	/// Callback for when leadership is lost, either because another candidate took over after the lease expired or by resigning
+	pub fn on_loss(mut self, callback: impl FnMut() + 'db) -> Self {
+		self.on_loss = Some(Box::new(callback));
+		self
+	}
+	/// Whether this candidate was the leader at the last [`heartbeat`](Self::heartbeat)
+	pub fn is_leader(&self) -> bool {
+		self.leading
+	}
+	/// Renew the lease if leading, or take over leadership if there is no leader or its lease has expired
+	///
+	/// Returns whether this candidate is (now) the leader.
+	pub fn heartbeat(&mut self) -> SqlResult<bool> {
+		let sql = format!(
+			"INSERT INTO {table} VALUES (?1, ?2, {NOW} + ?3) \
+			ON CONFLICT (role) DO UPDATE SET \
+			leader = excluded.leader, expires = excluded.expires \
+			WHERE leader = excluded.leader OR expires < {NOW} \
+			RETURNING leader",
+			table = Leadership::NAME
+		);
+		let params = (
+			self.role.as_str(),
+			self.candidate.as_str(),
+			self.lease.as_secs_f64()
+		);
+		let renewed: Vec<String> = self.db.query_all_with(&sql, &params)?;
+		self.set_leading(!renewed.is_empty());
+		Ok(self.leading)
+	}
+	/// Give up leadership (if leading), so that another candidate can take over right away
+	pub fn resign(&mut self) -> SqlResult<()> {
+		let sql = format!(
+			"DELETE FROM {} WHERE role = ? AND leader = ?",
+			Leadership::NAME
+		);
+		self.db.execute(&sql, &(self.role.as_str(), self.candidate.as_str()))?;
+		self.set_leading(false);
+		Ok(())
+	}
+}
+
+impl<S: Schema> Election<'_, S> {
+	fn set_leading(&mut self, leading: bool) {
+		let callback = match (self.leading, leading) {
+			(false, true) => self.on_gain.as_mut(),
+			(true, false) => self.on_loss.as_mut(),
+			_ => None
+		};
+		self.leading = leading;
+		if let Some(callback) = callback {
+			callback();
+		}
+	}
+}
+
+impl<S: Schema> Drop for Election<'_, S> {
+	fn drop(&mut self) {
+		if self.leading {
+			let sql = format!(
+				"DELETE FROM {} WHERE role = ? AND leader = ?",
+				Leadership::NAME
+			);
+			let params = (self.role.as_str(), self.candidate.as_str());
+			let _ = self.db.execute(&sql, &params);
+		}
+	}
+}
+
+impl<S: Schema> fmt::Debug for Election<'_, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Election")
+			.field("role", &self.role)
+			.field("candidate", &self.candidate)
+			.field("lease", &self.lease)
+			.field("leading", &self.leading)
+			.finish_non_exhaustive()
+	}
+}
@@ -138,27 +138,94 @@
 //!```
 //!
 
+// lets the derive macros be used for the tables defined in this crate
+extern crate self as liter;
 
+pub mod advisor;
+#[cfg(feature = "async")]
+pub mod background;
+pub mod backup;
+pub mod build;
+#[cfg(feature = "async")]
+pub use background::AsyncDatabase;
+pub mod collation;
 pub mod column;
 pub use column::Column;
+pub mod compat;
+#[cfg(feature = "crashsim")]
+pub mod crashsim;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod diagnostics;
+pub mod eager;
+pub mod eav;
+pub mod eventlog;
+#[cfg(feature = "files")]
+pub mod files;
+pub mod fts;
+pub mod graph;
+pub mod guard;
+pub mod hlc;
+pub mod invariant;
+pub mod join;
+pub mod junction;
+pub mod leader;
+pub mod lock;
 pub mod meta;
+pub mod open;
+pub use open::OpenOptions;
+pub mod page;
+pub mod params;
+pub mod prepared;
+pub mod projection;
+pub mod ratelimit;
+pub mod read_only;
+pub use read_only::ReadOnlyDatabase;
+#[cfg(feature = "regex")]
+pub mod regexp;
 pub mod schema;
 pub use schema::Schema;
+#[cfg(feature = "sealed")]
+pub mod sealed;
+pub mod sessions;
+#[cfg(feature = "shell")]
+pub mod shell;
+pub mod shared;
+pub use shared::SyncDatabase;
+pub mod storage;
 pub mod table;
 pub use table::{
 	Entry,
 	HasKey,
 	Table
 };
+pub mod table_fn;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transaction;
+pub use transaction::{
+	Savepoint,
+	Transaction
+};
+pub mod tree;
+pub mod trigger;
 pub mod types;
 pub use types::{
 	Bind,
 	Binder,
 	Fetch
 };
+pub mod update;
+pub use update::UpdateWhere;
+pub mod uri;
 pub mod util;
 pub mod value;
 pub use value::Value;
+pub mod view;
+#[cfg(feature = "vtab")]
+pub mod vtab;
+pub mod wal;
+pub mod watch;
 
 pub use liter_derive::{
 	database,
@@ -166,8 +233,20 @@ pub use liter_derive::{
 	Value
 };
 
+use std::cell::{
+	Cell,
+	RefCell
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{
+	Hash,
+	Hasher
+};
 use std::marker::PhantomData;
+use std::num::ParseIntError;
 use std::path::Path;
+use std::str::FromStr;
 
 use rusqlite::{
 	Connection,
@@ -184,9 +263,17 @@ use rusqlite::types::{
 };
 
 use crate::column::Affinity;
-use crate::meta::tuple::CloneFromRef;
-use crate::table::HasSingleKey;
-use crate::types::Fetcher;
+use crate::diagnostics::WriteStats;
+use crate::meta::tuple::{
+	marker,
+	CloneFromRef
+};
+use crate::types::{
+	FetchRows,
+	Fetcher,
+	FromSql2,
+	ToSql2
+};
 use crate::value::{
 	ForeignKey,
 	ValueDef
@@ -197,24 +284,81 @@ const DB_OPEN_FLAGS: OpenFlags = OpenFlags::SQLITE_OPEN_READ_WRITE
 	.union(OpenFlags::SQLITE_OPEN_URI)
 	.union(OpenFlags::SQLITE_OPEN_NO_MUTEX);
 
+/// Pragmas set on every connection, unless it's opened with [`OpenOptions`] that change them
+const DEFAULT_PRAGMAS: &[(&str, &str)] = &[("foreign_keys", "on")];
+
+/// Default capacity of the prepared statement cache: enough for the generated statements (`GET_ALL`, `INSERT`, `UPSERT`, …) of every table, and at least SQLite's default of 16
+const fn statement_cache_capacity<S: Schema>() -> usize {
+	let capacity = S::DEFINITIONS.len() * 8;
+	if capacity < 16 {16} else {capacity}
+}
+
 #[derive(Debug)]
 pub struct Database<S: Schema> {
 	connection: Connection,
-	schema: PhantomData<S>
+	schema: PhantomData<S>,
+	/// Writes made through this database, for [`Database::lock_diagnostics`]
+	writes: RefCell<WriteStats>,
+	/// See [`Database::require_where_for_bulk_ops`]
+	bulk_limit: Cell<Option<usize>>
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Id(Option<i64>);
+/// Integer primary key, an alias for SQLite's `rowid`
+///
+/// A plain `Id` is the same type for the keys of all tables.
+/// The type parameter can mark which table an `Id` belongs to, so that an `Id<User>` can't be passed where an `Id<Order>` is expected, and [`cast`](Self::cast) converts between them.
+/// `#[derive(Table)]` doesn't support such typed `Id`s as the `#[key]` (yet), so use a plain `Id` there.
+pub struct Id<T: ?Sized = ()>(Option<i64>, PhantomData<fn() -> T>);
+
+/// Key that is an alias for SQLite's `rowid`, which [`Database::create`] assigns, i.e. any [`Id`]
+pub trait RowId: fmt::Debug {
+	fn is_null(&self) -> bool;
+	fn set_rowid(&mut self, rowid: i64);
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Ref<T: HasKey + ?Sized>(pub T::Key);
 
+/// What [`Database::upsert_all`] did with an entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Upserted {
+	/// There was no entry with the key yet
+	Inserted,
+	/// The existing entry with the key was updated
+	Updated,
+	/// The existing entry with the key was left as it was, because the table only consists of its key
+	Unchanged
+}
+
 /* DATABASE */
 
 impl<S: Schema> Database<S> {
 	fn from_connection(connection: Connection) -> SqlResult<Self> {
-		connection.pragma_update(None, "foreign_keys", "on")?;
-		Ok(Self { connection, schema: PhantomData })
+		Self::from_connection_with(connection, DEFAULT_PRAGMAS.iter().copied())
+	}
+	/// Set the pragmas on the connection, followed by the rest of its setup, ending with [`Schema::on_open`]
+	pub(crate) fn from_connection_with<'p>(
+		connection: Connection,
+		pragmas: impl IntoIterator<Item = (&'p str, &'p str)>)
+		-> SqlResult<Self>
+	{
+		for (name, value) in pragmas {
+			connection.pragma_update(None, name, value)?;
+		}
+		connection.set_prepared_statement_cache_capacity(statement_cache_capacity::<S>());
+		#[cfg(feature = "regex")]
+		regexp::register(&connection)?;
+		#[cfg(feature = "series")]
+		rusqlite::vtab::series::load_module(&connection)?;
+		S::on_open(&connection)?;
+		#[cfg(debug_assertions)]
+		collation::check_registered::<S>(&connection)?;
+		Ok(Self {
+			connection,
+			schema: PhantomData,
+			writes: Default::default(),
+			bulk_limit: Cell::new(None)
+		})
 	}
 	/// Open the database at the path
 	pub fn open(path: &Path) -> SqlResult<Self> {
@@ -228,16 +372,44 @@ impl<S: Schema> Database<S> {
 		if path.exists() {
 			return Err(Error::InvalidPath(path.to_path_buf()));
 		}
-		let connection = Connection::open(path)?;
-		connection.pragma_update(None, "foreign_keys", "on")?;
-		connection.execute_batch(S::CREATE)?;
-		Ok(Self { connection, schema: PhantomData })
+		let new = Connection::open(path).and_then(Self::from_connection)?;
+		new.create_schema()?;
+		Ok(new)
 	}
 	pub fn create_in_memory() -> SqlResult<Self> {
 		let new = Connection::open_in_memory().and_then(Self::from_connection)?;
-		new.connection.execute_batch(S::CREATE)?;
+		new.create_schema()?;
 		Ok(new)
 	}
+	/// Create the tables (and views) of the schema, followed by its triggers
+	pub(crate) fn create_schema(&self) -> SqlResult<()> {
+		self.connection.execute_batch(S::CREATE)?;
+		for view in S::VIEWS {
+			self.connection.execute_batch(view.create)?;
+		}
+		for trigger in S::TRIGGERS {
+			self.create_trigger(trigger)?;
+		}
+		Ok(())
+	}
+
+	/// Optimize, checkpoint the WAL (if any) and close the database
+	///
+	/// Unlike dropping it, this reports errors, returning the database back along with the error if it couldn't be closed.
+	pub fn close(self) -> Result<(), (Self, Error)> {
+		let finish = self.connection.execute_batch("PRAGMA optimize;")
+			.and_then(|_| self.connection.query_row(
+				"PRAGMA wal_checkpoint(TRUNCATE)",
+				[],
+				|_row| Ok(())
+			));
+		if let Err(e) = finish {
+			return Err((self, e));
+		}
+		let Self { connection, schema, writes, bulk_limit } = self;
+		connection.close()
+			.map_err(|(connection, e)| (Self { connection, schema, writes, bulk_limit }, e))
+	}
 
 	pub fn debug_show(&self) -> SqlResult<()> {
 		let mut q = self.connection.prepare("SELECT * FROM pragma_table_list")?;
@@ -274,7 +446,7 @@ impl<S: Schema> Database<S> {
 	}
 
 	pub fn get_all<T: Entry>(&self) -> SqlResult<Vec<T>> {
-		let mut stmt = self.connection.prepare(T::GET_ALL)?;
+		let mut stmt = self.connection.prepare_cached(T::GET_ALL)?;
 		let mut rows = stmt.query([])?;
 		let mut entries = Vec::new();
 		while let Some(row) = rows.next()? {
@@ -283,66 +455,399 @@ impl<S: Schema> Database<S> {
 		Ok(entries)
 	}
 
+	/// Like [`get_all`](Self::get_all), but without reading the given columns, e.g. large `BLOB`s when only listing entries
+	///
+	/// Instead, each skipped column is read as `NULL` if it's nullable, and otherwise as the empty (or zero) value of its [`Affinity`].
+	/// So the fields fetched from them get their [`Default`] for the common types, like `Vec<u8>`, `String`, numbers or `Option`s.
+	/// Columns are named as in [`Table::ALL_COLUMNS`], and naming one that isn't part of the table results in an [`Error::InvalidColumnName`].
+	pub fn get_all_without<T>(&self, skip: &[&str]) -> SqlResult<Vec<T>>
+		where T: Table + Entry
+	{
+		let sql = table::select_without(&T::DEFINITION, T::ALL_COLUMNS, skip)
+			.map_err(|column| Error::InvalidColumnName(column.to_string()))?;
+		let mut stmt = self.connection.prepare(&sql)?;
+		let mut rows = stmt.raw_query();
+		let mut entries = Vec::new();
+		while let Some(row) = rows.next()? {
+			entries.push(T::from_row(row)?);
+		}
+		Ok(entries)
+	}
+
+	/// Count the entries of the table, without fetching them
+	pub fn count<T: Table>(&self) -> SqlResult<u64> {
+		let sql = format!("SELECT count(*) FROM \"{}\"", T::NAME);
+		self.query_one::<i64>(&sql).map(|count| count as u64)
+	}
+	/// Count the entries of the table matching the condition, i.e. the SQL following `WHERE`
+	///
+	/// The condition may contain parameters, which are bound to `params`.
+	///
+	///```
+	/// # use liter::{database, Id, Table};
+	/// # #[database]
+	/// # struct Db (Task);
+	/// #[derive(Table)]
+	/// struct Task {
+	/// 	#[key]
+	/// 	id: Id,
+	/// 	done: bool
+	/// }
+	/// # let db = Db::create_in_memory()?;
+	/// for done in [true, false, true] {
+	/// 	db.insert(&Task { id: Id::NULL, done })?;
+	/// }
+	/// assert_eq!(db.count::<Task>()?, 3);
+	/// assert_eq!(db.count_where::<Task, _>("done = ?", &true)?, 2);
+	/// assert!(db.exists::<Task>(&Id::new(3))?);
+	/// assert!(!db.exists::<Task>(&Id::new(4))?);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	pub fn count_where<T, P>(&self, condition: &str, params: &P) -> SqlResult<u64>
+		where T: Table, P: Bind
+	{
+		let sql = format!("SELECT count(*) FROM \"{}\" WHERE {condition}", T::NAME);
+		self.query_one_with::<i64, _>(&sql, params).map(|count| count as u64)
+	}
+	/// Check whether an entry with the key exists, without fetching it
+	pub fn exists<T>(&self, key: &T::Key) -> SqlResult<bool>
+		where T: Table + HasKey
+	{
+		let sql = format!(
+			"SELECT EXISTS (SELECT 1 FROM \"{}\" WHERE ({}) = ({}))",
+			T::NAME,
+			T::KEY_COLUMNS.join(", "),
+			vec!["?"; T::KEY_COLUMNS.len()].join(", ")
+		);
+		self.query_one_with(&sql, key)
+	}
+
 	pub fn get<T>(&self, key: <T as HasKey>::Key) -> SqlResult<Option<T>>
 		where T: Entry + HasKey
 	{
-		let mut stmt = self.connection.prepare(T::GET_BY_KEY)?;
+		let mut stmt = self.connection.prepare_cached(T::GET_BY_KEY)?;
 		Binder::make(&mut stmt).bind(&key)?;
 		let mut rows = stmt.raw_query();
 		rows.next()?
 			.map(T::from_row)
 			.transpose()
 	}
+	/// Get the entry that the [`Ref`] points to
+	pub fn resolve<T>(&self, reference: &Ref<T>) -> SqlResult<Option<T>>
+		where T: Entry + HasKey
+	{
+		let mut stmt = self.connection.prepare_cached(T::GET_BY_KEY)?;
+		Binder::make(&mut stmt).bind(&reference.0)?;
+		let mut rows = stmt.raw_query();
+		rows.next()?
+			.map(T::from_row)
+			.transpose()
+	}
+	/// Get the entries that the [`Ref`]s point to, like [`get_many`](Self::get_many)
+	pub fn resolve_all<T>(&self, references: &[Ref<T>]) -> SqlResult<Vec<T>>
+		where
+			T: Table + Entry + HasKey,
+			T::Key: Eq + Hash + CloneFromRef<T::Marker>
+	{
+		let keys: Vec<&T::Key> = references.iter()
+			.map(|reference| &reference.0)
+			.collect();
+		self.get_by_keys(&keys)
+	}
+	/// Get the entries with the keys, using a single query per few hundred keys
+	///
+	/// The entries are returned in the order of the keys.
+	/// Keys of entries that don't exist are skipped, and an entry whose key is given more than once is only returned once.
+	pub fn get_many<T>(&self, keys: &[T::Key]) -> SqlResult<Vec<T>>
+		where
+			T: Table + Entry + HasKey,
+			T::Key: Eq + Hash + CloneFromRef<T::Marker>
+	{
+		let keys: Vec<&T::Key> = keys.iter().collect();
+		self.get_by_keys(&keys)
+	}
+
+	fn get_by_keys<T>(&self, keys: &[&T::Key]) -> SqlResult<Vec<T>>
+		where
+			T: Table + Entry + HasKey,
+			T::Key: Eq + Hash + CloneFromRef<T::Marker>
+	{
+		/// Maximum number of keys bound to a single statement
+		const BATCH: usize = 500;
+
+		let key_params = format!("({})", vec!["?"; T::KEY_COLUMNS.len()].join(", "));
+		let mut found = HashMap::new();
+		for batch in keys.chunks(BATCH) {
+			let sql = format!(
+				"SELECT * FROM {table} WHERE ({key}) IN (VALUES {params})",
+				table = T::NAME,
+				key = T::KEY_COLUMNS.join(", "),
+				params = vec![key_params.as_str(); batch.len()].join(", ")
+			);
+			let mut stmt = self.connection.prepare(&sql)?;
+			let mut binder = Binder::make(&mut stmt);
+			for key in batch {
+				binder.bind(*key)?;
+			}
+			let mut rows = stmt.raw_query();
+			while let Some(row) = rows.next()? {
+				let entry = T::from_row(row)?;
+				let key = T::Key::clone_from_ref(entry.get_key());
+				found.insert(key, entry);
+			}
+		}
+
+		let mut entries = Vec::with_capacity(found.len());
+		for key in keys {
+			if let Some(entry) = found.remove(*key) {
+				entries.push(entry);
+			}
+		}
+		Ok(entries)
+	}
+
+	/// Get all entries of `C` that [`Ref`]erence the entry of `P` with the key
+	///
+	/// `C` has to contain exactly one reference to `P`, which may be nested inside another value.
+	/// Otherwise, this fails with [`Error::InvalidQuery`], since it's not clear which of them to use.
+	pub fn children_of<C, P>(&self, key: &P::Key) -> SqlResult<Vec<C>>
+		where
+			C: Table + Entry,
+			P: Table + HasKey
+	{
+		let references = table::referencing_columns(&C::DEFINITION, C::ALL_COLUMNS, P::NAME);
+		let [columns] = references[..] else {
+			return Err(Error::InvalidQuery);
+		};
+		let sql = format!(
+			"SELECT * FROM \"{}\" WHERE ({}) = ({})",
+			C::NAME,
+			columns.join(", "),
+			vec!["?"; columns.len()].join(", ")
+		);
+		let mut stmt = self.connection.prepare(&sql)?;
+		Binder::make(&mut stmt).bind(key)?;
+		let mut rows = stmt.raw_query();
+		let mut entries = Vec::new();
+		while let Some(row) = rows.next()? {
+			entries.push(C::from_row(row)?);
+		}
+		Ok(entries)
+	}
 
 	/// Special method to insert and set id to `last_insert_rowid`
+	#[track_caller]
 	pub fn create<T>(&self, entry: &mut T) -> SqlResult<()>
-		where T: Entry + HasSingleKey<Id>
+		where T: Entry + HasKey<Marker = marker::One>, T::Key: RowId
 	{
-		if *entry.get_key() != Id::NULL {
+		if !entry.get_key().is_null() {
 			return Err(Error::ToSqlConversionFailure(format!(
 				"tried to create entry that already had the ID {:?}",
 				*entry.get_key()
 			).into()));
 		}
-		let mut stmt = self.connection.prepare(T::INSERT)?;
-		Binder::make(&mut stmt).bind(&*entry)?;
-		let changes = stmt.raw_execute()?;
-		if changes != 1 {
-			return Err(Error::StatementChangedRows(changes));
-		}
-		let id = self.connection.last_insert_rowid();
-		*entry.get_key_mut() = Id::from_i64(id);
-		Ok(())
+		self.track_write("create", || {
+			let mut stmt = self.connection.prepare_cached(T::INSERT)?;
+			Binder::make_for::<T>(&mut stmt).bind(&*entry)?;
+			let changes = stmt.raw_execute()?;
+			if changes != 1 {
+				return Err(Error::StatementChangedRows(changes));
+			}
+			let id = self.connection.last_insert_rowid();
+			entry.get_key_mut().set_rowid(id);
+			Ok(())
+		})
 	}
 
+	#[track_caller]
 	pub fn insert<T: Entry>(&self, entry: &T) -> SqlResult<usize> {
-		let mut stmt = self.connection.prepare(T::INSERT)?;
-		Binder::make(&mut stmt).bind(entry)?;
-		stmt.raw_execute()
+		self.track_write("insert", || {
+			let mut stmt = self.connection.prepare_cached(T::INSERT)?;
+			Binder::make_for::<T>(&mut stmt).bind(entry)?;
+			stmt.raw_execute()
+		})
+	}
+	/// Insert, returning the entry as it was stored, in a single statement
+	///
+	/// Unlike after [`create`](Self::create), this includes all values that SQLite filled in, like `DEFAULT`s, generated columns and the `rowid` of an [`Id`] that was [`NULL`](Id::NULL).
+	///
+	///```
+	/// # use liter::{database, Id, Table};
+	/// # #[database]
+	/// # struct Db (Note);
+	/// #[derive(Table, Debug, PartialEq)]
+	/// struct Note {
+	/// 	#[key]
+	/// 	id: Id,
+	/// 	text: String
+	/// }
+	/// # let db = Db::create_in_memory()?;
+	/// let note = db.insert_returning(&Note { id: Id::NULL, text: "hello".to_string() })?;
+	/// assert_eq!(note, Note { id: Id::new(1), text: "hello".to_string() });
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	#[track_caller]
+	pub fn insert_returning<T: Entry>(&self, entry: &T) -> SqlResult<T> {
+		let sql = format!("{} RETURNING *", T::INSERT);
+		self.track_write("insert_returning", || {
+			let mut stmt = self.connection.prepare_cached(&sql)?;
+			Binder::make_for::<T>(&mut stmt).bind(entry)?;
+			let mut rows = stmt.raw_query();
+			rows.next()?
+				.ok_or(Error::QueryReturnedNoRows)
+				.and_then(T::from_row)
+		})
+	}
+	/// Insert, but leave out the values with a `DEFAULT` (see [`ValueDef::with_default`](value::ValueDef::with_default)) so SQLite fills them in
+	///
+	/// The values of those fields in `entry` are ignored.
+	#[track_caller]
+	pub fn insert_with_defaults<T>(&self, entry: &T) -> SqlResult<usize>
+		where T: Table + Entry
+	{
+		let sql = table::insert_with_defaults(&T::DEFINITION, T::ALL_COLUMNS);
+		self.track_table_write::<T, _>("insert_with_defaults", || {
+			let mut stmt = self.connection.prepare(&sql)?;
+			Binder::make_partial(&mut stmt).bind(entry)?;
+			stmt.raw_execute()
+		})
 	}
 
+	#[track_caller]
 	pub fn upsert<T: HasKey + Entry>(&self, entry: &T) -> SqlResult<usize> {
-		let mut stmt = self.connection.prepare(T::UPSERT)?;
-		Binder::make(&mut stmt).bind(entry)?;
-		stmt.raw_execute()
+		self.track_write("upsert", || {
+			let mut stmt = self.connection.prepare_cached(T::UPSERT)?;
+			Binder::make_for::<T>(&mut stmt).bind(entry)?;
+			stmt.raw_execute()
+		})
 	}
+	/// Upsert all entries in a single transaction, reusing the prepared statements, returning what was done with each of them
+	///
+	/// If upserting one of them fails, none of them are.
+	/// This also works inside a [`Transaction`], since it uses a savepoint.
+	///
+	///```
+	/// # use liter::{database, Table, Upserted};
+	/// # #[database]
+	/// # struct Db (Rate);
+	/// #[derive(Table)]
+	/// struct Rate {
+	/// 	#[key]
+	/// 	currency: String,
+	/// 	per_euro: f64
+	/// }
+	/// # let db = Db::create_in_memory()?;
+	/// db.insert(&Rate { currency: "USD".to_string(), per_euro: 1.08 })?;
+	/// let outcomes = db.upsert_all(&[
+	/// 	Rate { currency: "USD".to_string(), per_euro: 1.09 },
+	/// 	Rate { currency: "CHF".to_string(), per_euro: 0.94 }
+	/// ])?;
+	/// assert_eq!(outcomes, [Upserted::Updated, Upserted::Inserted]);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	#[track_caller]
+	pub fn upsert_all<T>(&self, entries: &[T]) -> SqlResult<Vec<Upserted>>
+		where
+			T: Table + HasKey + Entry,
+			T::Key: CloneFromRef<T::Marker>
+	{
+		let exists = format!(
+			"SELECT EXISTS (SELECT 1 FROM \"{}\" WHERE ({}) = ({}))",
+			T::NAME,
+			T::KEY_COLUMNS.join(", "),
+			vec!["?"; T::KEY_COLUMNS.len()].join(", ")
+		);
+		self.track_table_write::<T, _>("upsert_all", || {
+			self.in_savepoint("liter_upsert_all", || {
+				let mut exists = self.connection.prepare(&exists)?;
+				let mut upsert = self.connection.prepare_cached(T::UPSERT)?;
+				let mut outcomes = Vec::with_capacity(entries.len());
+				for entry in entries {
+					Binder::make(&mut exists).bind(&T::Key::clone_from_ref(entry.get_key()))?;
+					let existed = exists.raw_query()
+						.next()?
+						.ok_or(Error::QueryReturnedNoRows)
+						.and_then(|row| row.get::<_, bool>(0))?;
+					Binder::make_for::<T>(&mut upsert).bind(entry)?;
+					let changed = upsert.raw_execute()?;
+					outcomes.push(match (existed, changed) {
+						(false, _) => Upserted::Inserted,
+						(true, 0) => Upserted::Unchanged,
+						(true, _) => Upserted::Updated
+					});
+				}
+				Ok(outcomes)
+			})
+		})
+	}
+	#[track_caller]
 	pub fn update<T: HasKey + Entry>(&self, entry: &T) -> SqlResult<usize> {
-		let mut stmt = self.connection.prepare(T::UPDATE)?;
-		Binder::make(&mut stmt).bind(entry)?;
-		stmt.raw_execute()
+		self.track_write("update", || {
+			let mut stmt = self.connection.prepare_cached(T::UPDATE)?;
+			Binder::make_for::<T>(&mut stmt).bind(entry)?;
+			stmt.raw_execute()
+		})
 	}
+	#[track_caller]
 	pub fn delete<T>(&self, key: &<T as HasKey>::Key) -> SqlResult<bool>
 		where T: Entry + HasKey
 	{
-		let mut stmt = self.connection.prepare(T::DELETE)?;
-		Binder::make(&mut stmt).bind(key)?;
-		stmt.raw_execute().map(|i| i == 1)
+		self.track_write("delete", || {
+			let mut stmt = self.connection.prepare_cached(T::DELETE)?;
+			Binder::make(&mut stmt).bind(key)?;
+			stmt.raw_execute().map(|i| i == 1)
+		})
+	}
+	/// Delete all entries of the table, returning how many were deleted
+	///
+	/// Since this is meant to change many rows, it isn't limited by [`require_where_for_bulk_ops`](Self::require_where_for_bulk_ops).
+	#[track_caller]
+	pub fn delete_all<T: Table>(&self) -> SqlResult<usize> {
+		let sql = format!("DELETE FROM \"{}\"", T::NAME);
+		self.track_table_write::<T, _>("delete_all", || self.connection.execute(&sql, []))
+	}
+	/// Delete the entries of the table matching the condition, i.e. the SQL following `WHERE`, returning how many were deleted
+	///
+	/// The condition may contain parameters, which are bound to `params`.
+	/// Like [`execute`](Self::execute), this is limited by [`require_where_for_bulk_ops`](Self::require_where_for_bulk_ops).
+	///
+	///```
+	/// # use liter::{database, Id, Table};
+	/// # #[database]
+	/// # struct Db (Session);
+	/// #[derive(Table)]
+	/// struct Session {
+	/// 	#[key]
+	/// 	id: Id,
+	/// 	expires: i64
+	/// }
+	/// # let db = Db::create_in_memory()?;
+	/// for expires in [10, 20, 30] {
+	/// 	db.insert(&Session { id: Id::NULL, expires })?;
+	/// }
+	/// assert_eq!(db.delete_where::<Session, _>("expires < ?", &25)?, 2);
+	/// assert_eq!(db.delete_all::<Session>()?, 1);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	#[track_caller]
+	pub fn delete_where<T, P>(&self, condition: &str, params: &P) -> SqlResult<usize>
+		where T: Table, P: Bind
+	{
+		let sql = format!("DELETE FROM \"{}\" WHERE {condition}", T::NAME);
+		self.track_table_write::<T, _>("delete_where", || {
+			let mut stmt = self.prepare(&sql)?;
+			Binder::make(&mut stmt).bind(params)?;
+			self.guard_bulk(&sql, &mut stmt)
+		})
 	}
 
+	#[track_caller]
 	pub fn execute<T: Bind>(&self, sql: &str, params: &T) -> SqlResult<usize> {
-		let mut stmt = self.prepare(sql)?;
-		Binder::make(&mut stmt).bind(params)?;
-		stmt.raw_execute()
+		self.track_write("execute", || {
+			let mut stmt = self.prepare(sql)?;
+			Binder::make(&mut stmt).bind(params)?;
+			self.guard_bulk(sql, &mut stmt)
+		})
 	}
 	pub fn query_one<T: Fetch>(&self, sql: &str) -> SqlResult<T> {
 		let mut stmt = self.prepare(sql)?;
@@ -383,6 +888,65 @@ impl<S: Schema> Database<S> {
 		}
 		Ok(items)
 	}
+
+	/// Run a query with parameters and fetch a `T` from each row
+	///
+	/// The parameters can be anything that implements [`Bind`], e.g. a tuple or a [`params!`](crate::params!) struct.
+	///
+	///```
+	/// # use liter::{database, Id, Table};
+	/// # #[database]
+	/// # struct Db (Item);
+	/// #[derive(Table, Debug, PartialEq)]
+	/// struct Item {
+	/// 	#[key]
+	/// 	id: Id,
+	/// 	name: String,
+	/// 	stock: u32
+	/// }
+	/// # let db = Db::create_in_memory()?;
+	/// db.insert(&Item { id: Id::new(1), name: "bolt".to_string(), stock: 120 })?;
+	/// db.insert(&Item { id: Id::new(2), name: "nut".to_string(), stock: 0 })?;
+	///
+	/// let names: Vec<String> = db.query("SELECT name FROM item WHERE stock > ?", &10)?;
+	/// assert_eq!(names, ["bolt"]);
+	///
+	/// let item: Option<Item> = db.query_first("SELECT * FROM item WHERE name = ?", &"nut")?;
+	/// assert_eq!(item.map(|item| item.stock), Some(0));
+	///
+	/// let total = db.query_iter("SELECT stock FROM item", &(), |stocks| {
+	/// 	stocks.sum::<Result<u32, _>>()
+	/// })??;
+	/// assert_eq!(total, 120);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	pub fn query<T, P>(&self, sql: &str, params: &P) -> SqlResult<Vec<T>>
+		where T: Fetch, P: Bind
+	{
+		self.query_all_with(sql, params)
+	}
+	/// Run a query with parameters and fetch a `T` from the first row, `None` if there are no rows
+	pub fn query_first<T, P>(&self, sql: &str, params: &P) -> SqlResult<Option<T>>
+		where T: Fetch, P: Bind
+	{
+		let mut stmt = self.prepare(sql)?;
+		Binder::make(&mut stmt).bind(params)?;
+		let mut rows = stmt.raw_query();
+		rows.next()?
+			.map(T::from_row)
+			.transpose()
+	}
+	/// Run a query with parameters, and call `f` with an iterator fetching a `T` from each row
+	///
+	/// Unlike [`query`](Self::query), this doesn't collect the rows into a `Vec` first, so large results can be processed one row at a time, or stopped early.
+	/// The iterator borrows the statement, which is why it's only available inside of `f`.
+	pub fn query_iter<T, P, F, R>(&self, sql: &str, params: &P, f: F) -> SqlResult<R>
+		where T: Fetch, P: Bind, F: FnOnce(FetchRows<'_, T>) -> R
+	{
+		let mut stmt = self.prepare(sql)?;
+		Binder::make(&mut stmt).bind(params)?;
+		Ok(f(FetchRows::make(stmt.raw_query())))
+	}
 }
 
 impl<S: Schema> std::ops::Deref for Database<S> {
@@ -395,30 +959,95 @@ impl<S: Schema> std::ops::DerefMut for Database<S> {
 
 /* ID */
 
-impl Id {
-	pub const NULL: Self = Self(None);
-	pub fn from_i64(id: i64) -> Self {Self(Some(id))}
+impl<T: ?Sized> Id<T> {
+	/// Placeholder for entries that weren't inserted yet, see [`Database::create`]
+	pub const NULL: Self = Self(None, PhantomData);
+	pub const fn new(id: i64) -> Self {Self(Some(id), PhantomData)}
+	pub const fn from_i64(id: i64) -> Self {Self::new(id)}
+	/// The value of the id, `None` for [`Id::NULL`]
+	pub const fn get(&self) -> Option<i64> {
+		self.0
+	}
+	/// Convert to the `Id` of another table (or a plain `Id`), keeping the value
+	pub fn cast<U: ?Sized>(self) -> Id<U> {
+		Id(self.0, PhantomData)
+	}
+}
+
+impl<T: ?Sized> RowId for Id<T> {
+	fn is_null(&self) -> bool {
+		self.0.is_none()
+	}
+	fn set_rowid(&mut self, rowid: i64) {
+		self.0 = Some(rowid);
+	}
+}
+
+// not derived, since that would require `T` to implement the traits as well
+impl<T: ?Sized> fmt::Debug for Id<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("Id").field(&self.0).finish()
+	}
+}
+impl<T: ?Sized> Clone for Id<T> {
+	fn clone(&self) -> Self {
+		Self(self.0, PhantomData)
+	}
+}
+impl<T: ?Sized> PartialEq for Id<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+impl<T: ?Sized> Eq for Id<T> {}
+impl<T: ?Sized> Hash for Id<T> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.hash(state);
+	}
+}
+
+/// Shows just the number, or `NULL` for [`Id::NULL`]
+impl<T: ?Sized> fmt::Display for Id<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0 {
+			Some(id) => id.fmt(f),
+			None => f.write_str("NULL")
+		}
+	}
+}
+impl<T: ?Sized> From<i64> for Id<T> {
+	fn from(id: i64) -> Self {
+		Self::new(id)
+	}
+}
+/// Parses just the number, e.g. to get an id back from a URL
+impl<T: ?Sized> FromStr for Id<T> {
+	type Err = ParseIntError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.parse().map(Self::new)
+	}
 }
 
-impl FromSql for Id {
+impl<T: ?Sized> FromSql for Id<T> {
 	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-		i64::column_result(value).map(Some).map(Self)
+		i64::column_result(value).map(Self::from_i64)
 	}
 }
-impl ToSql for Id {
+impl<T: ?Sized> ToSql for Id<T> {
 	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
 		self.0.to_sql()
 	}
 }
-crate::types::impl_from_to_sql_2!(Id);
+impl<T: ?Sized> FromSql2 for Id<T> {}
+impl<T: ?Sized> ToSql2 for Id<T> {}
 
-impl Column for Id {
+impl<T: ?Sized> Column for Id<T> {
 	const AFFINITY: Affinity = Affinity::Integer;
 }
 
 /* REFERENCE */
 
-impl<T: HasKey<Key = Id>> Ref<T> {
+impl<T: HasKey<Key = Id<M>>, M: ?Sized> Ref<T> {
 	pub const NULL: Self = Self(Id::NULL);
 }
 impl<T: HasKey<Key = K>, K: CloneFromRef<T::Marker>> Ref<T> {
@@ -0,0 +1,156 @@
+//! Advisory locks shared by all processes using the database
+//!
+//! Each held lock is a row in the [`AdvisoryLock`] table, which has to be part of your [`database`](crate::database).
+//! Taking a lock is a single `INSERT`, so only one connection can succeed even when several processes share the database file.
+//! The lock is released when the returned [`LockGuard`] is dropped.
+//!
+//! Locks of crashed processes are never released automatically, see [`Database::force_unlock`].
+//!
+//!```
+//! use std::time::Duration;
+//! use liter::database;
+//! use liter::lock::AdvisoryLock;
+//!
+//! #[database]
+//! struct Db (AdvisoryLock);
+//!
+//! let db = Db::create_in_memory()?;
+//!
+//! let guard = db.advisory_lock("migrator", Duration::from_secs(1))?
+//! 	.expect("nobody else holds the lock");
+//! assert!(db.try_advisory_lock("migrator")?.is_none());
+//! guard.release()?;
+//! assert!(db.try_advisory_lock("migrator")?.is_some());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::process;
+use std::thread;
+use std::time::{
+	Duration,
+	Instant,
+	SystemTime,
+	UNIX_EPOCH
+};
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// How long to wait between attempts to take a lock that is held
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A held lock
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+pub struct AdvisoryLock {
+	#[key]
+	pub name: String,
+	/// Unique identifier of the [`LockGuard`] holding the lock
+	pub owner: String,
+	/// UNIX timestamp (in seconds) at which the lock was taken
+	pub acquired: i64
+}
+
+/// Held advisory lock, which is released when dropped
+#[derive(Debug)]
+#[must_use = "the lock is released when the guard is dropped"]
+pub struct LockGuard<'db, S: Schema> {
+	db: &'db Database<S>,
+	name: String,
+	owner: String,
+	released: bool
+}
+
+impl<S: Schema> Database<S>
+	where AdvisoryLock: PartOf<S>
+{
+	/// Take the lock `name`, waiting for up to `timeout` if it is held by someone else
+	///
+	/// Returns `None` if the lock couldn't be taken in time.
+	pub fn advisory_lock(&self, name: &str, timeout: Duration)
+		-> SqlResult<Option<LockGuard<'_, S>>>
+	{
+		let deadline = Instant::now() + timeout;
+		loop {
+			if let Some(guard) = self.try_advisory_lock(name)? {
+				return Ok(Some(guard));
+			}
+			let now = Instant::now();
+			if now >= deadline {
+				return Ok(None);
+			}
+			thread::sleep(RETRY_INTERVAL.min(deadline - now));
+		}
+	}
+	/// Take the lock `name` if it isn't held by anyone
+	pub fn try_advisory_lock(&self, name: &str)
+		-> SqlResult<Option<LockGuard<'_, S>>>
+	{
+		let owner = new_owner();
+		let sql = format!(
+			"INSERT INTO {} VALUES (?, ?, ?) ON CONFLICT (name) DO NOTHING",
+			AdvisoryLock::NAME
+		);
+		let inserted = self.execute(&sql, &(name, owner.as_str(), unix_now()))?;
+		Ok((inserted == 1).then(|| LockGuard {
+			db: self,
+			name: name.to_string(),
+			owner,
+			released: false
+		}))
+	}
+	/// Release the lock `name` regardless of who holds it, returning whether it was held
+	///
+	/// This is meant for cleaning up after a process that crashed while holding the lock.
+	pub fn force_unlock(&self, name: &str) -> SqlResult<bool> {
+		self.delete::<AdvisoryLock>(&name.to_string())
+	}
+}
+
+impl<S: Schema> LockGuard<'_, S> {
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+	/// Release the lock, returning whether it was still held by this guard
+	///
+	/// It might not be, if it was taken away with [`Database::force_unlock`].
+	pub fn release(mut self) -> SqlResult<bool> {
+		self.released = true;
+		self.unlock()
+	}
+	fn unlock(&self) -> SqlResult<bool> {
+		let sql = format!(
+			"DELETE FROM {} WHERE name = ? AND owner = ?",
+			AdvisoryLock::NAME
+		);
+		self.db.execute(&sql, &(self.name.as_str(), self.owner.as_str()))
+			.map(|deleted| deleted == 1)
+	}
+}
+
+impl<S: Schema> Drop for LockGuard<'_, S> {
+	fn drop(&mut self) {
+		if !self.released {
+			let _ = self.unlock();
+		}
+	}
+}
+
+/// Identifier that is unique across processes & threads
+pub(crate) fn new_owner() -> String {
+	let nanos = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |since| since.as_nanos());
+	format!("{}:{:?}:{}", process::id(), thread::current().id(), nanos)
+}
+
+fn unix_now() -> i64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |since| since.as_secs() as i64)
+}
@@ -32,7 +32,14 @@
 //!
 //! Note that it's not an error to define the structs `A` and `B` by themselves, since they don't know what [`Schema`](crate::Schema)s they will be a part of, `#[derive(Table)]` on `A` and `B` compiles just fine.
 //! Only when we try to define an invalid database does the issue arise.
+//!
+//! Only the tables referenced *directly* are checked, not what they reference in turn -- those are checked as part of the [`Schema`](crate::Schema) themselves.
+//! That's why a table may reference itself (e.g. `parent: Option<Ref<Self>>` for tree-shaped data), or two tables may reference each other: the check never recurses.
+//! Code that follows references further, like [`Database::load_graph`](crate::Database::load_graph), has to go through the [`Schema`](crate::Schema)'s (finite) list of tables rather than [`Table::References`](crate::Table::References) for the same reason.
 
+/// Find a type in nested tuple types
+pub mod contains;
+pub use contains::Contains;
 /// Strip `()`s from nested tuple types
 pub mod filter;
 pub use filter::Filtered;
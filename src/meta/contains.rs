@@ -0,0 +1,36 @@
+use crate::table::Table;
+
+/// Index of a type inside a nested tuple, see [`Contains`]
+pub mod index {
+	use std::marker::PhantomData;
+
+	/// The type itself
+	pub struct Here;
+	/// Inside the first element of a tuple
+	pub struct Left<I>(PhantomData<I>);
+	/// Inside the second element of a tuple
+	pub struct Right<I>(PhantomData<I>);
+}
+
+use index::{
+	Here,
+	Left,
+	Right
+};
+
+/// Indicate that the [`Table`] `T` occurs in a nested tuple of tables (like [`Table::References`]) at the index `I`
+///
+/// The index is always inferred, it only exists so that the implementations don't overlap.
+/// If `T` occurs more than once, the index can't be inferred, which makes the bound fail to resolve.
+pub trait Contains<T: Table, I> {}
+
+impl<T: Table> Contains<T, Here> for T {}
+impl<T: Table, I, A> Contains<T, Left<I>> for (A, )
+	where A: Contains<T, I>
+{}
+impl<T: Table, I, A, B> Contains<T, Left<I>> for (A, B)
+	where A: Contains<T, I>
+{}
+impl<T: Table, I, A, B> Contains<T, Right<I>> for (A, B)
+	where B: Contains<T, I>
+{}
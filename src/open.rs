@@ -0,0 +1,390 @@
+//! Options for opening a [`Database`], and registering custom VFSes
+//!
+//! [`Database::options`] returns an [`OpenOptions`] builder, which sets the open flags, pragmas (like the [`JournalMode`]) and timeouts of the connection.
+//!
+//! SQLite accesses database files through a [VFS](https://sqlite.org/vfs.html), which can be swapped out to e.g. serve a database read-only from an archive or object storage.
+//! A custom VFS is registered once per process with [`register_vfs`], after which databases can be opened with it by [`OpenOptions::vfs`].
+//!
+//! Databases can also be opened from bytes embedded in the application with [`Database::open_from_bytes`].
+//!
+//!```
+//! use liter::{database, Table};
+//! use liter::open::{is_vfs_registered, OpenOptions};
+//!
+//! #[database]
+//! struct Db (Item);
+//!
+//! #[derive(Table)]
+//! struct Item {
+//! 	name: String
+//! }
+//!
+//! // the "unix-none" VFS of SQLite doesn't do any file locking
+//! # if cfg!(unix) {
+//! assert!(is_vfs_registered("unix-none"));
+//! let path = std::env::temp_dir().join("liter_open_vfs_doctest.db");
+//! # let _ = std::fs::remove_file(&path);
+//! let db = OpenOptions::<Db>::new().vfs("unix-none").init(&path)?;
+//! db.insert(&Item { name: "lamp".to_string() })?;
+//! # std::fs::remove_file(&path).unwrap();
+//! # }
+//! assert!(!is_vfs_registered("no-such-vfs"));
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::ffi::{
+	CStr,
+	CString
+};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::{
+	ffi,
+	Connection,
+	Error,
+	OpenFlags,
+	Result as SqlResult
+};
+
+use crate::{
+	Database,
+	Schema,
+	DB_OPEN_FLAGS,
+	DEFAULT_PRAGMAS
+};
+
+impl<S: Schema> Database<S> {
+	/// Open a read-only database from its file contents, without copying them
+	///
+	/// This is meant for shipping a prebuilt reference database in the binary, through [`include_bytes!`].
+	/// Writing to the database fails with `SQLITE_READONLY`.
+	/// The database must not be in WAL mode.
+	///
+	///```
+	/// use liter::{database, Database, Table};
+	///
+	/// #[database]
+	/// struct Dictionary (Word);
+	///
+	/// #[derive(Table, Debug, PartialEq)]
+	/// struct Word {
+	/// 	#[key]
+	/// 	word: String,
+	/// 	meaning: String
+	/// }
+	///
+	/// # let path = std::env::temp_dir().join("liter_open_from_bytes_doctest.db");
+	/// # let _ = std::fs::remove_file(&path);
+	/// # let db = Dictionary::init(&path)?;
+	/// # db.insert(&Word { word: "liter".to_string(), meaning: "a unit of volume".to_string() })?;
+	/// # drop(db);
+	/// # let bytes = std::fs::read(&path).unwrap();
+	/// # std::fs::remove_file(&path).unwrap();
+	/// # let bytes: &'static [u8] = bytes.leak();
+	/// // let bytes: &'static [u8] = include_bytes!("dictionary.db");
+	/// let db = Database::<Dictionary>::open_from_bytes(bytes)?;
+	/// assert_eq!(
+	/// 	db.get::<Word>("liter".to_string())?.unwrap().meaning,
+	/// 	"a unit of volume"
+	/// );
+	/// assert!(db.execute("DELETE FROM word", &()).is_err());
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	pub fn open_from_bytes(bytes: &'static [u8]) -> SqlResult<Self> {
+		let connection = Connection::open_in_memory()?;
+		let size = i64::try_from(bytes.len())
+			.map_err(|err| Error::ToSqlConversionFailure(err.into()))?;
+		// SAFETY: the handle is valid while `connection` is alive, and SQLite never writes to (or frees) a read-only deserialized database, so the bytes being borrowed immutably for 'static is fine
+		let code = unsafe {
+			ffi::sqlite3_deserialize(
+				connection.handle(),
+				b"main\0".as_ptr().cast(),
+				bytes.as_ptr().cast_mut(),
+				size,
+				size,
+				ffi::SQLITE_DESERIALIZE_READONLY as _
+			)
+		};
+		if code != ffi::SQLITE_OK {
+			return Err(Error::SqliteFailure(ffi::Error::new(code), None));
+		}
+		Self::from_connection(connection)
+	}
+}
+
+/// `journal_mode` pragma: how SQLite makes transactions atomic, see [the SQLite documentation](https://sqlite.org/pragma.html#pragma_journal_mode)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JournalMode {
+	/// Delete the rollback journal at the end of each transaction (the default)
+	Delete,
+	Truncate,
+	Persist,
+	Memory,
+	/// Write-ahead log, which lets readers continue while there's a writer
+	Wal,
+	/// No rollback journal, so transactions can't be rolled back reliably
+	Off
+}
+
+impl JournalMode {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			JournalMode::Delete => "DELETE",
+			JournalMode::Truncate => "TRUNCATE",
+			JournalMode::Persist => "PERSIST",
+			JournalMode::Memory => "MEMORY",
+			JournalMode::Wal => "WAL",
+			JournalMode::Off => "OFF"
+		}
+	}
+}
+
+/// `synchronous` pragma: how often SQLite waits for writes to reach the disk, see [the SQLite documentation](https://sqlite.org/pragma.html#pragma_synchronous)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Synchronous {
+	Off,
+	/// Safe from corruption in WAL mode, but a power loss can roll back the last transactions
+	Normal,
+	/// The default
+	Full,
+	Extra
+}
+
+impl Synchronous {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Synchronous::Off => "OFF",
+			Synchronous::Normal => "NORMAL",
+			Synchronous::Full => "FULL",
+			Synchronous::Extra => "EXTRA"
+		}
+	}
+}
+
+/// Builder for opening a [`Database`] with non-default options, see [`Database::options`]
+///
+/// Pragmas are set in the order they were given in, before [`Schema::on_open`] runs.
+/// By default, only `foreign_keys` is turned on, which can be turned off again with `.pragma("foreign_keys", "off")`.
+///
+///```
+/// use std::time::Duration;
+/// use liter::{database, Database, Table};
+/// use liter::open::{JournalMode, Synchronous};
+///
+/// #[database]
+/// struct Db (Note);
+///
+/// #[derive(Table)]
+/// struct Note {
+/// 	text: String
+/// }
+///
+/// let path = std::env::temp_dir().join("liter_open_options_doctest.db");
+/// # let _ = std::fs::remove_file(&path);
+/// let db = Database::<Db>::options()
+/// 	.busy_timeout(Duration::from_secs(5))
+/// 	.journal_mode(JournalMode::Truncate)
+/// 	.synchronous(Synchronous::Normal)
+/// 	.pragma("cache_size", "-16000")
+/// 	.init(&path)?;
+/// db.insert(&Note { text: "hello".to_string() })?;
+/// let mode: String = db.pragma_query_value(None, "journal_mode", |row| row.get(0))?;
+/// assert_eq!(mode, "truncate");
+/// drop(db);
+///
+/// let reader = Database::<Db>::options().read_only().open(&path)?;
+/// assert_eq!(reader.get_all::<Note>()?.len(), 1);
+/// assert!(reader.insert(&Note { text: "denied".to_string() }).is_err());
+/// # drop(reader);
+/// # std::fs::remove_file(&path).unwrap();
+/// # Ok::<(), rusqlite::Error>(())
+///```
+#[derive(Debug, Clone)]
+pub struct OpenOptions<S: Schema> {
+	flags: OpenFlags,
+	vfs: Option<String>,
+	busy_timeout: Option<Duration>,
+	pragmas: Vec<(String, String)>,
+	bulk_limit: Option<usize>,
+	statement_cache: Option<usize>,
+	schema: PhantomData<S>
+}
+
+impl<S: Schema> Database<S> {
+	/// Builder for opening the database with non-default options
+	pub fn options() -> OpenOptions<S> {
+		OpenOptions::new()
+	}
+}
+
+impl<S: Schema> OpenOptions<S> {
+	pub fn new() -> Self {
+		Self {
+			flags: DB_OPEN_FLAGS,
+			vfs: None,
+			busy_timeout: None,
+			pragmas: DEFAULT_PRAGMAS.iter()
+				.map(|(name, value)| (name.to_string(), value.to_string()))
+				.collect(),
+			bulk_limit: None,
+			statement_cache: None,
+			schema: PhantomData
+		}
+	}
+	/// Open the database with these flags instead of the default ones (read-write, URI filenames & no mutex)
+	///
+	/// [`init`](Self::init) adds `SQLITE_OPEN_CREATE`.
+	pub fn flags(self, flags: OpenFlags) -> Self {
+		Self { flags, ..self }
+	}
+	/// Open the database read-only, so writing to it fails with `SQLITE_READONLY`
+	pub fn read_only(self) -> Self {
+		let flags = self.flags
+			.difference(OpenFlags::SQLITE_OPEN_READ_WRITE)
+			.union(OpenFlags::SQLITE_OPEN_READ_ONLY);
+		Self { flags, ..self }
+	}
+	/// Whether SQLite serializes access to the connection with a mutex, which isn't needed (and thus off by default) since a [`Database`] is never used by multiple threads at once
+	pub fn no_mutex(self, no_mutex: bool) -> Self {
+		let flags = match no_mutex {
+			true => self.flags.union(OpenFlags::SQLITE_OPEN_NO_MUTEX),
+			false => self.flags.difference(OpenFlags::SQLITE_OPEN_NO_MUTEX)
+		};
+		Self { flags, ..self }
+	}
+	/// Open the database through the VFS registered under this name, instead of the default one
+	///
+	/// Opening fails if no such VFS is registered.
+	pub fn vfs(self, name: &str) -> Self {
+		Self { vfs: Some(name.to_string()), ..self }
+	}
+	/// Wait up to `timeout` for locks held by other connections, instead of failing with `SQLITE_BUSY` right away
+	pub fn busy_timeout(self, timeout: Duration) -> Self {
+		Self { busy_timeout: Some(timeout), ..self }
+	}
+	pub fn journal_mode(self, mode: JournalMode) -> Self {
+		self.pragma("journal_mode", mode.as_str())
+	}
+	pub fn synchronous(self, synchronous: Synchronous) -> Self {
+		self.pragma("synchronous", synchronous.as_str())
+	}
+	/// Use the [write-ahead log](crate::wal), with `synchronous` set to `NORMAL` unless it's set separately
+	///
+	/// WAL mode is persistent, so this only has to be set once for a database file, but doesn't hurt to set every time.
+	/// With `NORMAL`, committing doesn't wait for the disk, which is still safe from corruption in WAL mode.
+	pub fn wal(self) -> Self {
+		let options = self.journal_mode(JournalMode::Wal);
+		match options.pragmas.iter().any(|(pragma, _value)| pragma.eq_ignore_ascii_case("synchronous")) {
+			true => options,
+			false => options.synchronous(Synchronous::Normal)
+		}
+	}
+	/// See [`Database::set_auto_checkpoint`]
+	pub fn auto_checkpoint(self, pages: u32) -> Self {
+		self.pragma("wal_autocheckpoint", &pages.to_string())
+	}
+	/// Set the pragma when opening the database, replacing an earlier value for the same pragma
+	pub fn pragma(mut self, name: &str, value: &str) -> Self {
+		self.pragmas.retain(|(pragma, _value)| !pragma.eq_ignore_ascii_case(name));
+		self.pragmas.push((name.to_string(), value.to_string()));
+		self
+	}
+	/// See [`Database::require_where_for_bulk_ops`]
+	pub fn require_where_for_bulk_ops(self, max_rows: usize) -> Self {
+		Self { bulk_limit: Some(max_rows), ..self }
+	}
+	/// Keep up to `capacity` prepared statements cached, instead of enough for the generated statements of every table
+	///
+	/// The generated statements (e.g. of [`Database::get`] or [`Database::insert`]) are prepared only once and then taken from the cache, as are those of [`prepare_cached`](rusqlite::Connection::prepare_cached).
+	/// `0` disables the cache, so every statement is prepared again.
+	pub fn statement_cache(self, capacity: usize) -> Self {
+		Self { statement_cache: Some(capacity), ..self }
+	}
+
+	/// Open the (existing) database at the path
+	pub fn open(&self, path: &Path) -> SqlResult<Database<S>> {
+		let db = self.connect(path, self.flags)?;
+		self.configure(&db);
+		Ok(db)
+	}
+	/// Open the (existing) database by a [URI filename](crate::uri), like `file:data.db?mode=ro`
+	///
+	/// This also interprets the URI if the flags were replaced by ones without `SQLITE_OPEN_URI`.
+	/// Its parameters can only restrict the flags, e.g. opening read-only with `mode=ro`.
+	pub fn open_uri(&self, uri: impl AsRef<str>) -> SqlResult<Database<S>> {
+		let flags = self.flags.union(OpenFlags::SQLITE_OPEN_URI);
+		let db = self.connect(Path::new(uri.as_ref()), flags)?;
+		self.configure(&db);
+		Ok(db)
+	}
+	/// Create, initialize & open the database at the path
+	///
+	/// Unlike [`Database::init`], this doesn't check whether the path exists, since it might not be on the regular file system, but fails when creating the tables if the database already has them.
+	pub fn init(&self, path: &Path) -> SqlResult<Database<S>> {
+		let flags = self.flags.union(OpenFlags::SQLITE_OPEN_CREATE);
+		let new = self.connect(path, flags)?;
+		new.create_schema()?;
+		self.configure(&new);
+		Ok(new)
+	}
+
+	fn configure(&self, db: &Database<S>) {
+		db.require_where_for_bulk_ops(self.bulk_limit);
+		if let Some(capacity) = self.statement_cache {
+			db.set_prepared_statement_cache_capacity(capacity);
+		}
+	}
+
+	fn connect(&self, path: &Path, flags: OpenFlags) -> SqlResult<Database<S>> {
+		let connection = match &self.vfs {
+			Some(vfs) => Connection::open_with_flags_and_vfs(path, flags, vfs.as_str()),
+			None => Connection::open_with_flags(path, flags)
+		}?;
+		if let Some(timeout) = self.busy_timeout {
+			connection.busy_timeout(timeout)?;
+		}
+		let pragmas = self.pragmas.iter()
+			.map(|(name, value)| (name.as_str(), value.as_str()));
+		Database::from_connection_with(connection, pragmas)
+	}
+}
+
+impl<S: Schema> Default for OpenOptions<S> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Register a custom VFS for this process, making it the default one if `make_default` is set
+///
+/// The VFS is leaked, since SQLite may use it for as long as the process runs, and is registered under its `zName`, which [`OpenOptions::vfs`] uses to select it.
+/// Registering a VFS under the same name as an already registered one replaces that one for databases opened afterwards.
+///
+/// # Safety
+///
+/// The VFS must be fully initialized according to the [SQLite documentation](https://sqlite.org/c3ref/vfs.html): `zName` must point to a NUL-terminated string that lives forever and the methods must be valid & thread safe.
+/// Implementing a VFS generally means writing `unsafe extern "C"` functions, which this can't check.
+pub unsafe fn register_vfs(vfs: Box<ffi::sqlite3_vfs>, make_default: bool)
+	-> SqlResult<&'static CStr>
+{
+	if vfs.zName.is_null() {
+		return Err(Error::InvalidParameterName("VFS without zName".to_string()));
+	}
+	let vfs = Box::leak(vfs);
+	let name = CStr::from_ptr(vfs.zName);
+	match ffi::sqlite3_vfs_register(vfs, make_default as i32) {
+		ffi::SQLITE_OK => Ok(name),
+		code => Err(Error::SqliteFailure(ffi::Error::new(code), None))
+	}
+}
+
+/// Whether a VFS is registered under the name, either built into SQLite or through [`register_vfs`]
+pub fn is_vfs_registered(name: &str) -> bool {
+	let Ok(name) = CString::new(name) else {
+		return false;
+	};
+	// SAFETY: the name is a valid NUL-terminated string & the returned pointer isn't used
+	!unsafe { ffi::sqlite3_vfs_find(name.as_ptr()) }.is_null()
+}
@@ -0,0 +1,141 @@
+//! Pagination, e.g. for list endpoints
+//!
+//! [`Database::page`] gets one [`Page`] of the entries of a table, together with the total number of entries, using `LIMIT` & `OFFSET`.
+//! The entries are ordered by their `rowid`, i.e. (unless the key is an [`Id`](crate::Id)) in the order they were inserted.
+//!
+//! Since SQLite has to step over all the rows skipped by the `OFFSET`, later pages of large tables get slow.
+//! For tables with a key, [`Database::page_after`] uses keyset pagination instead: it continues after the key of the last entry of the previous page, using the primary key's index.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Comment);
+//!
+//! #[derive(Table)]
+//! struct Comment {
+//! 	#[key]
+//! 	id: Id,
+//! 	text: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for n in 1..=25 {
+//! 	db.insert(&Comment { id: Id::NULL, text: format!("comment #{n}") })?;
+//! }
+//!
+//! let page = db.page::<Comment>(2, 10)?;
+//! assert_eq!(page.items.len(), 5);
+//! assert_eq!(page.items[0].text, "comment #21");
+//! assert_eq!(page.total, 25);
+//! assert_eq!(page.page_count(), 3);
+//! assert!(!page.has_next());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// One page of entries, see [`Database::page`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page<T> {
+	pub items: Vec<T>,
+	/// Number of entries in the table
+	pub total: u64,
+	/// Index of this page, starting at `0`
+	pub page: u64,
+	/// Maximum number of entries per page
+	pub page_size: u64
+}
+
+impl<T> Page<T> {
+	/// Number of pages needed for all entries, `0` for an empty table
+	pub fn page_count(&self) -> u64 {
+		self.total.div_ceil(self.page_size.max(1))
+	}
+	pub fn has_previous(&self) -> bool {
+		self.page > 0
+	}
+	pub fn has_next(&self) -> bool {
+		self.page.saturating_add(1) < self.page_count()
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Get the page with the index `page` (starting at `0`) of the table's entries, with `page_size` entries per page
+	///
+	/// This runs a `count(*)` and a `LIMIT … OFFSET …` query.
+	/// Since SQLite still has to step over all the skipped rows, later pages of large tables get slow, see [`page_after`](Self::page_after) for an alternative.
+	pub fn page<T>(&self, page: u64, page_size: u64) -> SqlResult<Page<T>>
+		where T: Table + Entry + PartOf<S>
+	{
+		let total = self.count::<T>()?;
+		let sql = format!("SELECT * FROM \"{}\" ORDER BY rowid LIMIT ? OFFSET ?", T::NAME);
+		// SQLite takes a negative LIMIT or OFFSET as none at all
+		let limit = i64::try_from(page_size).unwrap_or(i64::MAX);
+		let offset = i64::try_from(page.saturating_mul(page_size)).unwrap_or(i64::MAX);
+		let items = self.query_all_with(&sql, &(limit, offset))?;
+		Ok(Page { items, total, page, page_size })
+	}
+	/// Get up to `limit` entries ordered by their key, starting after the entry with `last_key`, or at the first entry if it's `None`
+	///
+	/// Composite keys are compared as a whole, column by column.
+	///
+	///```
+	/// # use liter::{database, Table};
+	/// # #[database]
+	/// # struct Db (Event);
+	/// #[derive(Table)]
+	/// struct Event {
+	/// 	#[key]
+	/// 	day: u32,
+	/// 	#[key]
+	/// 	seq: u32
+	/// }
+	/// # let db = Db::create_in_memory()?;
+	/// for (day, seq) in [(1, 1), (1, 2), (2, 1), (3, 1), (3, 2)] {
+	/// 	db.insert(&Event { day, seq })?;
+	/// }
+	///
+	/// let first = db.page_after::<Event>(None, 2)?;
+	/// let last = first.last().unwrap();
+	/// let second = db.page_after::<Event>(Some(&(last.day, last.seq)), 2)?;
+	/// let keys: Vec<_> = second.iter().map(|event| (event.day, event.seq)).collect();
+	/// assert_eq!(keys, [(2, 1), (3, 1)]);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	pub fn page_after<T>(&self, last_key: Option<&T::Key>, limit: u64) -> SqlResult<Vec<T>>
+		where T: Table + Entry + HasKey + PartOf<S>
+	{
+		let key = T::KEY_COLUMNS.join(", ");
+		let sql = format!(
+			"SELECT * FROM \"{table}\" {filter} ORDER BY {key} LIMIT ?",
+			table = T::NAME,
+			filter = match last_key {
+				Some(_) => format!("WHERE ({key}) > ({})", vec!["?"; T::KEY_COLUMNS.len()].join(", ")),
+				None => String::new()
+			}
+		);
+		let mut stmt = self.prepare(&sql)?;
+		let mut binder = Binder::make(&mut stmt);
+		if let Some(last_key) = last_key {
+			binder.bind(last_key)?;
+		}
+		binder.bind(&i64::try_from(limit).unwrap_or(i64::MAX))?;
+		let mut items = Vec::new();
+		let mut rows = stmt.raw_query();
+		while let Some(row) = rows.next()? {
+			items.push(T::from_row(row)?);
+		}
+		Ok(items)
+	}
+}
@@ -0,0 +1,68 @@
+//! Named parameters: binding the fields of a struct to `:field` placeholders
+//!
+//! Hand-written SQL with many positional `?` parameters breaks silently when they're reordered.
+//! The [`params!`] macro defines a struct whose fields are bound by name instead, using [`Binder::bind_named`](crate::Binder::bind_named): each field `x` is bound to the parameter `:x`, wherever it appears in the statement.
+//! Since it implements [`Bind`](crate::Bind), it can be used anywhere parameters are accepted.
+//!
+//!```
+//! use liter::{database, params, Id, Table};
+//!
+//! #[database]
+//! struct Db (Reading);
+//!
+//! #[derive(Table)]
+//! struct Reading {
+//! 	#[key]
+//! 	id: Id,
+//! 	sensor: String,
+//! 	celsius: f64
+//! }
+//!
+//! params! {
+//! 	struct Range {
+//! 		sensor: String,
+//! 		low: f64,
+//! 		high: f64
+//! 	}
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for celsius in [18.5, 21.0, 25.5] {
+//! 	db.insert(&Reading { id: Id::NULL, sensor: "attic".to_string(), celsius })?;
+//! }
+//!
+//! let range = Range { sensor: "attic".to_string(), low: 20.0, high: 30.0 };
+//! let sql = "SELECT count(*) FROM reading WHERE celsius BETWEEN :low AND :high AND sensor = :sensor";
+//! let count: u32 = db.query_one_with(sql, &range)?;
+//! assert_eq!(count, 2);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+/// Define a struct that binds its fields to named parameters, see the [module documentation](crate::params)
+///
+/// Each field has to bind exactly one column, and the statement has to contain a `:field` parameter for every field.
+/// Attributes (like doc comments or derives) are applied to the struct.
+#[macro_export]
+macro_rules! params {
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident {
+			$( $(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty ),+ $(,)?
+		}
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			$( $(#[$field_attr])* $field_vis $field: $ty ),+
+		}
+		impl $crate::Bind for $name {
+			const COLUMNS: usize = [$( ::std::stringify!($field) ),+].len();
+			fn bind(&self, binder: &mut $crate::Binder<'_, '_>) -> $crate::util::SqlResult<()> {
+				$( binder.bind_named(::std::concat!(":", ::std::stringify!($field)), &self.$field)?; )+
+				Ok(())
+			}
+		}
+	};
+}
+
+#[doc(inline)]
+pub use params;
@@ -0,0 +1,106 @@
+//! Prepared queries with typed parameters & results
+//!
+//! Each call of e.g. [`Database::query`] prepares its statement again, which is a significant part of the cost of small queries run in a loop.
+//! [`Database::prepare_typed`] prepares the statement once and returns a [`PreparedQuery`], which can then be run any number of times.
+//! Its type fixes the parameters `P` and the result `R` of each row, so every call binds and fetches the same types.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//! use liter::prepared::PreparedQuery;
+//!
+//! #[database]
+//! struct Db (Item);
+//!
+//! #[derive(Table)]
+//! struct Item {
+//! 	#[key]
+//! 	id: Id,
+//! 	data: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for data in ["a", "b", "c"] {
+//! 	db.insert(&Item { id: Id::NULL, data: data.to_string() })?;
+//! }
+//!
+//! let mut data: PreparedQuery<i64, String> = db.prepare_typed("SELECT data FROM item WHERE id = ?")?;
+//! let mut found = String::new();
+//! for id in 1..=3 {
+//! 	found += &data.one(&id)?;
+//! }
+//! assert_eq!(found, "abc");
+//! assert_eq!(data.first(&4)?, None);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::marker::PhantomData;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult,
+	Statement
+};
+
+use crate::{
+	Bind,
+	Binder,
+	Database,
+	Fetch,
+	Schema
+};
+use crate::types::FetchRows;
+
+/// Statement binding `P` and fetching an `R` from each row, see the [module documentation](crate::prepared)
+pub struct PreparedQuery<'conn, P: ?Sized, R> {
+	stmt: Statement<'conn>,
+	types: PhantomData<(fn(&P), fn() -> R)>
+}
+
+impl<S: Schema> Database<S> {
+	/// Prepare a query once, to run it repeatedly with different parameters
+	pub fn prepare_typed<P, R>(&self, sql: &str) -> SqlResult<PreparedQuery<'_, P, R>>
+		where P: Bind + ?Sized, R: Fetch
+	{
+		let stmt = self.prepare(sql)?;
+		if stmt.parameter_count() != P::COLUMNS {
+			return Err(Error::InvalidParameterCount(P::COLUMNS, stmt.parameter_count()));
+		}
+		Ok(PreparedQuery {stmt, types: PhantomData})
+	}
+}
+
+impl<P: Bind + ?Sized, R: Fetch> PreparedQuery<'_, P, R> {
+	/// Fetch all rows
+	pub fn all(&mut self, params: &P) -> SqlResult<Vec<R>> {
+		self.iter(params, |rows| rows.collect())?
+	}
+	/// Fetch the first row, `None` if there are no rows
+	pub fn first(&mut self, params: &P) -> SqlResult<Option<R>> {
+		self.iter(params, |mut rows| rows.next().transpose())?
+	}
+	/// Fetch the first row, [`Error::QueryReturnedNoRows`] if there are no rows
+	pub fn one(&mut self, params: &P) -> SqlResult<R> {
+		self.first(params)?.ok_or(Error::QueryReturnedNoRows)
+	}
+	/// Call `f` with an iterator fetching each row, like [`Database::query_iter`]
+	pub fn iter<F, T>(&mut self, params: &P, f: F) -> SqlResult<T>
+		where F: FnOnce(FetchRows<'_, R>) -> T
+	{
+		// parameters bound by the previous run would otherwise stay bound, e.g. where `None` skips them
+		self.stmt.clear_bindings();
+		Binder::make(&mut self.stmt).bind(params)?;
+		Ok(f(FetchRows::make(self.stmt.raw_query())))
+	}
+	/// Run the statement without fetching any rows and return the number of changed rows
+	///
+	/// This isn't tracked by [`diagnostics`](crate::diagnostics), nor checked by the [guard](crate::guard).
+	pub fn execute(&mut self, params: &P) -> SqlResult<usize> {
+		self.stmt.clear_bindings();
+		Binder::make(&mut self.stmt).bind(params)?;
+		self.stmt.raw_execute()
+	}
+	/// The underlying statement, e.g. to [`expand`](Statement::expanded_sql) its SQL
+	pub fn statement(&self) -> &Statement<'_> {
+		&self.stmt
+	}
+}
@@ -0,0 +1,184 @@
+//! Projections: structs containing only some of the columns of a [`Table`]
+//!
+//! Listing entries often only needs a few of their columns, and fetching the others (e.g. large `BLOB`s) is wasted effort.
+//! The [`projection!`] macro defines a read-only struct with a subset of the fields of a table, and generates `SELECT field_a, field_b FROM table` for it at compile-time.
+//! Each field has to have the same name and type as the table's field, which is checked (by name) at compile-time too.
+//!
+//!```
+//! use liter::{database, projection, Id, Table};
+//!
+//! #[database]
+//! struct Db (Photo);
+//!
+//! #[derive(Table)]
+//! struct Photo {
+//! 	#[key]
+//! 	id: Id,
+//! 	title: String,
+//! 	jpeg: Vec<u8>
+//! }
+//!
+//! projection! {
+//! 	#[select(from = Photo)]
+//! 	#[derive(Debug, PartialEq)]
+//! 	struct PhotoSummary {
+//! 		id: Id,
+//! 		title: String
+//! 	}
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Photo { id: Id::new(1), title: "Harbor".to_string(), jpeg: vec![0xff; 100_000] })?;
+//!
+//! assert_eq!(PhotoSummary::SELECT, "SELECT id, title FROM \"photo\"");
+//! let photos = db.select_all::<PhotoSummary>()?;
+//! assert_eq!(photos, [PhotoSummary { id: Id::new(1), title: "Harbor".to_string() }]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use construe::StrConstrue;
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Bind,
+	Database,
+	Fetch,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+use crate::value::{
+	StrChain,
+	ValueDef
+};
+
+/// Subset of the columns of a [`Table`], defined with [`projection!`]
+pub trait Projection: Fetch {
+	type Table: Table;
+	/// `SELECT (...) FROM ...`
+	///
+	/// Select the projection's columns of all rows of the [`Table`](Self::Table).
+	const SELECT: &'static str;
+}
+
+impl<S: Schema> Database<S> {
+	/// Get the projection of all entries of its table
+	pub fn select_all<P>(&self) -> SqlResult<Vec<P>>
+		where P: Projection, P::Table: PartOf<S>
+	{
+		self.query_all(P::SELECT)
+	}
+	/// Get the projection of the entries of its table matching the condition, i.e. the SQL following `WHERE`
+	///
+	/// The condition may contain parameters, which are bound to `params`.
+	pub fn select_where<P, B>(&self, condition: &str, params: &B) -> SqlResult<Vec<P>>
+		where P: Projection, P::Table: PartOf<S>, B: Bind
+	{
+		self.query_all_with(&format!("{} WHERE {condition}", P::SELECT), params)
+	}
+}
+
+/// Define a [`Projection`] of a [`Table`], see the [module documentation](crate::projection)
+///
+/// The first attribute has to be `#[select(from = Table)]`, any others (like doc comments or derives) are applied to the struct.
+/// Since a projection only reads the columns, it isn't a [`Value`](crate::Value) and can't be inserted.
+///
+///```compile_fail
+/// # use liter::{projection, Table};
+/// # use liter::projection::Projection;
+/// # #[derive(Table)]
+/// # struct Photo { title: String }
+/// projection! {
+/// 	#[select(from = Photo)]
+/// 	struct PhotoSummary { caption: String }
+/// }
+/// # let _ = PhotoSummary::SELECT;
+///```
+#[macro_export]
+macro_rules! projection {
+	(
+		#[select(from = $table:ty)]
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident {
+			$( $(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty ),+ $(,)?
+		}
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			$( $(#[$field_attr])* $field_vis $field: $ty ),+
+		}
+		impl $crate::projection::Projection for $name {
+			type Table = $table;
+			const SELECT: &'static str = $crate::util::construe!(
+				&str => $crate::projection::select(
+					<$table as $crate::Table>::NAME,
+					<$table as $crate::Table>::ALL_COLUMNS,
+					&[$( (::std::stringify!($field), &<$ty as $crate::Value>::DEFINITION) ),+]
+				)
+			);
+		}
+		impl $crate::Fetch for $name {
+			fn fetch(fetcher: &mut $crate::types::Fetcher<'_>)
+				-> $crate::util::SqlResult<Self>
+			{
+				Ok(Self {
+					$( $field: fetcher.fetch()? ),+
+				})
+			}
+			fn try_fetch(fetcher: &mut $crate::types::Fetcher<'_>)
+				-> $crate::util::SqlResult<::std::option::Option<Self>>
+			{
+				<Self as $crate::Fetch>::fetch(fetcher).map(::std::option::Option::Some)
+			}
+		}
+	};
+}
+
+#[doc(inline)]
+pub use projection;
+
+/// `SELECT (...) FROM "table"` for the fields of a projection
+///
+/// Panics if a field doesn't correspond to a column of the table.
+#[doc(hidden)]
+pub const fn select<const N: usize>(
+	table: &str,
+	all_columns: &[&str],
+	mut fields: &[(&str, &ValueDef)])
+	-> StrConstrue<N>
+{
+	let mut sc = StrConstrue::new();
+	sc = sc.push_str("SELECT ");
+	let mut is_first = true;
+	while let [(name, def), rest @ ..] = fields {
+		fields = rest;
+		if !has_column(all_columns, name) {
+			panic!("field of the projection isn't a field of the table");
+		}
+		if !is_first {
+			sc = sc.push_str(", ");
+		}
+		else {is_first = false;}
+		sc = def.inner.push_column_names(&StrChain::start(name), sc);
+	}
+	sc.push_str(" FROM \"").push_str(table).push_str("\"")
+}
+
+/// Whether a column is named after the field, i.e. is `field` or starts with `field_`
+const fn has_column(mut all_columns: &[&str], field: &str) -> bool {
+	while let [column, rest @ ..] = all_columns {
+		all_columns = rest;
+		let (column, field) = (column.as_bytes(), field.as_bytes());
+		if column.len() < field.len() {
+			continue;
+		}
+		let mut idx = 0;
+		while idx < field.len() && column[idx] == field[idx] {
+			idx += 1;
+		}
+		if idx == field.len() && (column.len() == idx || column[idx] == b'_') {
+			return true;
+		}
+	}
+	false
+}
@@ -0,0 +1,112 @@
+//! Token-bucket rate limiting shared by all processes using the database
+//!
+//! Each bucket is a row in the [`RateLimitBucket`] table, which has to be part of your [`database`](crate::database).
+//! Refilling and consuming tokens happens in a single statement, so it is atomic even when several processes share the database file.
+//! The current time is taken from SQLite, not the process.
+//!
+//!```
+//! use liter::database;
+//! use liter::ratelimit::{Policy, RateLimitBucket};
+//!
+//! #[database]
+//! struct Db (RateLimitBucket);
+//!
+//! let db = Db::create_in_memory()?;
+//! let limiter = db.limiter();
+//! // bursts of up to 2 requests, refilled at one per minute
+//! let policy = Policy::new(2.0, 1.0 / 60.0);
+//!
+//! assert!(limiter.check_and_consume("user:42", &policy)?);
+//! assert!(limiter.check_and_consume("user:42", &policy)?);
+//! assert!(!limiter.check_and_consume("user:42", &policy)?);
+//! assert!(limiter.check_and_consume("user:7", &policy)?);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+use crate::util::NOW;
+
+/// State of a single token bucket
+#[derive(Table, Clone, Debug, PartialEq)]
+pub struct RateLimitBucket {
+	#[key]
+	pub bucket: String,
+	/// Tokens left at the time of the last update
+	pub tokens: f64,
+	/// UNIX timestamp of the last update
+	pub updated: f64
+}
+
+/// Size and refill rate of a token bucket
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Policy {
+	/// Maximum number of tokens, i.e. the largest allowed burst
+	pub capacity: f64,
+	/// Tokens added per second
+	pub refill_per_second: f64,
+	/// Tokens consumed by each [`check_and_consume`](Limiter::check_and_consume)
+	pub cost: f64
+}
+
+/// Rate limiter, created by [`Database::limiter`]
+#[derive(Debug)]
+pub struct Limiter<'db, S: Schema> {
+	db: &'db Database<S>
+}
+
+impl Policy {
+	/// Policy where each check consumes one token
+	pub const fn new(capacity: f64, refill_per_second: f64) -> Self {
+		Self { capacity, refill_per_second, cost: 1.0 }
+	}
+	pub const fn with_cost(self, cost: f64) -> Self {
+		Self { cost, ..self }
+	}
+}
+
+impl<S: Schema> Database<S> {
+	pub fn limiter(&self) -> Limiter<'_, S>
+		where RateLimitBucket: PartOf<S>
+	{
+		Limiter { db: self }
+	}
+}
+
+impl<S: Schema> Limiter<'_, S> {
+	/// Refill the bucket, then take [`Policy::cost`] tokens from it if there are enough
+	///
+	/// Returns whether the tokens were taken, i.e. whether the action is allowed.
+	/// Buckets that don't exist yet start out full.
+	pub fn check_and_consume(&self, bucket: &str, policy: &Policy)
+		-> SqlResult<bool>
+	{
+		if policy.cost > policy.capacity {
+			return Ok(false);
+		}
+		// ?1 = bucket, ?2 = capacity, ?3 = refill_per_second, ?4 = cost
+		let refilled = "min(?2, tokens + max(0.0, NOW - updated) * ?3)"
+			.replace("NOW", NOW);
+		let sql = format!(
+			"INSERT INTO {table} VALUES (?1, ?2 - ?4, {NOW}) \
+			ON CONFLICT (bucket) DO UPDATE SET \
+			tokens = {refilled} - ?4, updated = {NOW} \
+			WHERE {refilled} >= ?4 \
+			RETURNING tokens",
+			table = RateLimitBucket::NAME
+		);
+		let params = (bucket, policy.capacity, policy.refill_per_second, policy.cost);
+		let remaining: Vec<f64> = self.db.query_all_with(&sql, &params)?;
+		Ok(!remaining.is_empty())
+	}
+	/// Fill the bucket back up by forgetting it
+	pub fn reset(&self, bucket: &str) -> SqlResult<bool> {
+		self.db.delete::<RateLimitBucket>(&bucket.to_string())
+	}
+}
@@ -0,0 +1,158 @@
+//! [`ReadOnlyDatabase`]: a [`Database`] handle that can only read
+//!
+//! [`Database::open_read_only`] opens the database with `SQLITE_OPEN_READONLY`, and returns it wrapped in a [`ReadOnlyDatabase`].
+//! That only has the methods for reading entries & running queries, so writing to it (e.g. in a process generating reports) doesn't compile to begin with.
+//! Writing SQL passed to [`query`](ReadOnlyDatabase::query) is rejected by SQLite with `SQLITE_READONLY`.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Sale);
+//!
+//! #[derive(Table)]
+//! struct Sale {
+//! 	#[key]
+//! 	id: Id,
+//! 	cents: u64
+//! }
+//!
+//! let path = std::env::temp_dir().join("liter_read_only_doctest.db");
+//! # let _ = std::fs::remove_file(&path);
+//! let db = Db::init(&path)?;
+//! db.insert(&Sale { id: Id::new(1), cents: 1250 })?;
+//! db.insert(&Sale { id: Id::new(2), cents: 300 })?;
+//! drop(db);
+//!
+//! let report = Db::open_read_only(&path)?;
+//! assert_eq!(report.count::<Sale>()?, 2);
+//! let total: u64 = report.query_one("SELECT sum(cents) FROM sale")?;
+//! assert_eq!(total, 1550);
+//! assert!(report.query::<u64, _>("DELETE FROM sale RETURNING cents", &()).is_err());
+//! # drop(report);
+//! # std::fs::remove_file(&path).unwrap();
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+//!
+//! There is no `insert` (or any other writing method):
+//!
+//!```compile_fail
+//! # use liter::{database, Id, Table};
+//! # #[database]
+//! # struct Db (Sale);
+//! # #[derive(Table)]
+//! # struct Sale {
+//! # 	#[key]
+//! # 	id: Id,
+//! # 	cents: u64
+//! # }
+//! # let path = std::env::temp_dir().join("liter_read_only_fail_doctest.db");
+//! let report = Db::open_read_only(&path)?;
+//! report.insert(&Sale { id: Id::new(3), cents: 99 })?;
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::hash::Hash;
+use std::path::Path;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Bind,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	OpenOptions,
+	Ref,
+	Schema,
+	Table
+};
+use crate::meta::tuple::CloneFromRef;
+use crate::types::FetchRows;
+
+/// Read-only handle to a [`Database`]
+///
+/// Unlike [`Database`], this doesn't dereference to the underlying [`Connection`](rusqlite::Connection), which could be used to write to it.
+#[derive(Debug)]
+pub struct ReadOnlyDatabase<S: Schema>(Database<S>);
+
+impl<S: Schema> Database<S> {
+	/// Open the (existing) database at the path read-only
+	///
+	/// This is the same as opening it with [`OpenOptions::read_only`], except that the returned handle doesn't have any methods that write.
+	pub fn open_read_only(path: &Path) -> SqlResult<ReadOnlyDatabase<S>> {
+		OpenOptions::new()
+			.read_only()
+			.open(path)
+			.map(ReadOnlyDatabase)
+	}
+}
+
+impl<S: Schema> ReadOnlyDatabase<S> {
+	/// See [`Database::get`]
+	pub fn get<T>(&self, key: <T as HasKey>::Key) -> SqlResult<Option<T>>
+		where T: Entry + HasKey
+	{
+		self.0.get(key)
+	}
+	/// See [`Database::get_all`]
+	pub fn get_all<T: Entry>(&self) -> SqlResult<Vec<T>> {
+		self.0.get_all()
+	}
+	/// See [`Database::get_many`]
+	pub fn get_many<T>(&self, keys: &[T::Key]) -> SqlResult<Vec<T>>
+		where
+			T: Table + Entry + HasKey,
+			T::Key: Eq + Hash + CloneFromRef<T::Marker>
+	{
+		self.0.get_many(keys)
+	}
+	/// See [`Database::resolve`]
+	pub fn resolve<T>(&self, reference: &Ref<T>) -> SqlResult<Option<T>>
+		where T: Entry + HasKey
+	{
+		self.0.resolve(reference)
+	}
+	/// See [`Database::count`]
+	pub fn count<T: Table>(&self) -> SqlResult<u64> {
+		self.0.count::<T>()
+	}
+	/// See [`Database::count_where`]
+	pub fn count_where<T, P>(&self, condition: &str, params: &P) -> SqlResult<u64>
+		where T: Table, P: Bind
+	{
+		self.0.count_where::<T, P>(condition, params)
+	}
+	/// See [`Database::exists`]
+	pub fn exists<T>(&self, key: &T::Key) -> SqlResult<bool>
+		where T: Table + HasKey
+	{
+		self.0.exists::<T>(key)
+	}
+
+	pub fn query_one<T: Fetch>(&self, sql: &str) -> SqlResult<T> {
+		self.0.query_one(sql)
+	}
+	pub fn query_all<T: Fetch>(&self, sql: &str) -> SqlResult<Vec<T>> {
+		self.0.query_all(sql)
+	}
+	/// See [`Database::query`]
+	pub fn query<T, P>(&self, sql: &str, params: &P) -> SqlResult<Vec<T>>
+		where T: Fetch, P: Bind
+	{
+		self.0.query(sql, params)
+	}
+	/// See [`Database::query_first`]
+	pub fn query_first<T, P>(&self, sql: &str, params: &P) -> SqlResult<Option<T>>
+		where T: Fetch, P: Bind
+	{
+		self.0.query_first(sql, params)
+	}
+	/// See [`Database::query_iter`]
+	pub fn query_iter<T, P, F, R>(&self, sql: &str, params: &P, f: F) -> SqlResult<R>
+		where T: Fetch, P: Bind, F: FnOnce(FetchRows<'_, T>) -> R
+	{
+		self.0.query_iter(sql, params, f)
+	}
+}
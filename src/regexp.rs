@@ -0,0 +1,85 @@
+//! `REGEXP` operator backed by the [`regex`] crate
+//!
+//! This module is only available with the `regex` feature.
+//!
+//! SQLite parses `text REGEXP pattern`, but leaves it to the application to provide the `regexp()` function implementing it.
+//! With the `regex` feature, [`register`] is called on every connection opened by a [`Database`](crate::Database), so `REGEXP` can be used in queries, as well as in [`Check::Regex`](crate::value::Check::Regex) constraints.
+//! Each pattern is compiled once per statement.
+//!
+//!```
+//! use liter::{database, Column, Id, Table};
+//! use liter::column::Affinity;
+//! use liter::value::Check;
+//!
+//! #[database]
+//! struct Db (Account);
+//!
+//! /// Lowercase letters, digits & underscores, starting with a letter
+//! #[derive(Debug, PartialEq)]
+//! struct Handle(String);
+//! # impl rusqlite::types::FromSql for Handle {
+//! # 	fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+//! # 		String::column_result(value).map(Handle)
+//! # 	}
+//! # }
+//! # impl rusqlite::ToSql for Handle {
+//! # 	fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+//! # 		self.0.to_sql()
+//! # 	}
+//! # }
+//! # liter::types::impl_from_to_sql_2!(Handle);
+//! impl Column for Handle {
+//! 	const AFFINITY: Affinity = Affinity::Text;
+//! 	const CHECKS: &'static [Check] = &[Check::Regex("^[a-z][a-z0-9_]*$")];
+//! }
+//!
+//! #[derive(Table)]
+//! struct Account {
+//! 	#[key]
+//! 	id: Id,
+//! 	handle: Handle
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Account { id: Id::NULL, handle: Handle("ada_1815".to_string()) })?;
+//! assert!(db.insert(&Account { id: Id::NULL, handle: Handle("Not Valid".to_string()) }).is_err());
+//!
+//! let count: u32 = db.query_one("SELECT count(*) FROM account WHERE handle REGEXP '[0-9]{4}$'")?;
+//! assert_eq!(count, 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::sync::Arc;
+
+use regex::Regex;
+use rusqlite::{
+	Connection,
+	Error,
+	Result as SqlResult
+};
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::ValueRef;
+
+/// Register the `regexp(pattern, text)` function on the connection, which implements `text REGEXP pattern`
+///
+/// The result is `NULL` if the text is `NULL`, and an error if the pattern isn't a valid regular expression.
+pub fn register(connection: &Connection) -> SqlResult<()> {
+	connection.create_scalar_function(
+		"regexp",
+		2,
+		FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+		|ctx| {
+			// cached by SQLite for as long as the pattern stays the same
+			let regex: Arc<Regex> = ctx.get_or_create_aux(0, |pattern| -> SqlResult<Regex> {
+				Regex::new(pattern.as_str()?).map_err(|err| Error::UserFunctionError(err.into()))
+			})?;
+			match ctx.get_raw(1) {
+				ValueRef::Null => Ok(None),
+				text => {
+					let text = text.as_str().map_err(|err| Error::UserFunctionError(err.into()))?;
+					Ok(Some(regex.is_match(text)))
+				}
+			}
+		}
+	)
+}
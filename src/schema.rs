@@ -1,14 +1,22 @@
 //! The [`Schema`] is a set of [`Table`]s and defines a [`Database`](crate::Database)
 
 use construe::StrConstrue;
+use rusqlite::{
+	Connection,
+	Result as SqlResult
+};
 
 use crate::Table;
+use crate::invariant::InvariantDef;
 use crate::table::TableDef;
+use crate::trigger::TriggerDef;
+use crate::view::ViewDef;
 
 /// The set of [`Table`]s contained in a [`Database`](crate::Database)
 ///
-/// Don't try to implement this trait manually -- use [`#[database]`](crate::database) on a tuple struct of [`Table`]s.
+/// Use [`#[database]`](crate::database) on a tuple struct of [`Table`]s to implement this trait.
 /// The proc macro won't just generate the [`Schema`] implementation, it will also validate it.
+/// Only implement it manually for what the macro doesn't support, like setting [`on_open`](Self::on_open).
 pub trait Schema {
 	/// Represents the list of tables in the type system
 	type Tables: TableList;
@@ -16,6 +24,54 @@ pub trait Schema {
 	const DEFINITIONS: &'static [TableDef];
 	/// Statically generated schema definition SQL
 	const CREATE: &'static str;
+	/// Cross-table [`Invariant`](crate::invariant::Invariant)s checked by [`Database::check_invariants`](crate::Database::check_invariants)
+	///
+	/// `#[database]` doesn't set them, so list their [`Invariant::DEFINITION`](crate::invariant::Invariant::DEFINITION)s in a manual implementation, see the [`invariant`](crate::invariant) module.
+	const INVARIANTS: &'static [InvariantDef] = &[];
+	/// [`View`](crate::view::View)s created together with the tables, after them
+	///
+	/// `#[database]` doesn't set them, so list their [`View::DEFINITION`](crate::view::View::DEFINITION)s in a manual implementation, see the [`view`](crate::view) module.
+	const VIEWS: &'static [ViewDef] = &[];
+	/// [`TriggerDef`]s created together with the tables, after them
+	///
+	/// `#[database]` doesn't set them, so list them in a manual implementation, see the [`trigger`](crate::trigger) module.
+	const TRIGGERS: &'static [TriggerDef] = &[];
+
+	/// Initialize every newly opened [`Connection`]
+	///
+	/// This runs after the connection's pragmas are set and before it is used, so it's the place to register functions, collations and extensions.
+	/// `#[database]` doesn't set it, so implement [`Schema`] manually to override it:
+	///
+	///```
+	/// use liter::{Database, Schema, Table};
+	/// use liter::table::TableDef;
+	/// use liter::util::construe;
+	/// use rusqlite::Connection;
+	///
+	/// #[derive(Table)]
+	/// struct Word {
+	/// 	text: String
+	/// }
+	///
+	/// struct Db;
+	/// impl Schema for Db {
+	/// 	type Tables = (Word, );
+	/// 	const DEFINITIONS: &'static [TableDef] = &[Word::DEFINITION];
+	/// 	const CREATE: &'static str = construe!(
+	/// 		&str => liter::schema::define(&[Word::CREATE_TABLE])
+	/// 	);
+	/// 	fn on_open(connection: &Connection) -> rusqlite::Result<()> {
+	/// 		connection.pragma_update(None, "recursive_triggers", true)
+	/// 	}
+	/// }
+	///
+	/// let db = Database::<Db>::create_in_memory()?;
+	/// assert!(db.query_one::<bool>("PRAGMA recursive_triggers")?);
+	/// # Ok::<(), rusqlite::Error>(())
+	///```
+	fn on_open(_connection: &Connection) -> SqlResult<()> {
+		Ok(())
+	}
 }
 
 /// Assemble [`Table`] definitions into schema definition (internal)
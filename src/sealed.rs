@@ -0,0 +1,256 @@
+//! Tamper-evidence for tables through an HMAC over each row
+//!
+//! This module is only available with the `sealed` feature.
+//!
+//! The HMAC-SHA256 of each row of a sealed table is kept in the [`RowSeal`] table, which has to be part of your [`database`](crate::database).
+//! The key is supplied by the application, e.g. compiled into it or derived from a license, so anyone editing the database file without it can't produce valid seals.
+//! Every value of a row goes into the HMAC exactly as it is stored (unlike with SQLite's `quote()`, which rounds `REAL`s to 15 significant digits), together with the table name, so moving rows between tables is detected as well.
+//!
+//! Seals aren't updated automatically: call [`Database::seal`] after legitimately changing a sealed table.
+//!
+//!```
+//! use liter::{database, Table};
+//! use liter::sealed::{RowSeal, Tampered};
+//!
+//! #[database]
+//! struct Db (License, RowSeal);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct License {
+//! 	#[key]
+//! 	feature: String,
+//! 	seats: u32
+//! }
+//!
+//! const KEY: &[u8] = b"not a secret in this example";
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&License { feature: "pro".to_string(), seats: 5 })?;
+//! db.seal::<License>(KEY)?;
+//! assert!(db.verify_sealed::<License>(KEY)?.is_empty());
+//!
+//! db.execute("UPDATE license SET seats = 500", &())?;
+//! assert_eq!(
+//! 	db.verify_sealed::<License>(KEY)?,
+//! 	[Tampered::Modified("'pro'".to_string())]
+//! );
+//! assert!(db.get_sealed::<License>(&"pro".to_string(), KEY).is_err());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::collections::HashMap;
+
+use hmac::{
+	Hmac,
+	Mac
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::{
+	Type,
+	ValueRef
+};
+use sha2::Sha256;
+
+use crate::{
+	Bind,
+	Binder,
+	Database,
+	Entry,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// Seal of a single row of a sealed table
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+pub struct RowSeal {
+	/// [`Table::NAME`] of the sealed table
+	#[key]
+	pub table_name: String,
+	/// Primary key of the row, serialized by SQLite's `quote()`
+	#[key]
+	pub row_key: String,
+	/// HMAC-SHA256 of the row
+	pub mac: [u8; 32]
+}
+
+/// Row of a sealed table that failed verification, identified by its primary key as serialized by SQLite's `quote()`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Tampered {
+	/// The row was changed
+	Modified(String),
+	/// The row was added
+	Unsealed(String),
+	/// The row was deleted
+	Deleted(String)
+}
+
+impl<S: Schema> Database<S>
+	where RowSeal: PartOf<S>
+{
+	/// (Re-)seal all rows of the table with the key, returning how many were sealed
+	///
+	/// Seals of rows that were deleted are removed.
+	/// If sealing a row fails, the previous seals are kept.
+	pub fn seal<T>(&self, key: &[u8]) -> SqlResult<usize>
+		where T: Table + HasKey + PartOf<S>
+	{
+		self.in_savepoint("liter_seal", || {
+			self.execute_bulk(
+				&format!("DELETE FROM {} WHERE table_name = ?", RowSeal::NAME),
+				&T::NAME
+			)?;
+			let rows = self.sealed_rows::<T>()?;
+			for (row_key, row) in &rows {
+				self.insert(&RowSeal {
+					table_name: T::NAME.to_string(),
+					row_key: row_key.clone(),
+					mac: make_mac::<T>(key, row)?.finalize().into_bytes().into()
+				})?;
+			}
+			Ok(rows.len())
+		})
+	}
+	/// Check all rows of the table against their seals, returning those that don't match
+	pub fn verify_sealed<T>(&self, key: &[u8]) -> SqlResult<Vec<Tampered>>
+		where T: Table + HasKey + PartOf<S>
+	{
+		let seals: Vec<(String, [u8; 32])> = self.query_all_with(
+			&format!(
+				"SELECT row_key, mac FROM {} WHERE table_name = ?",
+				RowSeal::NAME
+			),
+			&T::NAME
+		)?;
+		let mut seals: HashMap<String, [u8; 32]> = seals.into_iter().collect();
+
+		let mut tampered = Vec::new();
+		for (row_key, row) in self.sealed_rows::<T>()? {
+			match seals.remove(&row_key) {
+				Some(mac) => if make_mac::<T>(key, &row)?.verify_slice(&mac).is_err() {
+					tampered.push(Tampered::Modified(row_key));
+				},
+				None => tampered.push(Tampered::Unsealed(row_key))
+			}
+		}
+		let mut deleted: Vec<String> = seals.into_keys().collect();
+		deleted.sort();
+		tampered.extend(deleted.into_iter().map(Tampered::Deleted));
+		Ok(tampered)
+	}
+	/// Get an entry by its primary key, but only if it matches its seal
+	///
+	/// A row that doesn't match its seal (or has none) results in an [`Error::FromSqlConversionFailure`].
+	pub fn get_sealed<T>(&self, row_key: &T::Key, key: &[u8])
+		-> SqlResult<Option<T>>
+		where T: Table + HasKey + Entry + PartOf<S>
+	{
+		let key_filter = T::KEY_COLUMNS.iter()
+			.map(|column| format!("{}.{column} = ?", T::NAME))
+			.collect::<Vec<_>>()
+			.join(" AND ");
+		let sql = format!(
+			"SELECT {table}.*, seal.mac FROM {table} \
+			LEFT JOIN {seals} AS seal \
+			ON seal.table_name = ? AND seal.row_key = {row_key} \
+			WHERE {key_filter}",
+			table = T::NAME,
+			row_key = quote_all(T::NAME, T::KEY_COLUMNS),
+			seals = RowSeal::NAME
+		);
+		let mut stmt = self.prepare(&sql)?;
+		let mut binder = Binder::make(&mut stmt);
+		T::NAME.bind(&mut binder)?;
+		row_key.bind(&mut binder)?;
+		let mut rows = stmt.raw_query();
+		let Some(row) = rows.next()? else {
+			return Ok(None);
+		};
+		let columns = T::ALL_COLUMNS.len();
+		let mac: Option<[u8; 32]> = row.get(columns)?;
+		let verified = match mac {
+			Some(mac) => make_mac::<T>(key, &serialize_row(row, 0, columns)?)?
+				.verify_slice(&mac)
+				.is_ok(),
+			None => false
+		};
+		if !verified {
+			return Err(Error::FromSqlConversionFailure(
+				columns,
+				Type::Blob,
+				format!("row of {} doesn't match its seal", T::NAME).into()
+			));
+		}
+		T::from_row(row).map(Some)
+	}
+
+	/// The primary key (serialized by `quote()`) & the serialized values of each row of the table
+	fn sealed_rows<T: Table>(&self) -> SqlResult<Vec<(String, Vec<u8>)>> {
+		let sql = format!(
+			"SELECT {} AS row_key, {} FROM {}",
+			quote_all(T::NAME, T::KEY_COLUMNS),
+			T::ALL_COLUMNS.iter()
+				.map(|column| format!("{}.{column}", T::NAME))
+				.collect::<Vec<_>>()
+				.join(", "),
+			T::NAME
+		);
+		let mut stmt = self.prepare(&sql)?;
+		let mut rows = stmt.raw_query();
+		let mut sealed = Vec::new();
+		while let Some(row) = rows.next()? {
+			sealed.push((row.get(0)?, serialize_row(row, 1, T::ALL_COLUMNS.len())?));
+		}
+		Ok(sealed)
+	}
+}
+
+/// Serialize `count` columns of the row starting at `start`, each value exactly as stored
+fn serialize_row(row: &rusqlite::Row<'_>, start: usize, count: usize) -> SqlResult<Vec<u8>> {
+	let mut bytes = Vec::new();
+	for idx in start..start + count {
+		match row.get_ref(idx)? {
+			ValueRef::Null => bytes.push(b'n'),
+			ValueRef::Integer(int) => {
+				bytes.push(b'i');
+				bytes.extend(int.to_le_bytes());
+			},
+			ValueRef::Real(real) => {
+				bytes.push(b'r');
+				bytes.extend(real.to_bits().to_le_bytes());
+			},
+			ValueRef::Text(text) => {
+				bytes.push(b't');
+				bytes.extend((text.len() as u64).to_le_bytes());
+				bytes.extend(text);
+			},
+			ValueRef::Blob(blob) => {
+				bytes.push(b'b');
+				bytes.extend((blob.len() as u64).to_le_bytes());
+				bytes.extend(blob);
+			}
+		}
+	}
+	Ok(bytes)
+}
+
+/// SQL expression serializing the columns with `quote()`, separated by commas
+fn quote_all(table: &str, columns: &[&str]) -> String {
+	columns.iter()
+		.map(|column| format!("quote({table}.{column})"))
+		.collect::<Vec<_>>()
+		.join(" || ',' || ")
+}
+
+fn make_mac<T: Table>(key: &[u8], row: &[u8]) -> SqlResult<Hmac<Sha256>> {
+	let mut mac = Hmac::<Sha256>::new_from_slice(key)
+		.map_err(|err| Error::ToSqlConversionFailure(err.into()))?;
+	mac.update(T::NAME.as_bytes());
+	mac.update(b"\0");
+	mac.update(row);
+	Ok(mac)
+}
@@ -0,0 +1,121 @@
+//! Session storage for web frameworks
+//!
+//! Sessions are stored in the [`Session`] table, which has to be part of your [`database`](crate::database).
+//! The [`SessionStore`] trait is what a web framework's session store implementation (e.g. for `tower-sessions` or `actix-session`) should wrap: it is implemented for both [`Database`] and [`SyncDatabase`], the latter of which can be shared between request handlers.
+//!
+//!```
+//! use std::time::{Duration, SystemTime};
+//! use liter::database;
+//! use liter::sessions::{Session, SessionStore};
+//!
+//! #[database]
+//! struct Db (Session);
+//!
+//! let db = Db::create_in_memory()?;
+//! let expires = SystemTime::now() + Duration::from_secs(3600);
+//!
+//! db.save_session("abc123", r#"{"user":42}"#, expires)?;
+//! assert_eq!(db.load_session("abc123")?.as_deref(), Some(r#"{"user":42}"#));
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::time::{
+	SystemTime,
+	UNIX_EPOCH
+};
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema,
+	SyncDatabase,
+	Table
+};
+use crate::meta::PartOf;
+
+/// A stored session
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+#[check("json_valid(data)")]
+pub struct Session {
+	#[key]
+	pub id: String,
+	/// Session data serialized as JSON
+	pub data: String,
+	/// UNIX timestamp (in seconds) after which the session is expired
+	pub expires: i64
+}
+
+/// Backend for storing sessions
+///
+/// Expired sessions are never loaded, but they are only deleted by [`delete_expired_sessions`](Self::delete_expired_sessions), which should be called periodically.
+pub trait SessionStore {
+	/// Load the data of a session that hasn't expired yet
+	fn load_session(&self, id: &str) -> SqlResult<Option<String>>;
+	/// Create or replace a session
+	fn save_session(&self, id: &str, data: &str, expires: SystemTime)
+		-> SqlResult<()>;
+	/// Delete a session, returning whether it existed
+	fn delete_session(&self, id: &str) -> SqlResult<bool>;
+	/// Delete all expired sessions, returning how many were deleted
+	fn delete_expired_sessions(&self) -> SqlResult<usize>;
+}
+
+impl<S: Schema> SessionStore for Database<S>
+	where Session: PartOf<S>
+{
+	fn load_session(&self, id: &str) -> SqlResult<Option<String>> {
+		let sql = format!(
+			"SELECT data FROM {} WHERE id = ? AND expires > ?",
+			Session::NAME
+		);
+		let mut data: Vec<String> =
+			self.query_all_with(&sql, &(id, unix_time(SystemTime::now())))?;
+		Ok(data.pop())
+	}
+	fn save_session(&self, id: &str, data: &str, expires: SystemTime)
+		-> SqlResult<()>
+	{
+		self.upsert(&Session {
+			id: id.to_string(),
+			data: data.to_string(),
+			expires: unix_time(expires)
+		}).map(|_| ())
+	}
+	fn delete_session(&self, id: &str) -> SqlResult<bool> {
+		self.delete::<Session>(&id.to_string())
+	}
+	fn delete_expired_sessions(&self) -> SqlResult<usize> {
+		self.execute_bulk(
+			&format!("DELETE FROM {} WHERE expires <= ?", Session::NAME),
+			&unix_time(SystemTime::now())
+		)
+	}
+}
+
+impl<S: Schema> SessionStore for SyncDatabase<S>
+	where Session: PartOf<S>
+{
+	fn load_session(&self, id: &str) -> SqlResult<Option<String>> {
+		self.lock().load_session(id)
+	}
+	fn save_session(&self, id: &str, data: &str, expires: SystemTime)
+		-> SqlResult<()>
+	{
+		self.lock().save_session(id, data, expires)
+	}
+	fn delete_session(&self, id: &str) -> SqlResult<bool> {
+		self.lock().delete_session(id)
+	}
+	fn delete_expired_sessions(&self) -> SqlResult<usize> {
+		self.lock().delete_expired_sessions()
+	}
+}
+
+/// Seconds since the UNIX epoch, negative for times before it
+fn unix_time(time: SystemTime) -> i64 {
+	match time.duration_since(UNIX_EPOCH) {
+		Ok(since) => since.as_secs() as i64,
+		Err(before) => -(before.duration().as_secs() as i64)
+	}
+}
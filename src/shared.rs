@@ -0,0 +1,86 @@
+//! [`SyncDatabase`]: a [`Database`] handle that can be shared between threads
+
+use std::sync::{
+	Mutex,
+	MutexGuard,
+	PoisonError
+};
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Entry,
+	HasKey,
+	RowId,
+	Schema
+};
+use crate::meta::tuple::marker;
+
+/// Thread-safe handle to a [`Database`]
+///
+/// A [`Database`] wraps a single SQLite connection, which can't be used from multiple threads at the same time.
+/// The [`SyncDatabase`] serializes access with a [`Mutex`], so it is `Send + Sync` and can be put in an [`Arc`](std::sync::Arc) (e.g. to be shared as web framework app state).
+///
+/// Use [`lock`](Self::lock) to get exclusive access to the [`Database`] for several calls (or a [`Transaction`](crate::Transaction)) in a row.
+/// The most common methods are also available directly, each of which locks the [`Database`] for just that call.
+#[derive(Debug)]
+pub struct SyncDatabase<S: Schema> {
+	db: Mutex<Database<S>>
+}
+
+impl<S: Schema> SyncDatabase<S> {
+	pub fn new(db: Database<S>) -> Self {
+		Self { db: Mutex::new(db) }
+	}
+	/// Wait for exclusive access to the [`Database`]
+	///
+	/// If another thread panicked while holding the lock, the [`Database`] is still returned: any [`Transaction`](crate::Transaction) it had open was rolled back when it was dropped.
+	pub fn lock(&self) -> MutexGuard<'_, Database<S>> {
+		self.db.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	/// Unwrap the [`Database`]
+	pub fn into_inner(self) -> Database<S> {
+		self.db.into_inner().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	pub fn get_all<T: Entry>(&self) -> SqlResult<Vec<T>> {
+		self.lock().get_all()
+	}
+	pub fn get<T>(&self, key: <T as HasKey>::Key) -> SqlResult<Option<T>>
+		where T: Entry + HasKey
+	{
+		self.lock().get(key)
+	}
+	/// See [`Database::create`]
+	#[track_caller]
+	pub fn create<T>(&self, entry: &mut T) -> SqlResult<()>
+		where T: Entry + HasKey<Marker = marker::One>, T::Key: RowId
+	{
+		self.lock().create(entry)
+	}
+	#[track_caller]
+	pub fn insert<T: Entry>(&self, entry: &T) -> SqlResult<usize> {
+		self.lock().insert(entry)
+	}
+	#[track_caller]
+	pub fn upsert<T: HasKey + Entry>(&self, entry: &T) -> SqlResult<usize> {
+		self.lock().upsert(entry)
+	}
+	#[track_caller]
+	pub fn update<T: HasKey + Entry>(&self, entry: &T) -> SqlResult<usize> {
+		self.lock().update(entry)
+	}
+	#[track_caller]
+	pub fn delete<T>(&self, key: &<T as HasKey>::Key) -> SqlResult<bool>
+		where T: Entry + HasKey
+	{
+		self.lock().delete::<T>(key)
+	}
+}
+
+impl<S: Schema> From<Database<S>> for SyncDatabase<S> {
+	fn from(db: Database<S>) -> Self {
+		Self::new(db)
+	}
+}
@@ -0,0 +1,244 @@
+//! Minimal interactive shell for inspecting a [`Database`] during development
+//!
+//! This module is only available with the `shell` feature.
+//!
+//! Unlike the `sqlite3` command line tool, the shell knows the [`Schema`]: it lists its tables, and shows their columns as named by [`Table::ALL_COLUMNS`](crate::Table::ALL_COLUMNS).
+//! Statements are run with bound parameters, and only if they're read-only, so the shell can't change the database.
+//!
+//! These commands are understood, anything else is run as an SQL statement:
+//! - `.tables`: list the tables & their number of rows
+//! - `.columns TABLE`: list the columns of the table
+//! - `.browse TABLE [LIMIT [OFFSET]]`: show the rows of the table, 20 at a time by default
+//! - `.param VALUE`: add a parameter for the next statement: `NULL`, a number, or text (optionally in single quotes)
+//! - `.help`, `.quit`
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Planet);
+//!
+//! #[derive(Table)]
+//! struct Planet {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Planet { id: Id::new(1), name: "Mercury".to_string() })?;
+//!
+//! let input = ".tables\n.param 1\nSELECT name FROM planet WHERE id = ?\nDELETE FROM planet\n";
+//! let mut output = Vec::new();
+//! liter::shell::run_with(&db, input.as_bytes(), &mut output)?;
+//! let output = String::from_utf8(output)?;
+//! assert!(output.contains("planet (1 rows)"));
+//! assert!(output.contains("Mercury"));
+//! assert!(output.contains("only read-only statements"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//!```
+//!
+//! In a development build, `liter::shell::run(&db)` reads from standard input instead.
+
+use std::io::{
+	self,
+	BufRead,
+	Write
+};
+
+use rusqlite::{
+	params_from_iter,
+	Error,
+	Result as SqlResult,
+	Statement
+};
+use rusqlite::types::{
+	Value,
+	ValueRef
+};
+
+use crate::{
+	Database,
+	Schema
+};
+use crate::table::{
+	self,
+	TableDef
+};
+
+const PROMPT: &str = "liter> ";
+/// Number of rows shown by `.browse` without a limit
+const PAGE: i64 = 20;
+/// BLOBs longer than this are only shown by their size
+const MAX_BLOB: usize = 32;
+
+const HELP: &str = "\
+.tables                         list the tables & their number of rows
+.columns TABLE                  list the columns of the table
+.browse TABLE [LIMIT [OFFSET]]  show the rows of the table
+.param VALUE                    add a parameter for the next statement
+.help                           show this help
+.quit                           leave the shell
+anything else is run as a (read-only) SQL statement";
+
+/// Run the shell on standard input & output, until `.quit` or the end of the input
+pub fn run<S: Schema>(db: &Database<S>) -> io::Result<()> {
+	run_with(db, io::stdin().lock(), io::stdout().lock())
+}
+
+/// Run the shell on the given input & output, until `.quit` or the end of the input
+///
+/// Errors from the database are written to the output, only I/O errors end the shell early.
+pub fn run_with<S, R, W>(db: &Database<S>, input: R, mut output: W) -> io::Result<()>
+	where S: Schema, R: BufRead, W: Write
+{
+	let mut params = Vec::new();
+	write!(output, "{PROMPT}")?;
+	output.flush()?;
+	for line in input.lines() {
+		let line = line?;
+		let line = line.trim();
+		let (command, args) = line.split_once(char::is_whitespace)
+			.map_or((line, ""), |(command, args)| (command, args.trim()));
+		let result = match command {
+			"" => Ok(()),
+			".quit" | ".exit" => return Ok(()),
+			".help" => writeln!(output, "{HELP}").map_err(io_error),
+			".tables" => tables(db, &mut output),
+			".columns" => find_table::<S>(args).and_then(|def| columns(def, &mut output)),
+			".browse" => browse(db, args, &mut output),
+			".param" => {
+				params.push(parse_value(args));
+				Ok(())
+			},
+			_ if command.starts_with('.') =>
+				writeln!(output, "unknown command {command}, see .help").map_err(io_error),
+			_ => query(db, line, &std::mem::take(&mut params), &mut output)
+		};
+		if let Err(error) = result {
+			match error {
+				Error::ToSqlConversionFailure(error) => match error.downcast::<io::Error>() {
+					Ok(error) => return Err(*error),
+					Err(error) => writeln!(output, "error: {error}")?
+				},
+				error => writeln!(output, "error: {error}")?
+			}
+		}
+		write!(output, "{PROMPT}")?;
+		output.flush()?;
+	}
+	writeln!(output)
+}
+
+fn tables<S: Schema>(db: &Database<S>, output: &mut impl Write) -> SqlResult<()> {
+	for def in S::DEFINITIONS {
+		let rows: i64 = db.query_one(&format!("SELECT count(*) FROM \"{}\"", def.name))?;
+		writeln!(output, "{} ({rows} rows)", def.name).map_err(io_error)?;
+	}
+	Ok(())
+}
+
+fn columns(def: &TableDef, output: &mut impl Write) -> SqlResult<()> {
+	for (name, column) in table::columns(def) {
+		let key = match def.primary_key.iter().any(|key| *key == name) {
+			true => " (key)",
+			false => ""
+		};
+		let nullable = match column.nullable {
+			true => "",
+			false => " NOT NULL"
+		};
+		writeln!(output, "{name}: {}{nullable}{key}", column.affinity.as_str())
+			.map_err(io_error)?;
+	}
+	Ok(())
+}
+
+fn browse<S: Schema>(db: &Database<S>, args: &str, output: &mut impl Write) -> SqlResult<()> {
+	let mut args = args.split_whitespace();
+	let def = find_table::<S>(args.next().unwrap_or(""))?;
+	let mut number = || args.next()
+		.map(|arg| arg.parse::<i64>().map_err(|e| Error::ToSqlConversionFailure(e.into())))
+		.transpose();
+	let limit = number()?.unwrap_or(PAGE);
+	let offset = number()?.unwrap_or(0);
+	let names: Vec<String> = table::columns(def).into_iter()
+		.map(|(name, _column)| name)
+		.collect();
+	let sql = format!(
+		"SELECT {} FROM \"{}\" LIMIT ? OFFSET ?",
+		names.join(", "),
+		def.name
+	);
+	let mut stmt = db.prepare(&sql)?;
+	print_rows(&mut stmt, &[Value::Integer(limit), Value::Integer(offset)], output)
+}
+
+fn query<S: Schema>(db: &Database<S>, sql: &str, params: &[Value], output: &mut impl Write)
+	-> SqlResult<()>
+{
+	let mut stmt = db.prepare(sql)?;
+	if !stmt.readonly() {
+		writeln!(output, "only read-only statements are run").map_err(io_error)?;
+		return Ok(());
+	}
+	print_rows(&mut stmt, params, output)
+}
+
+/// Print the column names, followed by the rows, separated by tabs
+fn print_rows(stmt: &mut Statement<'_>, params: &[Value], output: &mut impl Write)
+	-> SqlResult<()>
+{
+	let header = stmt.column_names().join("\t");
+	let count = stmt.column_count();
+	writeln!(output, "{header}").map_err(io_error)?;
+	let mut rows = stmt.query(params_from_iter(params))?;
+	while let Some(row) = rows.next()? {
+		let mut values = Vec::with_capacity(count);
+		for idx in 0..count {
+			values.push(display(row.get_ref(idx)?));
+		}
+		writeln!(output, "{}", values.join("\t")).map_err(io_error)?;
+	}
+	Ok(())
+}
+
+fn find_table<S: Schema>(name: &str) -> SqlResult<&'static TableDef> {
+	S::DEFINITIONS.iter()
+		.find(|def| def.name == name)
+		.ok_or_else(|| Error::ToSqlConversionFailure(format!("no table named {name:?}").into()))
+}
+
+fn display(value: ValueRef<'_>) -> String {
+	match value {
+		ValueRef::Null => "NULL".to_string(),
+		ValueRef::Integer(int) => int.to_string(),
+		ValueRef::Real(real) => real.to_string(),
+		ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+		ValueRef::Blob(blob) if blob.len() > MAX_BLOB => format!("<{} bytes>", blob.len()),
+		ValueRef::Blob(blob) => {
+			let hex: String = blob.iter().map(|byte| format!("{byte:02x}")).collect();
+			format!("x'{hex}'")
+		}
+	}
+}
+
+fn parse_value(arg: &str) -> Value {
+	if arg.eq_ignore_ascii_case("null") {
+		return Value::Null;
+	}
+	if let Ok(int) = arg.parse() {
+		return Value::Integer(int);
+	}
+	if let Ok(real) = arg.parse() {
+		return Value::Real(real);
+	}
+	let text = arg.strip_prefix('\'')
+		.and_then(|quoted| quoted.strip_suffix('\''))
+		.unwrap_or(arg);
+	Value::Text(text.to_string())
+}
+
+fn io_error(error: io::Error) -> Error {
+	Error::ToSqlConversionFailure(error.into())
+}
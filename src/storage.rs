@@ -0,0 +1,151 @@
+//! Measuring how much space the columns of a [`Table`] take up
+//!
+//! [`Database::storage_report`] adds up the sizes of the values in each column, and estimates how much [dictionary encoding](ColumnStorage::dictionary_savings) would save: storing each distinct value once in a separate table, and only an integer id in the column.
+//! This helps deciding e.g. whether a repetitive `TEXT` column is worth normalizing into its own table.
+//!
+//! Sizes are the sizes of the values themselves, not counting SQLite's page & record overhead.
+//! `TEXT` and `BLOB` values are counted in bytes, while numbers are counted as 8 bytes, which is the most SQLite uses to store them.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Visit);
+//!
+//! #[derive(Table)]
+//! struct Visit {
+//! 	#[key]
+//! 	id: Id,
+//! 	browser: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for id in 1..=100 {
+//! 	let browser = ["Mozilla/5.0 (X11; Linux x86_64)", "Mozilla/5.0 (Macintosh)"][id % 2];
+//! 	db.insert(&Visit { id: Id::new(id as i64), browser: browser.to_string() })?;
+//! }
+//!
+//! let report = db.storage_report::<Visit>()?;
+//! let browser = &report.columns[1];
+//! assert_eq!(browser.distinct, 2);
+//! assert!(browser.dictionary_savings > browser.total_bytes / 2);
+//! println!("{report}");
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+/// Storage used by the columns of a [`Table`], see [`Database::storage_report`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageReport {
+	/// Name of the [`Table`], as in [`Table::NAME`]
+	pub table: &'static str,
+	pub rows: u64,
+	/// The columns in the order of [`Table::ALL_COLUMNS`]
+	pub columns: Vec<ColumnStorage>
+}
+
+/// Storage used by a single column
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnStorage {
+	pub name: &'static str,
+	/// Size of all values, in bytes
+	pub total_bytes: u64,
+	/// Number of distinct non-`NULL` values
+	pub distinct: u64,
+	/// Size of all distinct values, in bytes
+	pub distinct_bytes: u64,
+	/// Estimated number of bytes saved by storing each distinct value once, and an integer id per row instead
+	///
+	/// This is `0` if dictionary encoding wouldn't save anything.
+	pub dictionary_savings: u64
+}
+
+impl ColumnStorage {
+	/// Average size of a value in bytes, `0` for an empty table
+	pub fn average_bytes(&self, rows: u64) -> u64 {
+		self.total_bytes.checked_div(rows).unwrap_or(0)
+	}
+}
+
+impl fmt::Display for StorageReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "{} ({} rows)", self.table, self.rows)?;
+		for column in &self.columns {
+			writeln!(
+				f,
+				"\t{}: {} bytes ({} per row), {} distinct, dictionary encoding saves ~{} bytes",
+				column.name,
+				column.total_bytes,
+				column.average_bytes(self.rows),
+				column.distinct,
+				column.dictionary_savings
+			)?;
+		}
+		Ok(())
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Measure the space taken up by each column of the table
+	///
+	/// This reads the entire table, once for each column.
+	pub fn storage_report<T>(&self) -> SqlResult<StorageReport>
+		where T: Table + PartOf<S>
+	{
+		let rows = self.query_one::<i64>(&format!("SELECT count(*) FROM \"{}\"", T::NAME))? as u64;
+		let mut columns = Vec::new();
+		for &name in T::ALL_COLUMNS {
+			let size = format!(
+				"CASE typeof({name}) \
+				WHEN 'null' THEN 0 WHEN 'integer' THEN 8 WHEN 'real' THEN 8 \
+				ELSE length(CAST({name} AS BLOB)) END"
+			);
+			let sql = format!(
+				"SELECT coalesce(sum({size}), 0), \
+				(SELECT count(*) FROM (SELECT DISTINCT {name} FROM \"{table}\" WHERE {name} IS NOT NULL)), \
+				(SELECT coalesce(sum({size}), 0) FROM (SELECT DISTINCT {name} FROM \"{table}\")) \
+				FROM \"{table}\"",
+				table = T::NAME
+			);
+			let (total_bytes, distinct, distinct_bytes): (i64, i64, i64) = self.query_one(&sql)?;
+			let (total_bytes, distinct, distinct_bytes) =
+				(total_bytes as u64, distinct as u64, distinct_bytes as u64);
+			let encoded = distinct_bytes + rows * integer_size(distinct);
+			columns.push(ColumnStorage {
+				name,
+				total_bytes,
+				distinct,
+				distinct_bytes,
+				dictionary_savings: total_bytes.saturating_sub(encoded)
+			});
+		}
+		Ok(StorageReport {
+			table: T::NAME,
+			rows,
+			columns
+		})
+	}
+}
+
+/// Number of bytes SQLite uses to store the integer
+fn integer_size(value: u64) -> u64 {
+	match value {
+		0..=1 => 0, // stored in the record header
+		2..=0x7f => 1,
+		0x80..=0x7fff => 2,
+		0x8000..=0x7f_ffff => 3,
+		0x80_0000..=0x7fff_ffff => 4,
+		0x8000_0000..=0x7fff_ffff_ffff => 6,
+		_ => 8
+	}
+}
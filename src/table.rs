@@ -11,6 +11,10 @@ use crate::{
 	Fetch,
 	Ref
 };
+use crate::column::{
+	Affinity,
+	ColumnDef
+};
 use crate::meta::tuple::{
 	Tuple,
 	marker,
@@ -18,6 +22,7 @@ use crate::meta::tuple::{
 	CloneFromRef
 };
 use crate::value::{
+	Check,
 	ValueDef,
 	NestedValueDef,
 	StrChain
@@ -28,12 +33,24 @@ use crate::value::{
 /// The items that make up this trait are mostly an implementation detail.
 /// See the [`Entry`] trait (which is also implemented by the `#[derive(Table)]` proc-macro) as well as the [`HasKey`] trait (same, but only if the table has a primary key) for SQL generated to be used by you.
 pub trait Table {
-	/// Name of the table: `#[derive(Table)]` uses the lowercase name of the struct
+	/// Name of the table: `#[derive(Table)]` uses the lowercase name of the struct, a manual implementation can use any other name
+	///
+	/// All generated SQL (including the `REFERENCES` of [`Ref`]s to this table and the names of its indexes) uses this name, so it has to be a valid SQL identifier.
 	const NAME: &'static str;
 	/// The [`TableDef`] struct defines the table and is used to assemble the `CREATE_TABLE` SQL statement
 	const DEFINITION: TableDef;
 	/// `CREATE TABLE` SQL statement
 	const CREATE_TABLE: &'static str;
+	/// Whether [`CREATE_TABLE`](Self::CREATE_TABLE) creates a [`STRICT`](https://sqlite.org/stricttables.html) table
+	///
+	/// `#[derive(Table)]` always creates strict tables.
+	/// For matching existing databases or extensions that don't support strict tables, set this to `false` in a manual implementation and pass it to [`TableDef::define_with`].
+	/// In a non-strict table, the [`Affinity`] of a [`Column`](crate::Column) is only a preference, and SQLite will store values of any type in it.
+	const STRICT: bool = true;
+	/// Secondary [`Index`]es of the table
+	///
+	/// Their `CREATE INDEX` statements are generated with [`define_indexes`] and have to follow [`CREATE_TABLE`](Self::CREATE_TABLE) in the [`Schema::CREATE`](crate::Schema::CREATE) SQL.
+	const INDEXES: &'static [Index] = &[];
 
 	/// Names of all the [`Column`](crate::Column)s that make up the table.
 	const ALL_COLUMNS: &'static [&'static str];
@@ -87,6 +104,17 @@ pub enum Constraint {
 	Unique(Values)
 }
 
+/// Secondary index on one or more [`Value`](crate::Value)s of a [`Table`]
+///
+/// The index is over the columns of the named values (in that order), and is named after the table and the values, e.g. `item_a_b_index`.
+#[derive(Debug)]
+pub struct Index {
+	/// Whether this is a `UNIQUE` index
+	pub unique: bool,
+	/// Names of the [`Value`](crate::Value)s (i.e. fields) the index is over
+	pub values: &'static [&'static str]
+}
+
 /// SQL statements for interacting with a [`Table`]
 ///
 /// This trait is also implemented by `#[derive(Table)]` and contains SQL statements to retrieve and insert entries from/into the table.
@@ -101,6 +129,10 @@ pub trait Entry: Sized + Fetch + Bind {
 	/// Insert a new row into the [`Table`] for this type.
 	/// [`Bind`] is used to bind an instance of this type to the parameters in the correct order.
 	const INSERT: &'static str;
+	/// Whether the table has generated columns, which [`INSERT`](Self::INSERT) (and [`HasKey::UPSERT`] & [`HasKey::UPDATE`]) leave out
+	///
+	/// Those statements are then generated with [`insert_for`], [`upsert_for`] & [`update_for`], and the parameters of the generated columns are skipped when binding.
+	const HAS_GENERATED: bool = false;
 }
 
 /// [`Table`] that has a primary key, which may be composite
@@ -152,7 +184,12 @@ impl<T: HasKey<Marker = marker::One>> HasSingleKey<T::Key> for T {}
 impl<T: HasKey<Marker = marker::Many>> HasCompositeKey<T::Key> for T {}
 
 impl TableDef {
+	/// `CREATE TABLE` statement of a [`STRICT`](Table::STRICT) table
 	pub const fn define<const N: usize>(&self) -> StrConstrue<N> {
+		self.define_with(true)
+	}
+	/// `CREATE TABLE` statement, of a [`STRICT`](Table::STRICT) table if `strict` is set
+	pub const fn define_with<const N: usize>(&self, strict: bool) -> StrConstrue<N> {
 		let mut sc = StrConstrue::new();
 		sc = sc.push_str("CREATE TABLE ");
 		sc = sc.push_str(self.name);
@@ -208,10 +245,93 @@ impl TableDef {
 			sc = constraint.push_sql(sc);
 		}
 
-		sc.push_str("\n) STRICT;")
+		match strict {
+			true => sc.push_str("\n) STRICT;"),
+			false => sc.push_str("\n);")
+		}
 	}
 }
 
+impl Index {
+	/// Write out the `CREATE INDEX` statement for the [`Table`] defined by `def`
+	const fn push_sql<const N: usize>(
+		&self,
+		def: &TableDef,
+		mut sc: StrConstrue<N>)
+		-> StrConstrue<N>
+	{
+		let [first_name, other_names @ ..] = self.values else {
+			panic!("index without values")
+		};
+		sc = match self.unique {
+			true => sc.push_str("CREATE UNIQUE INDEX "),
+			false => sc.push_str("CREATE INDEX ")
+		};
+		write!(sc, def.name, "_", *first_name);
+		let mut names = other_names;
+		while let [name, rest @ ..] = names {
+			names = rest;
+			write!(sc, "_", *name);
+		}
+		write!(sc, "_index ON ", def.name, " ( ");
+
+		sc = find_value(def, first_name).inner
+			.push_column_names(&StrChain::start(first_name), sc);
+		let mut names = other_names;
+		while let [name, rest @ ..] = names {
+			names = rest;
+			sc = sc.push_str(", ");
+			sc = find_value(def, name).inner
+				.push_column_names(&StrChain::start(name), sc);
+		}
+		sc.push_str(" );")
+	}
+}
+
+/// [`ValueDef`] of the table's value named `name`
+const fn find_value<'d>(def: &'d TableDef, name: &str) -> &'d ValueDef {
+	let mut values = def.values;
+	while let [(value_name, value), rest @ ..] = values {
+		values = rest;
+		if str_eq(value_name, name) {
+			return value;
+		}
+	}
+	panic!("index on a value that isn't part of the table")
+}
+
+pub(crate) const fn str_eq(a: &str, b: &str) -> bool {
+	let (a, b) = (a.as_bytes(), b.as_bytes());
+	if a.len() != b.len() {
+		return false;
+	}
+	let mut i = 0;
+	while i < a.len() {
+		if a[i] != b[i] {
+			return false;
+		}
+		i += 1;
+	}
+	true
+}
+
+/// Generates the `CREATE INDEX` statements for the [`Table::INDEXES`] at compile-time
+///
+/// Panics if an [`Index`] names a value that isn't part of the table.
+pub const fn define_indexes<const N: usize>(def: &TableDef, mut indexes: &[Index])
+	-> StrConstrue<N>
+{
+	let mut sc = StrConstrue::new();
+	while let [index, rest @ ..] = indexes {
+		indexes = rest;
+		sc = index.push_sql(def, sc);
+		if !rest.is_empty() {
+			sc = sc.push_str("\n");
+		}
+	}
+	sc
+}
+
 impl Constraint {
 	const fn push_sql<const N: usize>(&self, mut sc: StrConstrue<N>)
 		-> StrConstrue<N>
@@ -239,6 +359,28 @@ impl Constraint {
 	}
 }
 
+/// Generates the [`Entry::GET_ALL`] statement at compile-time
+pub const fn get_all<const N: usize>(name: &str) -> StrConstrue<N> {
+	StrConstrue::new()
+		.push_str("SELECT * FROM ")
+		.push_str(name)
+}
+
+/// The lowercase `name`, which `#[derive(Table)]` uses as the [`Table::NAME`] of a struct
+pub const fn lowercase<const N: usize>(name: &str) -> StrConstrue<N> {
+	let mut sc = StrConstrue::new();
+	let mut bytes = name.as_bytes();
+	while let [byte, rest @ ..] = bytes {
+		bytes = rest;
+		assert!(byte.is_ascii(), "table name isn't ASCII");
+		sc = match std::str::from_utf8(&[byte.to_ascii_lowercase()]) {
+			Ok(lower) => sc.push_str(lower),
+			Err(_e) => unreachable!()
+		};
+	}
+	sc
+}
+
 /// Generates the [`HasKey::GET_BY_KEY`] statement at compile-time
 pub const fn get_by_key<const N: usize>(name: &str, key_columns: &[&str])
 	-> StrConstrue<N>
@@ -302,6 +444,323 @@ pub const fn insert<const N: usize>(name: &str, column_count: usize)
 	sc.push_str(")")
 }
 
+/// Whether the column at `column_idx` (in [`Table::ALL_COLUMNS`] order) is generated
+const fn is_generated(def: &TableDef, column_idx: usize) -> bool {
+	let mut values = def.values;
+	let mut start = 0;
+	while let [(_name, value), rest @ ..] = values {
+		values = rest;
+		let end = start + value.inner.count_columns();
+		if column_idx < end {
+			return value.clauses().generated.is_some();
+		}
+		start = end;
+	}
+	panic!("column index out of bounds")
+}
+
+/// Whether the column is part of the primary key
+const fn is_key(def: &TableDef, column: &str) -> bool {
+	let mut key_columns = def.primary_key;
+	while let [key_column, rest @ ..] = key_columns {
+		key_columns = rest;
+		if str_eq(key_column, column) {
+			return true;
+		}
+	}
+	false
+}
+
+/// Write out `INSERT INTO "name" (a, c) VALUES (?1, ?3)`, leaving out generated columns
+const fn push_insert_columns<const N: usize>(
+	def: &TableDef,
+	all_columns: &[&str],
+	mut sc: StrConstrue<N>)
+	-> StrConstrue<N>
+{
+	write!(sc, "INSERT INTO \"", def.name, "\" (");
+	let mut is_first = true;
+	let mut idx = 0;
+	while idx < all_columns.len() {
+		if !is_generated(def, idx) {
+			if !is_first {
+				sc = sc.push_str(", ");
+			}
+			else {is_first = false;}
+			sc = sc.push_str(all_columns[idx]);
+		}
+		idx += 1;
+	}
+	sc = sc.push_str(") VALUES (");
+	let mut is_first = true;
+	let mut idx = 0;
+	while idx < all_columns.len() {
+		if !is_generated(def, idx) {
+			if !is_first {
+				sc = sc.push_str(", ");
+			}
+			else {is_first = false;}
+			write!(sc, "?", idx + 1); // params 1-based
+		}
+		idx += 1;
+	}
+	sc.push_str(")")
+}
+
+/// Generates the [`Entry::INSERT`] statement at compile-time for tables with generated columns
+///
+/// Unlike [`insert`], this names the columns, so that the generated ones can be left out.
+/// The parameters are numbered (`?n`) in the [`Entry`] [`Bind`] order, so the parameters of the generated columns are simply unused.
+pub const fn insert_for<const N: usize>(def: &TableDef, all_columns: &[&str])
+	-> StrConstrue<N>
+{
+	push_insert_columns(def, all_columns, StrConstrue::new())
+}
+
+/// Generates the [`HasKey::UPSERT`] statement at compile-time for tables with generated columns
+///
+/// See [`insert_for`].
+pub const fn upsert_for<const N: usize>(def: &TableDef, all_columns: &[&str])
+	-> StrConstrue<N>
+{
+	let mut sc = push_insert_columns(def, all_columns, StrConstrue::new());
+	sc = sc.push_str(" ON CONFLICT (");
+
+	let [first, other_key_columns @ ..] = def.primary_key else {
+		panic!("no key columns")
+	};
+	sc = sc.push_str(first);
+	let mut columns = other_key_columns;
+	while let [name, rest @ ..] = columns {
+		sc = sc.push_str(", ").push_str(name);
+		columns = rest;
+	}
+	sc = sc.push_str(") ");
+
+	let mut is_first = true;
+	let mut idx = 0;
+	while idx < all_columns.len() {
+		let name = all_columns[idx];
+		if !is_generated(def, idx) && !is_key(def, name) {
+			if !is_first {
+				sc = sc.push_str(", ");
+			}
+			else {
+				sc = sc.push_str("DO UPDATE SET ");
+				is_first = false;
+			}
+			write!(sc, name, " = excluded.", name);
+		}
+		idx += 1;
+	}
+	if is_first {
+		// only key & generated columns
+		sc = sc.push_str("DO NOTHING");
+	}
+	sc
+}
+
+/// Generates the [`HasKey::UPDATE`] statement at compile-time for tables with generated columns
+///
+/// Like [`update`], but the generated columns are left out of the `SET`.
+pub const fn update_for<const N: usize>(def: &TableDef, all_columns: &[&str])
+	-> StrConstrue<N>
+{
+	let mut sc = StrConstrue::new();
+	write!(sc, "UPDATE \"", def.name, "\" SET ");
+
+	let mut is_first = true;
+	let mut idx = 0;
+	while idx < all_columns.len() {
+		let name = all_columns[idx];
+		if !is_generated(def, idx) && !is_key(def, name) {
+			if !is_first {
+				sc = sc.push_str(", ");
+			}
+			else {is_first = false;}
+			write!(sc, name, " = ?", idx + 1); // params 1-based
+		}
+		idx += 1;
+	}
+	sc = sc.push_str(" WHERE ");
+
+	let mut is_first = true;
+	let mut idx = 0;
+	while idx < all_columns.len() {
+		let name = all_columns[idx];
+		if is_key(def, name) {
+			if !is_first {
+				sc = sc.push_str(" AND ");
+			}
+			else {is_first = false;}
+			write!(sc, name, " = ?", idx + 1); // params 1-based
+		}
+		idx += 1;
+	}
+	sc
+}
+
+/// Generates the `INSERT` statement used by [`Database::insert_with_defaults`](crate::Database::insert_with_defaults)
+///
+/// Unlike the other statements, this one is assembled at runtime.
+/// The columns of values with a `DEFAULT` (and generated columns) are left out, and the parameters are numbered (`?n`) in the [`Entry`] [`Bind`] order, skipping over those columns.
+pub fn insert_with_defaults(def: &TableDef, all_columns: &[&str]) -> String {
+	let mut columns = Vec::new();
+	let mut params = Vec::new();
+	let mut param_idx = 0;
+	for (_name, value) in def.values {
+		let count = value.inner.count_columns();
+		let clauses = value.clauses();
+		if clauses.default.is_none() && clauses.generated.is_none() {
+			let value_columns = all_columns.iter()
+				.enumerate()
+				.skip(param_idx)
+				.take(count);
+			for (idx, column) in value_columns {
+				columns.push(*column);
+				params.push(format!("?{}", idx + 1)); // params 1-based
+			}
+		}
+		param_idx += count;
+	}
+	match columns.is_empty() {
+		true => format!("INSERT INTO \"{}\" DEFAULT VALUES", def.name),
+		false => format!(
+			"INSERT INTO \"{}\" ({}) VALUES ({})",
+			def.name,
+			columns.join(", "),
+			params.join(", ")
+		)
+	}
+}
+
+/// Generates the `SELECT` statement used by [`Database::get_all_without`](crate::Database::get_all_without)
+///
+/// Like [`insert_with_defaults`], this is assembled at runtime.
+/// The skipped columns are replaced by a literal that is cheap to read: `NULL` for nullable (and `ANY`) columns, and otherwise the empty (or zero) value of their [`Affinity`](crate::column::Affinity).
+/// Returns the name of the first column in `skip` that isn't part of the table as the error.
+pub fn select_without<'s>(def: &TableDef, all_columns: &[&str], skip: &[&'s str])
+	-> Result<String, &'s str>
+{
+	let unknown = skip.iter()
+		.find(|skipped| !all_columns.iter().any(|column| column == *skipped));
+	if let Some(unknown) = unknown {
+		return Err(unknown);
+	}
+	let mut column_defs = Vec::new();
+	for (_name, value) in def.values {
+		collect_column_defs(value, false, &mut column_defs);
+	}
+	let columns: Vec<&str> = all_columns.iter()
+		.zip(column_defs)
+		.map(|(name, column)| match skip.iter().any(|skipped| skipped == name) {
+			false => *name,
+			true if column.nullable => "NULL",
+			true => match column.affinity {
+				Affinity::Integer => "0",
+				Affinity::Real => "0.0",
+				Affinity::Text => "''",
+				Affinity::Blob => "x''",
+				Affinity::Any => "NULL"
+			}
+		})
+		.collect();
+	Ok(format!("SELECT {} FROM \"{}\"", columns.join(", "), def.name))
+}
+
+/// Names of the columns that have the [`Check`], e.g. to find the vector column searched by `Database::nearest`
+pub fn columns_with_check<'c>(def: &TableDef, all_columns: &'c [&'c str], check: Check)
+	-> Vec<&'c str>
+{
+	let mut column_defs = Vec::new();
+	for (_name, value) in def.values {
+		collect_column_defs(value, false, &mut column_defs);
+	}
+	all_columns.iter()
+		.zip(column_defs)
+		.filter(|(_name, column)| column.checks.contains(&check))
+		.map(|(name, _column)| *name)
+		.collect()
+}
+
+/// Names and [`ColumnDef`]s of all columns of the table, in the order of [`Table::ALL_COLUMNS`]
+///
+/// This is for when only the [`TableDef`] is known, e.g. when going through a [`Schema`](crate::Schema)'s `DEFINITIONS`.
+pub fn columns(def: &TableDef) -> Vec<(String, ColumnDef)> {
+	let mut names = Vec::new();
+	let mut column_defs = Vec::new();
+	for (name, value) in def.values {
+		collect_column_names(name, value, &mut names);
+		collect_column_defs(value, false, &mut column_defs);
+	}
+	names.into_iter().zip(column_defs).collect()
+}
+
+/// Push the name of each column of the value, in order, joining nested names with `_` like the generated SQL does
+fn collect_column_names(prefix: &str, def: &ValueDef, names: &mut Vec<String>) {
+	match &def.inner {
+		NestedValueDef::Column(_) | NestedValueDef::ColumnWith(..) =>
+			names.push(prefix.to_string()),
+		NestedValueDef::Value(inner) => collect_column_names(prefix, inner, names),
+		NestedValueDef::Values(values) => for (name, inner) in values.iter() {
+			collect_column_names(&format!("{prefix}_{name}"), inner, names);
+		}
+	}
+}
+
+/// Push the [`ColumnDef`] of each column of the value, in order
+fn collect_column_defs(def: &ValueDef, nullable: bool, columns: &mut Vec<ColumnDef>) {
+	let nullable = nullable || def.nullable;
+	match &def.inner {
+		NestedValueDef::Column(column) | NestedValueDef::ColumnWith(column, _) =>
+			columns.push(match nullable {
+				true => column.nullable(),
+				false => *column
+			}),
+		NestedValueDef::Value(inner) => collect_column_defs(inner, nullable, columns),
+		NestedValueDef::Values(values) => for (_name, inner) in values.iter() {
+			collect_column_defs(inner, nullable, columns);
+		}
+	}
+}
+
+/// Names of the columns of each [`Ref`](crate::Ref)erence to the table `referenced`, used by [`Database::children_of`](crate::Database::children_of)
+///
+/// This includes references nested inside of other values.
+pub fn referencing_columns<'c>(def: &TableDef, all_columns: &'c [&'c str], referenced: &str)
+	-> Vec<&'c [&'c str]>
+{
+	let mut found = Vec::new();
+	let mut offset = 0;
+	for (_name, value) in def.values {
+		collect_references(value, referenced, all_columns, &mut offset, &mut found);
+	}
+	found
+}
+
+fn collect_references<'c>(
+	def: &ValueDef,
+	referenced: &str,
+	all_columns: &'c [&'c str],
+	offset: &mut usize,
+	found: &mut Vec<&'c [&'c str]>)
+{
+	if def.reference.is_some_and(|fk| fk.table_name == referenced) {
+		let count = def.inner.count_columns();
+		found.push(&all_columns[*offset..*offset + count]);
+		*offset += count;
+		return;
+	}
+	match &def.inner {
+		NestedValueDef::Column(_) | NestedValueDef::ColumnWith(..) => *offset += 1,
+		NestedValueDef::Value(inner) =>
+			collect_references(inner, referenced, all_columns, offset, found),
+		NestedValueDef::Values(values) => for (_name, inner) in values.iter() {
+			collect_references(inner, referenced, all_columns, offset, found);
+		}
+	}
+}
+
 /// Generates the [`HasKey::UPSERT`] statement at compile-time
 pub const fn upsert<const N: usize>(
 	name: &str,
@@ -476,7 +935,7 @@ impl<const C: usize, const L: usize> Names<C, L> {
 		-> Self
 	{
 		match def {
-			NestedValueDef::Column(_def) => {
+			NestedValueDef::Column(_) | NestedValueDef::ColumnWith(..) => {
 				let start = self.bytes.len();
 				self.bytes = chain.join(self.bytes, "_");
 				let end = self.bytes.len();
@@ -545,3 +1004,149 @@ macro_rules! column_names {
 #[doc(inline)]
 pub use column_names;
 
+/// Implement [`Table`], [`Entry`], [`HasKey`], [`Bind`] & [`Fetch`] for a struct with a composite primary key
+///
+/// This is what `#[derive(Table)]` generates, but every value is given with its [`ValueDef`], so that e.g. [`ValueDef::on_delete`] can be used on it.
+/// The key fields come first, in the order of the struct's fields.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! table_impls {
+	(
+		$name:ident {
+			key: [ $( $key:ident : $key_ty:ty = $key_def:expr ),+ $(,)? ],
+			other: [ $( $other:ident : $other_ty:ty = $other_def:expr ),* $(,)? ]
+		}
+	) => {
+		const _: () = {
+			use $crate::table::{
+				TableDef,
+				Values
+			};
+			use $crate::util::construe;
+
+			const KEY_VALUES: Values = &[
+				$( (::std::stringify!($key), $key_def) ),+
+			];
+			const OTHER_VALUES: Values = &[
+				$( (::std::stringify!($other), $other_def) ),*
+			];
+			const VALUES: Values = &[
+				$( (::std::stringify!($key), $key_def), )+
+				$( (::std::stringify!($other), $other_def) ),*
+			];
+
+			impl $crate::Table for $name {
+				const NAME: &'static str = construe!(
+					&str => $crate::table::lowercase(::std::stringify!($name))
+				);
+				const DEFINITION: TableDef = TableDef {
+					name: <$name as $crate::Table>::NAME,
+					primary_key: <$name as $crate::Table>::KEY_COLUMNS,
+					values: VALUES,
+					key_values: KEY_VALUES,
+					other_values: OTHER_VALUES,
+					constraints: &[]
+				};
+				const CREATE_TABLE: &'static str = construe!(
+					&str => TableDef::define(&<$name as $crate::Table>::DEFINITION)
+				);
+				$crate::table::column_names!(ALL_COLUMNS => VALUES);
+				$crate::table::column_names!(KEY_COLUMNS => KEY_VALUES);
+				$crate::table::column_names!(OTHER_COLUMNS => OTHER_VALUES);
+				type References = $crate::meta::Filtered<
+					$crate::table_impls!(@references $($key_ty),+ $(, $other_ty)*)
+				>;
+			}
+
+			impl $crate::Entry for $name {
+				const GET_ALL: &'static str = construe!(
+					&str => $crate::table::get_all(<$name as $crate::Table>::NAME)
+				);
+				const INSERT: &'static str = construe!(
+					&str => $crate::table::insert(
+						<$name as $crate::Table>::NAME,
+						<$name as $crate::Table>::ALL_COLUMNS.len()
+					)
+				);
+			}
+
+			impl $crate::HasKey for $name {
+				const GET_BY_KEY: &'static str = construe!(
+					&str => $crate::table::get_by_key(
+						<$name as $crate::Table>::NAME,
+						<$name as $crate::Table>::KEY_COLUMNS
+					)
+				);
+				const UPSERT: &'static str = construe!(
+					&str => $crate::table::upsert(
+						<$name as $crate::Table>::NAME,
+						<$name as $crate::Table>::KEY_COLUMNS,
+						<$name as $crate::Table>::OTHER_COLUMNS
+					)
+				);
+				const UPDATE: &'static str = construe!(
+					&str => $crate::table::update_for(
+						&<$name as $crate::Table>::DEFINITION,
+						<$name as $crate::Table>::ALL_COLUMNS
+					)
+				);
+				const DELETE: &'static str = construe!(
+					&str => $crate::table::delete(
+						<$name as $crate::Table>::NAME,
+						<$name as $crate::Table>::KEY_COLUMNS
+					)
+				);
+				const KEY_VALUE: $crate::value::NestedValueDef =
+					$crate::value::NestedValueDef::Values(KEY_VALUES);
+				type Marker = $crate::meta::tuple::marker::Many;
+				type Key = ( $($key_ty),+ );
+				fn get_key(&self) -> <Self::Key as $crate::meta::tuple::Tuple<Self::Marker>>::Ref<'_> {
+					( $(&self.$key),+ )
+				}
+				fn get_key_mut(&mut self) -> <Self::Key as $crate::meta::tuple::Tuple<Self::Marker>>::Mut<'_> {
+					( $(&mut self.$key),+ )
+				}
+			}
+
+			impl $crate::Bind for $name {
+				const COLUMNS: usize = 0
+					$( + <$key_ty as $crate::Bind>::COLUMNS )+
+					$( + <$other_ty as $crate::Bind>::COLUMNS )*;
+				fn bind(&self, binder: &mut $crate::types::Binder<'_, '_>)
+					-> $crate::util::SqlResult<()>
+				{
+					$( binder.bind(&self.$key)?; )+
+					$( binder.bind(&self.$other)?; )*
+					Ok(())
+				}
+			}
+
+			impl $crate::Fetch for $name {
+				fn fetch(fetcher: &mut $crate::types::Fetcher<'_>)
+					-> $crate::util::SqlResult<Self>
+				{
+					Ok(Self {
+						$( $key: fetcher.fetch()?, )+
+						$( $other: fetcher.fetch()? ),*
+					})
+				}
+				fn try_fetch(fetcher: &mut $crate::types::Fetcher<'_>)
+					-> $crate::util::SqlResult<::std::option::Option<Self>>
+				{
+					<Self as $crate::Fetch>::fetch(fetcher).map(::std::option::Option::Some)
+				}
+			}
+		};
+	};
+	// nested tuple of the values' references, for `Filtered`
+	(@references $last:ty) => {
+		(<$last as $crate::Value>::References, )
+	};
+	(@references $first:ty, $($rest:ty),+) => {
+		(<$first as $crate::Value>::References, $crate::table_impls!(@references $($rest),+))
+	};
+}
+
+#[doc(hidden)]
+pub use table_impls;
+
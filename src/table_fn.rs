@@ -0,0 +1,185 @@
+//! Typed wrappers for table-valued functions, i.e. `generate_series` and the `pragma_*` functions
+//!
+//! SQLite exposes most [pragmas](https://sqlite.org/pragma.html#pragfunc) that return rows as table-valued functions, like `pragma_table_info('table')`.
+//! [`Database::pragma_rows`] queries any of them, fetching each row as a `T`.
+//! For the ones describing a [`Table`], [`Database::table_info`], [`Database::index_list`] & [`Database::foreign_key_list`] fetch typed rows.
+//!
+//! With the `series` feature, every connection also gets the [`generate_series`](https://sqlite.org/series.html) table-valued function, which [`Database::series`] wraps.
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Shelf, Book);
+//!
+//! #[derive(Table)]
+//! struct Shelf {
+//! 	#[key]
+//! 	id: Id
+//! }
+//! #[derive(Table)]
+//! struct Book {
+//! 	#[key]
+//! 	id: Id,
+//! 	shelf: Ref<Shelf>,
+//! 	title: Option<String>
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! let columns = db.table_info::<Book>()?;
+//! let names: Vec<&str> = columns.iter().map(|column| column.name.as_str()).collect();
+//! assert_eq!(names, ["id", "shelf", "title"]);
+//! assert!(!columns[2].not_null);
+//!
+//! let keys = db.foreign_key_list::<Book>()?;
+//! assert_eq!(keys[0].table, "shelf");
+//!
+//! let journal: Vec<String> = db.pragma_rows("journal_mode", None)?;
+//! assert_eq!(journal, ["memory"]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Database,
+	Fetch,
+	Schema,
+	Table
+};
+use crate::types::Fetcher;
+
+/// Row of `pragma_table_info`, describing a column
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnInfo {
+	/// Index of the column
+	pub cid: i64,
+	pub name: String,
+	/// Type the column was declared with, e.g. `INTEGER`
+	pub declared_type: String,
+	pub not_null: bool,
+	/// SQL of the default value
+	pub default: Option<String>,
+	/// Index of the column within the primary key, starting at `1`, or `0` if it's not part of it
+	pub primary_key: u32
+}
+
+/// Row of `pragma_index_list`, describing an index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexListEntry {
+	pub seq: i64,
+	pub name: String,
+	pub unique: bool,
+	/// `c` for indexes created with `CREATE INDEX`, `u` for `UNIQUE` constraints & `pk` for the primary key
+	pub origin: String,
+	pub partial: bool
+}
+
+/// Row of `pragma_foreign_key_list`, describing (a column of) a foreign key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForeignKeyInfo {
+	/// Index of the foreign key, the same for all of its columns
+	pub id: i64,
+	/// Index of the column within the foreign key
+	pub seq: i64,
+	/// Name of the referenced table
+	pub table: String,
+	/// Referencing column
+	pub from: String,
+	/// Referenced column, `None` if it implicitly references the primary key
+	pub to: Option<String>,
+	pub on_update: String,
+	pub on_delete: String
+}
+
+impl<S: Schema> Database<S> {
+	/// Fetch the rows of the pragma's table-valued function `pragma_{name}`, with an optional argument
+	///
+	/// Returns [`Error::InvalidParameterName`] if the name isn't a valid pragma name.
+	pub fn pragma_rows<T: Fetch>(&self, name: &str, argument: Option<&str>) -> SqlResult<Vec<T>> {
+		if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+			return Err(Error::InvalidParameterName(name.to_string()));
+		}
+		match argument {
+			Some(argument) => self.query_all_with(&format!("SELECT * FROM pragma_{name}(?)"), &argument),
+			None => self.query_all(&format!("SELECT * FROM pragma_{name}"))
+		}
+	}
+	/// Columns of the table, in the order they were defined in
+	pub fn table_info<T: Table>(&self) -> SqlResult<Vec<ColumnInfo>> {
+		self.pragma_rows("table_info", Some(T::NAME))
+	}
+	/// Indexes of the table
+	pub fn index_list<T: Table>(&self) -> SqlResult<Vec<IndexListEntry>> {
+		self.query_all_with(
+			"SELECT seq, name, \"unique\", origin, partial FROM pragma_index_list(?)",
+			&T::NAME
+		)
+	}
+	/// Foreign keys of the table, with a row for each of their columns
+	pub fn foreign_key_list<T: Table>(&self) -> SqlResult<Vec<ForeignKeyInfo>> {
+		self.query_all_with(
+			"SELECT id, seq, \"table\", \"from\", \"to\", on_update, on_delete \
+			FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+			&T::NAME
+		)
+	}
+	/// Integers from `start` to `stop` (inclusive), `step` apart
+	///
+	/// This is only available with the `series` feature.
+	#[cfg(feature = "series")]
+	pub fn series(&self, start: i64, stop: i64, step: i64) -> SqlResult<Vec<i64>> {
+		self.query_all_with("SELECT value FROM generate_series(?, ?, ?)", &(start, stop, step))
+	}
+}
+
+impl Fetch for ColumnInfo {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		Ok(Self {
+			cid: fetcher.fetch()?,
+			name: fetcher.fetch()?,
+			declared_type: fetcher.fetch()?,
+			not_null: fetcher.fetch()?,
+			default: fetcher.fetch()?,
+			primary_key: fetcher.fetch()?
+		})
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		Self::fetch(fetcher).map(Some)
+	}
+}
+
+impl Fetch for IndexListEntry {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		Ok(Self {
+			seq: fetcher.fetch()?,
+			name: fetcher.fetch()?,
+			unique: fetcher.fetch()?,
+			origin: fetcher.fetch()?,
+			partial: fetcher.fetch()?
+		})
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		Self::fetch(fetcher).map(Some)
+	}
+}
+
+impl Fetch for ForeignKeyInfo {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		Ok(Self {
+			id: fetcher.fetch()?,
+			seq: fetcher.fetch()?,
+			table: fetcher.fetch()?,
+			from: fetcher.fetch()?,
+			to: fetcher.fetch()?,
+			on_update: fetcher.fetch()?,
+			on_delete: fetcher.fetch()?
+		})
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		Self::fetch(fetcher).map(Some)
+	}
+}
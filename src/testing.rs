@@ -0,0 +1,258 @@
+//! Injecting latency and errors, to test how an application handles them
+//!
+//! This module is only available with the `testing` feature, which is meant to be enabled for tests only.
+//!
+//! A [`FaultyDb`] wraps a [`Database`] and offers the same common operations, but before running one it checks the [`Fault`]s that were injected into it.
+//! A fault can delay the operation, or make it fail with `SQLITE_BUSY` or `SQLITE_IOERR` (without running it), and can be limited to certain operations, a certain table and a number of times.
+//! Faults apply in the order they were injected, so error-handling and retry paths can be tested deterministically.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//! use liter::testing::{Fault, FaultyDb};
+//! use rusqlite::ErrorCode;
+//!
+//! #[database]
+//! struct Db (Job);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Job {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//!
+//! let db = FaultyDb::new(Db::create_in_memory()?);
+//! db.inject(Fault::busy().on("insert").table::<Job>().times(2));
+//!
+//! // retry until the database isn't busy anymore
+//! let job = Job { id: Id::new(1), name: "backup".to_string() };
+//! let mut attempts = 0;
+//! loop {
+//! 	attempts += 1;
+//! 	match db.insert(&job) {
+//! 		Err(e) if e.sqlite_error_code() == Some(ErrorCode::DatabaseBusy) => continue,
+//! 		result => break result?
+//! 	};
+//! }
+//! assert_eq!(attempts, 3);
+//! assert_eq!(db.get_all::<Job>()?, [job]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::cell::RefCell;
+use std::thread;
+use std::time::Duration;
+
+use rusqlite::{
+	ffi,
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Bind,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	RowId,
+	Schema,
+	Table
+};
+use crate::meta::tuple::marker;
+
+/// [`Database`] that injects [`Fault`]s into its operations, see the [module documentation](self)
+#[derive(Debug)]
+pub struct FaultyDb<S: Schema> {
+	db: Database<S>,
+	faults: RefCell<Vec<Fault>>
+}
+
+/// Something that goes wrong when running an operation on a [`FaultyDb`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fault {
+	pub kind: FaultKind,
+	/// Names of the operations (i.e. the methods of [`FaultyDb`], like `"insert"`) the fault applies to, all if empty
+	pub operations: Vec<&'static str>,
+	/// Name of the table the fault applies to, all operations (including those without a table, like `execute`) if `None`
+	pub table: Option<&'static str>,
+	/// How many more times the fault applies, indefinitely if `None`
+	pub remaining: Option<usize>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultKind {
+	/// Sleep before running the operation
+	Delay(Duration),
+	/// Fail with `SQLITE_BUSY`, as if another connection held the lock
+	Busy,
+	/// Fail with `SQLITE_IOERR`
+	IoError
+}
+
+impl Fault {
+	pub fn new(kind: FaultKind) -> Self {
+		Self {
+			kind,
+			operations: Vec::new(),
+			table: None,
+			remaining: None
+		}
+	}
+	pub fn delay(duration: Duration) -> Self {
+		Self::new(FaultKind::Delay(duration))
+	}
+	pub fn busy() -> Self {
+		Self::new(FaultKind::Busy)
+	}
+	pub fn io_error() -> Self {
+		Self::new(FaultKind::IoError)
+	}
+	/// Only apply to the operation, which can be called multiple times to add more
+	pub fn on(mut self, operation: &'static str) -> Self {
+		self.operations.push(operation);
+		self
+	}
+	/// Only apply to operations on the table
+	pub fn table<T: Table>(mut self) -> Self {
+		self.table = Some(T::NAME);
+		self
+	}
+	/// Only apply the given number of times
+	pub fn times(mut self, times: usize) -> Self {
+		self.remaining = Some(times);
+		self
+	}
+
+	fn applies_to(&self, operation: &str, table: Option<&str>) -> bool {
+		self.remaining != Some(0)
+			&& (self.operations.is_empty() || self.operations.contains(&operation))
+			&& (self.table.is_none() || self.table == table)
+	}
+}
+
+impl<S: Schema> FaultyDb<S> {
+	pub fn new(db: Database<S>) -> Self {
+		Self { db, faults: RefCell::new(Vec::new()) }
+	}
+	/// Add a fault, which applies after the ones added before it
+	pub fn inject(&self, fault: Fault) {
+		self.faults.borrow_mut().push(fault);
+	}
+	/// Remove all faults
+	pub fn clear(&self) {
+		self.faults.borrow_mut().clear();
+	}
+	/// The wrapped database, to run operations without faults
+	pub fn database(&self) -> &Database<S> {
+		&self.db
+	}
+	pub fn into_inner(self) -> Database<S> {
+		self.db
+	}
+
+	/// Apply the faults for the operation, failing if one of them is an error
+	fn fault(&self, operation: &'static str, table: Option<&'static str>) -> SqlResult<()> {
+		let mut delays = Vec::new();
+		let mut error = None;
+		for fault in self.faults.borrow_mut().iter_mut() {
+			if !fault.applies_to(operation, table) {
+				continue;
+			}
+			if let Some(remaining) = &mut fault.remaining {
+				*remaining -= 1;
+			}
+			match fault.kind {
+				FaultKind::Delay(duration) => delays.push(duration),
+				FaultKind::Busy => error = Some(ffi::SQLITE_BUSY),
+				FaultKind::IoError => error = Some(ffi::SQLITE_IOERR)
+			}
+			if error.is_some() {
+				break;
+			}
+		}
+		for duration in delays {
+			thread::sleep(duration);
+		}
+		match error {
+			Some(code) => Err(Error::SqliteFailure(
+				ffi::Error::new(code),
+				Some(format!("injected fault in {operation}"))
+			)),
+			None => Ok(())
+		}
+	}
+
+	pub fn get<T>(&self, key: T::Key) -> SqlResult<Option<T>>
+		where T: Table + Entry + HasKey
+	{
+		self.fault("get", Some(T::NAME))?;
+		self.db.get(key)
+	}
+	pub fn get_all<T>(&self) -> SqlResult<Vec<T>>
+		where T: Table + Entry
+	{
+		self.fault("get_all", Some(T::NAME))?;
+		self.db.get_all()
+	}
+	#[track_caller]
+	pub fn create<T>(&self, entry: &mut T) -> SqlResult<()>
+		where T: Table + Entry + HasKey<Marker = marker::One>, T::Key: RowId
+	{
+		self.fault("create", Some(T::NAME))?;
+		self.db.create(entry)
+	}
+	#[track_caller]
+	pub fn insert<T>(&self, entry: &T) -> SqlResult<usize>
+		where T: Table + Entry
+	{
+		self.fault("insert", Some(T::NAME))?;
+		self.db.insert(entry)
+	}
+	#[track_caller]
+	pub fn upsert<T>(&self, entry: &T) -> SqlResult<usize>
+		where T: Table + Entry + HasKey
+	{
+		self.fault("upsert", Some(T::NAME))?;
+		self.db.upsert(entry)
+	}
+	#[track_caller]
+	pub fn update<T>(&self, entry: &T) -> SqlResult<usize>
+		where T: Table + Entry + HasKey
+	{
+		self.fault("update", Some(T::NAME))?;
+		self.db.update(entry)
+	}
+	#[track_caller]
+	pub fn delete<T>(&self, key: &T::Key) -> SqlResult<bool>
+		where T: Table + Entry + HasKey
+	{
+		self.fault("delete", Some(T::NAME))?;
+		self.db.delete::<T>(key)
+	}
+	#[track_caller]
+	pub fn execute<P: Bind>(&self, sql: &str, params: &P) -> SqlResult<usize> {
+		self.fault("execute", None)?;
+		self.db.execute(sql, params)
+	}
+	pub fn query_one<T: Fetch>(&self, sql: &str) -> SqlResult<T> {
+		self.fault("query_one", None)?;
+		self.db.query_one(sql)
+	}
+	pub fn query_all<T: Fetch>(&self, sql: &str) -> SqlResult<Vec<T>> {
+		self.fault("query_all", None)?;
+		self.db.query_all(sql)
+	}
+	pub fn query_one_with<T, P>(&self, sql: &str, params: &P) -> SqlResult<T>
+		where T: Fetch, P: Bind
+	{
+		self.fault("query_one_with", None)?;
+		self.db.query_one_with(sql, params)
+	}
+	pub fn query_all_with<T, P>(&self, sql: &str, params: &P) -> SqlResult<Vec<T>>
+		where T: Fetch, P: Bind
+	{
+		self.fault("query_all_with", None)?;
+		self.db.query_all_with(sql, params)
+	}
+}
@@ -0,0 +1,183 @@
+//! Typed [`Transaction`]s and nested [`Savepoint`]s on a [`Database`]
+//!
+//! Both dereference to the [`Database`] they were started on, so all the typed methods (`get`, `insert`, `update`, …) can be used on them directly.
+//! Neither can be held at the same time as the [`Database`] is used directly, since they borrow it mutably.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//!
+//! #[database]
+//! struct Db (Item);
+//!
+//! #[derive(Table)]
+//! struct Item {
+//!     #[key]
+//!     id: Id,
+//!     data: u64
+//! }
+//!
+//! let mut db = Db::create_in_memory()?;
+//!
+//! let tx = db.transaction()?;
+//! tx.insert(&Item { id: Id::from_i64(1), data: 1 })?;
+//! tx.commit()?;
+//!
+//! assert_eq!(db.get_all::<Item>()?.len(), 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::ops::Deref;
+use std::panic::Location;
+use std::time::Instant;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema
+};
+use crate::diagnostics::{
+	is_busy,
+	WriteRecord
+};
+
+/// Name used for all [`Savepoint`]s
+///
+/// SQLite allows reusing the same name: `RELEASE` and `ROLLBACK TO` always refer to the most recent savepoint of that name, which is exactly the innermost one.
+const SAVEPOINT_NAME: &str = "liter_savepoint";
+
+/// Transaction on a [`Database`] that is rolled back unless it is committed
+///
+/// Created by [`Database::transaction`].
+/// Dropping it without calling [`commit`](Self::commit) rolls back all changes made through it.
+#[derive(Debug)]
+pub struct Transaction<'db, S: Schema> {
+	db: &'db mut Database<S>,
+	finished: bool,
+	/// For [`Database::lock_diagnostics`]
+	caller: &'static Location<'static>,
+	started: Instant
+}
+
+/// Savepoint inside a [`Transaction`] (or another [`Savepoint`])
+///
+/// Rolling back a savepoint only undoes the changes made since it was created, the surrounding [`Transaction`] stays active.
+/// Dropping it without calling [`commit`](Self::commit) rolls it back.
+#[derive(Debug)]
+pub struct Savepoint<'tx, S: Schema> {
+	db: &'tx mut Database<S>,
+	finished: bool
+}
+
+impl<S: Schema> Database<S> {
+	/// Begin a (deferred) [`Transaction`]
+	#[track_caller]
+	pub fn transaction(&mut self) -> SqlResult<Transaction<'_, S>> {
+		let caller = Location::caller();
+		let started = Instant::now();
+		self.connection.execute_batch("BEGIN DEFERRED")?;
+		Ok(Transaction { db: self, finished: false, caller, started })
+	}
+	/// Run several statements inside a savepoint with the name, so either all or none of them take effect
+	///
+	/// Unlike a [`Savepoint`], this doesn't need a mutable borrow, and it also works inside a [`Transaction`].
+	pub(crate) fn in_savepoint<T>(&self, name: &str, f: impl FnOnce() -> SqlResult<T>)
+		-> SqlResult<T>
+	{
+		self.connection.execute_batch(&format!("SAVEPOINT {name}"))?;
+		let result = f();
+		match result {
+			Ok(_) => self.connection.execute_batch(&format!("RELEASE {name}"))?,
+			Err(_) => self.connection.execute_batch(&format!(
+				"ROLLBACK TO {name}; RELEASE {name}"
+			))?
+		}
+		result
+	}
+}
+
+impl<'db, S: Schema> Transaction<'db, S> {
+	/// Start a nested [`Savepoint`]
+	pub fn savepoint(&mut self) -> SqlResult<Savepoint<'_, S>> {
+		Savepoint::start(self.db)
+	}
+	/// Commit all changes made in this transaction
+	///
+	/// If the `COMMIT` fails, e.g. because a deferred foreign key constraint is violated, the transaction is rolled back.
+	pub fn commit(mut self) -> SqlResult<()> {
+		self.finish("COMMIT")
+	}
+	/// Roll back all changes made in this transaction
+	pub fn rollback(mut self) -> SqlResult<()> {
+		self.finish("ROLLBACK")
+	}
+	fn finish(&mut self, sql: &str) -> SqlResult<()> {
+		let result = self.db.execute_batch(sql);
+		if result.is_err() && !self.db.is_autocommit() {
+			// a failed COMMIT (e.g. SQLITE_BUSY or a deferred foreign key violation) leaves the transaction open
+			let _ = self.db.execute_batch("ROLLBACK");
+		}
+		self.finished = true;
+		self.db.writes.borrow_mut().record(WriteRecord {
+			operation: "transaction",
+			table: None,
+			caller: self.caller,
+			duration: self.started.elapsed(),
+			failed: result.is_err(),
+			busy: is_busy(&result)
+		});
+		result
+	}
+}
+
+impl<'tx, S: Schema> Savepoint<'tx, S> {
+	fn start(db: &'tx mut Database<S>) -> SqlResult<Self> {
+		db.execute_batch(&format!("SAVEPOINT {SAVEPOINT_NAME}"))?;
+		Ok(Self { db, finished: false })
+	}
+	/// Start a [`Savepoint`] nested inside this one
+	pub fn savepoint(&mut self) -> SqlResult<Savepoint<'_, S>> {
+		Savepoint::start(self.db)
+	}
+	/// Keep the changes made since this savepoint was created
+	///
+	/// They are still only persisted once the surrounding [`Transaction`] is committed.
+	pub fn commit(mut self) -> SqlResult<()> {
+		self.finished = true;
+		self.db.execute_batch(&format!("RELEASE {SAVEPOINT_NAME}"))
+	}
+	/// Undo the changes made since this savepoint was created
+	pub fn rollback(mut self) -> SqlResult<()> {
+		self.finished = true;
+		self.rollback_and_release()
+	}
+	fn rollback_and_release(&mut self) -> SqlResult<()> {
+		self.db.execute_batch(&format!(
+			"ROLLBACK TO {SAVEPOINT_NAME}; RELEASE {SAVEPOINT_NAME}"
+		))
+	}
+}
+
+impl<S: Schema> Deref for Transaction<'_, S> {
+	type Target = Database<S>;
+	fn deref(&self) -> &Self::Target {&*self.db}
+}
+impl<S: Schema> Deref for Savepoint<'_, S> {
+	type Target = Database<S>;
+	fn deref(&self) -> &Self::Target {&*self.db}
+}
+
+impl<S: Schema> Drop for Transaction<'_, S> {
+	fn drop(&mut self) {
+		if !self.finished {
+			let _ = self.finish("ROLLBACK");
+		}
+	}
+}
+impl<S: Schema> Drop for Savepoint<'_, S> {
+	fn drop(&mut self) {
+		if !self.finished {
+			let _ = self.rollback_and_release();
+		}
+	}
+}
@@ -0,0 +1,137 @@
+//! Traversing tree-shaped tables, i.e. tables that [`Ref`](crate::Ref)erence themselves
+//!
+//! For a table with a reference to its parent (e.g. `parent: Option<Ref<Self>>`), [`Database::descendants`] and [`Database::ancestors`] get all entries below or above an entry with a single `WITH RECURSIVE` query.
+//! The table has to contain exactly one reference to itself.
+//!
+//! The results are ordered by their distance from the given entry, which itself isn't included.
+//! The queries also terminate if the references form a cycle, in which case every entry is only returned once.
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Employee);
+//!
+//! #[derive(Table, Debug)]
+//! struct Employee {
+//! 	#[key]
+//! 	id: Id,
+//! 	manager: Option<Ref<Self>>,
+//! 	name: String
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for (id, manager, name) in [(1, None, "Grace"), (2, Some(1), "Alan"), (3, Some(2), "Ada")] {
+//! 	let manager = manager.map(|id| Ref(Id::new(id)));
+//! 	db.insert(&Employee { id: Id::new(id), manager, name: name.to_string() })?;
+//! }
+//!
+//! let names = |employees: Vec<Employee>| employees.into_iter().map(|e| e.name).collect::<Vec<_>>();
+//! assert_eq!(names(db.descendants::<Employee>(&Id::new(1))?), ["Alan", "Ada"]);
+//! assert_eq!(names(db.ancestors::<Employee>(&Id::new(3))?), ["Alan", "Grace"]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Fetch,
+	HasKey,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+use crate::table;
+
+impl<S: Schema> Database<S> {
+	/// Get all entries below the entry with the key, i.e. its children, their children and so on
+	///
+	/// If the table doesn't contain exactly one reference to itself, this fails with [`Error::InvalidQuery`].
+	pub fn descendants<T>(&self, key: &T::Key) -> SqlResult<Vec<T>>
+		where T: Table + Entry + HasKey + PartOf<S>
+	{
+		self.traverse_tree::<T>(key, Direction::Down)
+	}
+	/// Get all entries above the entry with the key, i.e. its parent, their parent and so on
+	///
+	/// If the table doesn't contain exactly one reference to itself, this fails with [`Error::InvalidQuery`].
+	pub fn ancestors<T>(&self, key: &T::Key) -> SqlResult<Vec<T>>
+		where T: Table + Entry + HasKey + PartOf<S>
+	{
+		self.traverse_tree::<T>(key, Direction::Up)
+	}
+
+	fn traverse_tree<T>(&self, key: &T::Key, direction: Direction) -> SqlResult<Vec<T>>
+		where T: Table + Entry + HasKey
+	{
+		let sql = tree_query::<T>(direction)?;
+		let mut stmt = self.prepare(&sql)?;
+		Binder::make(&mut stmt).bind(key)?;
+		let mut rows = stmt.raw_query();
+		let mut entries = Vec::new();
+		while let Some(row) = rows.next()? {
+			entries.push(T::from_row(row)?);
+		}
+		Ok(entries)
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+	Up,
+	Down
+}
+
+/// Assemble the `WITH RECURSIVE` query
+///
+/// The CTE `tree` holds the distance, key columns (`k0`, …) and parent columns (`p0`, …) of every entry that was reached.
+/// Since a cycle would make it reach the same entries over and over, the distance is capped at the number of rows, and only the shortest distance to each entry is kept.
+fn tree_query<T: Table>(direction: Direction) -> SqlResult<String> {
+	let references = table::referencing_columns(&T::DEFINITION, T::ALL_COLUMNS, T::NAME);
+	let [parent] = references[..] else {
+		return Err(Error::InvalidQuery);
+	};
+	let key = T::KEY_COLUMNS;
+	let tree_key = aliases("k", key.len());
+	let tree_parent = aliases("p", parent.len());
+	let join = match direction {
+		Direction::Down => format!("({}) = ({})", qualified("t", parent), qualified("tree", &tree_key)),
+		Direction::Up => format!("({}) = ({})", qualified("t", key), qualified("tree", &tree_parent))
+	};
+	Ok(format!(
+		"WITH RECURSIVE tree(depth, {tree_columns}) AS (\
+			SELECT 0, {columns} FROM \"{table}\" AS t WHERE ({key}) = ({params}) \
+			UNION ALL \
+			SELECT tree.depth + 1, {columns} FROM \"{table}\" AS t JOIN tree ON {join} \
+			WHERE tree.depth < (SELECT count(*) FROM \"{table}\")\
+		) \
+		SELECT t.* FROM \"{table}\" AS t JOIN (\
+			SELECT {found_key}, min(depth) AS depth FROM tree GROUP BY {found_key}\
+		) AS found ON ({key}) = ({found}) \
+		WHERE found.depth > 0 ORDER BY found.depth",
+		tree_columns = [tree_key.as_slice(), &tree_parent].concat().join(", "),
+		columns = [qualified("t", key), qualified("t", parent)].join(", "),
+		table = T::NAME,
+		key = qualified("t", key),
+		params = vec!["?"; key.len()].join(", "),
+		found_key = tree_key.join(", "),
+		found = qualified("found", &tree_key)
+	))
+}
+
+fn aliases(prefix: &str, count: usize) -> Vec<String> {
+	(0..count).map(|idx| format!("{prefix}{idx}")).collect()
+}
+
+fn qualified<C: AsRef<str>>(table: &str, columns: &[C]) -> String {
+	columns.iter()
+		.map(|column| format!("{table}.{}", column.as_ref()))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
@@ -0,0 +1,184 @@
+//! Triggers defined as part of the [`Schema`]
+//!
+//! A [`TriggerDef`] runs SQL statements whenever rows of a [`Table`] are inserted, updated or deleted, e.g. to maintain counters or audit logs inside of SQLite.
+//! The triggers listed in [`Schema::TRIGGERS`](crate::Schema::TRIGGERS) are created right after the tables, when the database is initialized.
+//! `#[database]` can't set them, so this takes a manual implementation of [`Schema`].
+//! A trigger that isn't listed can be created with [`Database::create_trigger`].
+//! The columns of an [`Event::UpdateOf`] are checked against the table at compile-time.
+//!
+//!```
+//! use liter::{Database, Id, Schema, Table};
+//! use liter::table::TableDef;
+//! use liter::trigger::{Event, TriggerDef};
+//! use liter::util::construe;
+//!
+//! struct Db;
+//! impl Schema for Db {
+//! 	type Tables = (Post, (Stats, ));
+//! 	const DEFINITIONS: &'static [TableDef] = &[Post::DEFINITION, Stats::DEFINITION];
+//! 	const CREATE: &'static str = construe!(
+//! 		&str => liter::schema::define(&[Post::CREATE_TABLE, Stats::CREATE_TABLE])
+//! 	);
+//! 	const TRIGGERS: &'static [TriggerDef] = &[COUNT_POSTS, NO_RENAMING];
+//! }
+//!
+//! #[derive(Table)]
+//! struct Post {
+//! 	#[key]
+//! 	id: Id,
+//! 	title: String
+//! }
+//! #[derive(Table)]
+//! struct Stats {
+//! 	#[key]
+//! 	id: u8,
+//! 	posts: u64
+//! }
+//!
+//! const COUNT_POSTS: TriggerDef = TriggerDef::after::<Post>(
+//! 	"count_posts",
+//! 	Event::Insert,
+//! 	"UPDATE stats SET posts = posts + 1 WHERE id = 0"
+//! );
+//! const NO_RENAMING: TriggerDef = TriggerDef::before::<Post>(
+//! 	"no_renaming",
+//! 	Event::UpdateOf(&["title"]),
+//! 	"SELECT raise(ABORT, 'posts can not be renamed')"
+//! ).when("old.title <> new.title");
+//!
+//! let db = Database::<Db>::create_in_memory()?;
+//! db.insert(&Stats { id: 0, posts: 0 })?;
+//! let mut post = Post { id: Id::NULL, title: "Hello".to_string() };
+//! db.create(&mut post)?;
+//! assert_eq!(db.get::<Stats>(0)?.unwrap().posts, 1);
+//!
+//! post.title = "Goodbye".to_string();
+//! assert!(db.update(&post).is_err());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema,
+	Table
+};
+
+/// When a trigger runs, relative to the change
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timing {
+	Before,
+	After
+}
+
+/// The change that fires a trigger
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+	Insert,
+	/// Updating any column
+	Update,
+	/// Updating any of these columns
+	UpdateOf(&'static [&'static str]),
+	Delete
+}
+
+/// Definition of a trigger, as listed in [`Schema::TRIGGERS`](crate::Schema::TRIGGERS)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TriggerDef {
+	pub name: &'static str,
+	/// Name of the table whose changes fire the trigger
+	pub table: &'static str,
+	pub timing: Timing,
+	pub event: Event,
+	/// Condition for running the trigger, which can refer to the changed row as `old` & `new`
+	pub when: Option<&'static str>,
+	/// Statements run by the trigger, separated by `;`
+	pub body: &'static str
+}
+
+impl TriggerDef {
+	/// Trigger running `body` before each `event` on the table `T`
+	///
+	/// Panics (at compile-time, when used in a `const`) if a column of an [`Event::UpdateOf`] isn't a column of `T`.
+	pub const fn before<T: Table>(name: &'static str, event: Event, body: &'static str) -> Self {
+		Self::new::<T>(name, Timing::Before, event, body)
+	}
+	/// Trigger running `body` after each `event` on the table `T`
+	///
+	/// Panics (at compile-time, when used in a `const`) if a column of an [`Event::UpdateOf`] isn't a column of `T`.
+	pub const fn after<T: Table>(name: &'static str, event: Event, body: &'static str) -> Self {
+		Self::new::<T>(name, Timing::After, event, body)
+	}
+	/// Only run the trigger if the condition holds
+	pub const fn when(self, condition: &'static str) -> Self {
+		Self { when: Some(condition), ..self }
+	}
+
+	const fn new<T: Table>(name: &'static str, timing: Timing, event: Event, body: &'static str)
+		-> Self
+	{
+		if let Event::UpdateOf(mut columns) = event {
+			if columns.is_empty() {
+				panic!("trigger on updating no columns");
+			}
+			while let [column, rest @ ..] = columns {
+				columns = rest;
+				if !has_column(T::ALL_COLUMNS, column) {
+					panic!("trigger on updating a column that isn't a column of the table");
+				}
+			}
+		}
+		Self { name, table: T::NAME, timing, event, when: None, body }
+	}
+}
+
+/// `CREATE TRIGGER IF NOT EXISTS ...`
+impl fmt::Display for TriggerDef {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "CREATE TRIGGER IF NOT EXISTS \"{}\" ", self.name)?;
+		f.write_str(match self.timing {
+			Timing::Before => "BEFORE ",
+			Timing::After => "AFTER "
+		})?;
+		match self.event {
+			Event::Insert => f.write_str("INSERT")?,
+			Event::Update => f.write_str("UPDATE")?,
+			Event::UpdateOf(columns) => write!(f, "UPDATE OF {}", columns.join(", "))?,
+			Event::Delete => f.write_str("DELETE")?
+		}
+		write!(f, " ON \"{}\" FOR EACH ROW", self.table)?;
+		if let Some(condition) = self.when {
+			write!(f, " WHEN {condition}")?;
+		}
+		let body = self.body.trim_end().trim_end_matches(';');
+		write!(f, " BEGIN {body}; END;")
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Create the trigger if it doesn't exist yet, which is only needed if it's not one of the [`Schema::TRIGGERS`](crate::Schema::TRIGGERS)
+	pub fn create_trigger(&self, trigger: &TriggerDef) -> SqlResult<()> {
+		self.execute_batch(&trigger.to_string())
+	}
+}
+
+const fn has_column(mut all_columns: &[&str], column: &str) -> bool {
+	while let [name, rest @ ..] = all_columns {
+		all_columns = rest;
+		if name.len() != column.len() {
+			continue;
+		}
+		let (a, b) = (name.as_bytes(), column.as_bytes());
+		let mut i = 0;
+		while i < a.len() && a[i] == b[i] {
+			i += 1;
+		}
+		if i == a.len() {
+			return true;
+		}
+	}
+	false
+}
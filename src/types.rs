@@ -3,6 +3,26 @@
 mod bind;
 mod fetch;
 
+pub mod any;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+pub mod enums;
+pub mod flags;
+pub mod int;
+pub mod money;
+pub mod parsed;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+#[cfg(feature = "time")]
+pub mod time;
+pub mod timestamp;
+#[cfg(feature = "url")]
+pub mod url;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "vector")]
+pub mod vector;
+
 pub use bind::{
 	Bind,
 	Binder,
@@ -11,6 +31,7 @@ pub use bind::{
 
 pub use fetch::{
 	Fetch,
+	FetchRows,
 	Fetcher,
 	FromSql2
 };
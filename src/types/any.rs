@@ -0,0 +1,99 @@
+//! [`AnyValue`]: a [`Column`] of any type, for genuinely dynamic data
+//!
+//! In a [`STRICT`](https://sqlite.org/stricttables.html) table, every column has to have a type, except for columns declared `ANY`, which accept values of every type and keep them exactly as they are.
+//! This is useful for things like user-defined attributes, without giving up strict typing for the rest of the table.
+//!
+//! Like every other [`Column`], an [`AnyValue`] is `NOT NULL` unless it's wrapped in an `Option`.
+//! Note that in non-strict tables (see [`Table::STRICT`](crate::Table::STRICT)), a column declared `ANY` has `NUMERIC` affinity, which converts text that looks like a number, so it's only useful in strict tables.
+//!
+//!```
+//! use liter::{database, Table};
+//! use liter::types::any::AnyValue;
+//!
+//! #[database]
+//! struct Db (Attribute);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Attribute {
+//! 	#[key]
+//! 	name: String,
+//! 	value: AnyValue
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Attribute { name: "width".to_string(), value: AnyValue::new(&640)? })?;
+//! db.insert(&Attribute { name: "title".to_string(), value: AnyValue::new("Untitled")? })?;
+//!
+//! let attributes = db.get_all::<Attribute>()?;
+//! assert_eq!(attributes[0].value.get::<i64>()?, 640);
+//! assert_eq!(attributes[1].value, AnyValue::from(rusqlite::types::Value::Text("Untitled".to_string())));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//!```
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::{
+	FromSql,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	Value,
+	ValueRef
+};
+
+use crate::Column;
+use crate::column::Affinity;
+
+/// Value of any type, stored in an `ANY` column
+///
+/// This wraps [`rusqlite`]'s dynamically typed [`Value`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnyValue(pub Value);
+
+impl AnyValue {
+	pub const NULL: Self = Self(Value::Null);
+
+	/// Convert anything that can be bound to a statement
+	pub fn new<T: ToSql + ?Sized>(value: &T) -> SqlResult<Self> {
+		match value.to_sql()? {
+			ToSqlOutput::Borrowed(value) => Ok(Self(value.into())),
+			ToSqlOutput::Owned(value) => Ok(Self(value)),
+			_ => Err(Error::ToSqlConversionFailure(
+				"value can't be stored in an ANY column".into()
+			))
+		}
+	}
+	/// Convert to anything that can be fetched from a column
+	pub fn get<T: FromSql>(&self) -> FromSqlResult<T> {
+		T::column_result(ValueRef::from(&self.0))
+	}
+}
+
+impl From<Value> for AnyValue {
+	fn from(value: Value) -> Self {
+		Self(value)
+	}
+}
+impl From<AnyValue> for Value {
+	fn from(value: AnyValue) -> Self {
+		value.0
+	}
+}
+
+impl FromSql for AnyValue {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		Ok(Self(value.into()))
+	}
+}
+impl ToSql for AnyValue {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		Ok(ToSqlOutput::Borrowed(ValueRef::from(&self.0)))
+	}
+}
+crate::types::impl_from_to_sql_2!(AnyValue);
+
+impl Column for AnyValue {
+	const AFFINITY: Affinity = Affinity::Any;
+}
@@ -1,9 +1,12 @@
 use rusqlite::{
+	Error,
 	Statement,
 	ToSql,
 	Result as SqlResult,
 };
 
+use crate::Entry;
+
 pub trait Bind {
 	const COLUMNS: usize;
 	fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()>;
@@ -38,21 +41,55 @@ pub trait ToSql2 {}
 
 pub struct Binder<'stmt, 'conn> {
 	index: usize,
+	/// Parameters after this index are skipped instead of bound
+	last: usize,
 	stmt: &'stmt mut Statement<'conn>
 }
 
 impl<'stmt, 'conn> Binder<'stmt, 'conn> {
 	pub(crate) fn make(stmt: &'stmt mut Statement<'conn>) -> Self {
-		Self {index: 0, stmt}
+		Self {index: 0, last: usize::MAX, stmt}
+	}
+	/// Make a [`Binder`] that silently skips parameters past the ones in the statement
+	///
+	/// This is for statements with numbered parameters (`?n`) that don't use the last few parameters, like those that leave out generated columns or columns with a `DEFAULT`.
+	pub(crate) fn make_partial(stmt: &'stmt mut Statement<'conn>) -> Self {
+		let last = stmt.parameter_count();
+		Self {index: 0, last, stmt}
+	}
+	/// Make a [`Binder`] for one of the statements generated for the [`Entry`]
+	///
+	/// This only skips parameters if the statements leave out [generated columns](Entry::HAS_GENERATED).
+	pub(crate) fn make_for<T: Entry>(stmt: &'stmt mut Statement<'conn>) -> Self {
+		match T::HAS_GENERATED {
+			true => Self::make_partial(stmt),
+			false => Self::make(stmt)
+		}
 	}
 	#[inline]
 	pub fn bind_parameter<T: ToSql>(&mut self, thing: &T) -> SqlResult<()> {
 		self.index += 1; // bind parameter index is 1-based
+		if self.index > self.last {
+			return Ok(());
+		}
 		self.stmt.raw_bind_parameter(self.index, thing)
 	}
 	pub fn bind<T: Bind + ?Sized>(&mut self, thing: &T) -> SqlResult<()> {
 		thing.bind(self)
 	}
+	/// Bind to the named parameter `name` (including its prefix, e.g. `:name`), regardless of its position
+	///
+	/// The value has to bind exactly one column.
+	/// Any positional parameters bound afterwards follow the named one.
+	pub fn bind_named<T: Bind + ?Sized>(&mut self, name: &str, thing: &T) -> SqlResult<()> {
+		if T::COLUMNS != 1 {
+			return Err(Error::InvalidParameterCount(T::COLUMNS, 1));
+		}
+		let index = self.stmt.parameter_index(name)?
+			.ok_or_else(|| Error::InvalidParameterName(name.to_string()))?;
+		self.index = index - 1;
+		thing.bind(self)
+	}
 	pub fn skip(&mut self, count: usize) {
 		self.index += count;
 	}
@@ -0,0 +1,83 @@
+//! [`Column`] implementations for [`chrono`](::chrono) types
+//!
+//! This module is only available with the `chrono` feature.
+//!
+//! The types are stored as ISO-8601 `TEXT`, using the formats of `rusqlite`'s own `chrono` support, which also sort correctly as text (`DateTime<Utc>` is always stored with a `+00:00` offset).
+//! A `CHECK` makes sure that the stored text at least starts out as a date or time.
+//! To store a `DateTime<Utc>` as an `INTEGER` UNIX timestamp instead, wrap it in [`UnixTime`].
+
+use ::chrono::{
+	DateTime,
+	NaiveDate,
+	NaiveDateTime,
+	NaiveTime,
+	Utc
+};
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlError,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+
+use crate::Column;
+use crate::column::Affinity;
+use crate::value::Check;
+
+const DATE_GLOB: Check = Check::Sql("GLOB '[0-9][0-9][0-9][0-9]-[0-1][0-9]-[0-3][0-9]*'");
+const TIME_GLOB: Check = Check::Sql("GLOB '[0-2][0-9]:[0-5][0-9]:[0-6][0-9]*'");
+
+impl Column for DateTime<Utc> {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[DATE_GLOB];
+}
+impl Column for NaiveDateTime {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[DATE_GLOB];
+}
+impl Column for NaiveDate {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[DATE_GLOB];
+}
+impl Column for NaiveTime {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[TIME_GLOB];
+}
+crate::types::impl_from_to_sql_2!(DateTime<Utc>);
+crate::types::impl_from_to_sql_2!(NaiveDateTime);
+crate::types::impl_from_to_sql_2!(NaiveDate);
+crate::types::impl_from_to_sql_2!(NaiveTime);
+
+/// `DateTime<Utc>` stored as an `INTEGER` UNIX timestamp (in seconds)
+///
+/// This is smaller than the ISO-8601 text, but fractional seconds are truncated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixTime(pub DateTime<Utc>);
+
+impl FromSql for UnixTime {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		let secs = i64::column_result(value)?;
+		DateTime::from_timestamp(secs, 0)
+			.map(Self)
+			.ok_or(FromSqlError::OutOfRange(secs))
+	}
+}
+impl ToSql for UnixTime {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		Ok(self.0.timestamp().into())
+	}
+}
+crate::types::impl_from_to_sql_2!(UnixTime);
+
+impl Column for UnixTime {
+	const AFFINITY: Affinity = Affinity::Integer;
+}
+
+impl From<DateTime<Utc>> for UnixTime {
+	fn from(time: DateTime<Utc>) -> Self {
+		Self(time)
+	}
+}
@@ -0,0 +1,130 @@
+//! [`Column`](crate::Column) implementations for fieldless enums stored as `INTEGER`s
+//!
+//! [`int_enum!`](crate::int_enum) defines an enum and implements [`Column`](crate::Column) for it, storing each variant as its discriminant.
+//! The column gets a `CHECK (… IN (0, 1, …))` constraint listing the discriminants, so it doesn't have to be written (and kept up to date) by hand.
+//! Fetching any other integer fails with an [`InvalidDiscriminant`] error naming the enum.
+//!
+//!```
+//! use liter::{database, int_enum, Id, Table};
+//! use liter::types::enums::InvalidDiscriminant;
+//!
+//! int_enum! {
+//! 	#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+//! 	pub enum Status {
+//! 		Draft,
+//! 		Published,
+//! 		Archived = 10
+//! 	}
+//! }
+//!
+//! #[database]
+//! struct Db (Post);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct Post {
+//! 	#[key]
+//! 	id: Id,
+//! 	status: Status
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Post { id: Id::new(1), status: Status::Archived })?;
+//! assert_eq!(db.query_one::<i64>("SELECT status FROM post")?, 10);
+//! assert!(db.execute("UPDATE post SET status = 2", &()).is_err());
+//!
+//! let error = db.query_one::<Status>("SELECT 2").unwrap_err();
+//! assert_eq!(error.to_string(), "Conversion error from type Integer at index: 0, 2 is not a discriminant of Status");
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::error::Error;
+use std::fmt;
+
+use construe::StrConstrue;
+
+use crate::types::flags::push_int;
+
+/// Define a fieldless enum and implement [`Column`](crate::Column) for it, see the [module documentation](crate::types::enums)
+///
+/// Variants may have explicit discriminants, which have to fit into an `i64`.
+#[macro_export]
+macro_rules! int_enum {
+	(
+		$(#[$attr:meta])*
+		$vis:vis enum $name:ident {
+			$( $(#[$variant_attr:meta])* $variant:ident $(= $discriminant:expr)? ),* $(,)?
+		}
+	) => {
+		$(#[$attr])*
+		$vis enum $name {
+			$( $(#[$variant_attr])* $variant $(= $discriminant)? ),*
+		}
+		impl $crate::Column for $name {
+			const AFFINITY: $crate::column::Affinity = $crate::column::Affinity::Integer;
+			const CHECKS: &'static [$crate::value::Check] = &[$crate::value::Check::Sql(
+				$crate::util::construe!(
+					&str => $crate::types::enums::in_check(&[$($name::$variant as i64),*])
+				)
+			)];
+		}
+		impl $crate::util::rusqlite::types::FromSql for $name {
+			fn column_result(value: $crate::util::rusqlite::types::ValueRef<'_>)
+				-> $crate::util::rusqlite::types::FromSqlResult<Self>
+			{
+				let value = value.as_i64()?;
+				$(
+					if value == $name::$variant as i64 {
+						return Ok($name::$variant);
+					}
+				)*
+				Err($crate::util::rusqlite::types::FromSqlError::Other(::std::boxed::Box::new(
+					$crate::types::enums::InvalidDiscriminant {
+						enum_name: ::std::stringify!($name),
+						value
+					}
+				)))
+			}
+		}
+		impl $crate::util::rusqlite::types::ToSql for $name {
+			fn to_sql(&self) -> $crate::util::SqlResult<$crate::util::rusqlite::types::ToSqlOutput<'_>> {
+				let value = match self {
+					$( $name::$variant => $name::$variant as i64 ),*
+				};
+				Ok(value.into())
+			}
+		}
+		$crate::types::impl_from_to_sql_2!($name);
+	};
+}
+
+/// Integer fetched for an enum defined with [`int_enum!`](crate::int_enum) that isn't one of its discriminants
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidDiscriminant {
+	pub enum_name: &'static str,
+	pub value: i64
+}
+
+impl fmt::Display for InvalidDiscriminant {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} is not a discriminant of {}", self.value, self.enum_name)
+	}
+}
+
+impl Error for InvalidDiscriminant {}
+
+/// `IN (a, b, …)`, which is prepended with the column name to check that it's one of the discriminants
+#[doc(hidden)]
+pub const fn in_check<const N: usize>(mut discriminants: &[i64]) -> StrConstrue<N> {
+	let mut sc = StrConstrue::new();
+	sc = sc.push_str("IN (");
+	let mut is_first = true;
+	while let [discriminant, rest @ ..] = discriminants {
+		if !is_first {
+			sc = sc.push_str(", ");
+		}
+		else {is_first = false;}
+		sc = push_int(sc, *discriminant);
+		discriminants = rest;
+	}
+	sc.push_str(")")
+}
@@ -1,5 +1,8 @@
+use std::marker::PhantomData;
+
 use rusqlite::{
 	Row,
+	Rows,
 	Result as SqlResult,
 	Error
 };
@@ -20,6 +23,27 @@ pub trait Fetch: Sized {
 }
 pub trait FromSql2 {}
 
+/// Iterator fetching a `T` from each row of a query, see [`Database::query_iter`](crate::Database::query_iter)
+pub struct FetchRows<'stmt, T> {
+	rows: Rows<'stmt>,
+	fetch: PhantomData<fn() -> T>
+}
+
+impl<'stmt, T: Fetch> FetchRows<'stmt, T> {
+	pub(crate) fn make(rows: Rows<'stmt>) -> Self {
+		Self {rows, fetch: PhantomData}
+	}
+}
+
+impl<T: Fetch> Iterator for FetchRows<'_, T> {
+	type Item = SqlResult<T>;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.rows.next()
+			.and_then(|row| row.map(T::from_row).transpose())
+			.transpose()
+	}
+}
+
 pub struct Fetcher<'row> {
 	index: usize,
 	all_nulls: bool,
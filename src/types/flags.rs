@@ -0,0 +1,108 @@
+//! [`Column`](crate::Column) implementations for [`bitflags`](https://docs.rs/bitflags) types
+//!
+//! [`bitflags_column!`](crate::bitflags_column) implements [`Column`](crate::Column) for a type generated by `bitflags!`, storing it as an `INTEGER`.
+//! The column gets a `CHECK` constraint that rejects any bits that don't belong to a defined flag, and fetching such bits fails as well.
+//!
+//! The macro only relies on the inherent methods generated by `bitflags!` (`all`, `bits`, `from_bits`), so there is no feature for it.
+//!
+//!```
+//! use bitflags::bitflags;
+//! use liter::{bitflags_column, database, Table};
+//!
+//! bitflags! {
+//! 	#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+//! 	struct Permissions: u8 {
+//! 		const READ = 0b001;
+//! 		const WRITE = 0b010;
+//! 		const EXECUTE = 0b100;
+//! 	}
+//! }
+//! bitflags_column!(Permissions);
+//!
+//! #[database]
+//! struct Db (File);
+//!
+//! #[derive(Table, Debug, PartialEq)]
+//! struct File {
+//! 	#[key]
+//! 	path: String,
+//! 	permissions: Permissions
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! let file = File {
+//! 	path: "notes.txt".to_string(),
+//! 	permissions: Permissions::READ | Permissions::WRITE
+//! };
+//! db.insert(&file)?;
+//! assert_eq!(db.get_all::<File>()?, [file]);
+//! assert!(db.execute("UPDATE file SET permissions = 8", &()).is_err());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use construe::StrConstrue;
+
+/// Implement [`Column`](crate::Column) for a type generated by `bitflags!`, see the [module documentation](crate::types::flags)
+#[macro_export]
+macro_rules! bitflags_column {
+	($t:ty) => {
+		impl $crate::Column for $t {
+			const AFFINITY: $crate::column::Affinity = $crate::column::Affinity::Integer;
+			const CHECKS: &'static [$crate::value::Check] = &[$crate::value::Check::Sql(
+				$crate::util::construe!(
+					&str => $crate::types::flags::mask_check(<$t>::all().bits() as i64)
+				)
+			)];
+		}
+		impl $crate::util::rusqlite::types::FromSql for $t {
+			fn column_result(value: $crate::util::rusqlite::types::ValueRef<'_>)
+				-> $crate::util::rusqlite::types::FromSqlResult<Self>
+			{
+				let bits = value.as_i64()?;
+				::std::convert::TryFrom::try_from(bits).ok()
+					.and_then(<$t>::from_bits)
+					.ok_or($crate::util::rusqlite::types::FromSqlError::OutOfRange(bits))
+			}
+		}
+		impl $crate::util::rusqlite::types::ToSql for $t {
+			fn to_sql(&self) -> $crate::util::SqlResult<$crate::util::rusqlite::types::ToSqlOutput<'_>> {
+				Ok((self.bits() as i64).into())
+			}
+		}
+		$crate::types::impl_from_to_sql_2!($t);
+	};
+}
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// `& ~mask = 0`, which is prepended with the column name to check that no other bits are set
+#[doc(hidden)]
+pub const fn mask_check<const N: usize>(mask: i64) -> StrConstrue<N> {
+	let mut sc = StrConstrue::new();
+	sc = sc.push_str("& ~");
+	sc = push_int(sc, mask);
+	sc.push_str(" = 0")
+}
+
+/// Write out the decimal digits of the integer
+pub(crate) const fn push_int<const N: usize>(mut sc: StrConstrue<N>, int: i64)
+	-> StrConstrue<N>
+{
+	if int < 0 {
+		sc = sc.push_str("-");
+	}
+	let mut rest = int.unsigned_abs();
+	let mut place = 1;
+	while rest / place >= 10 {
+		place *= 10;
+	}
+	loop {
+		sc = sc.push_str(DIGITS[(rest / place) as usize]);
+		rest %= place;
+		if place == 1 {
+			break;
+		}
+		place /= 10;
+	}
+	sc
+}
@@ -0,0 +1,135 @@
+//! [`Value`] implementations for non-zero & 128-bit integers
+//!
+//! The `NonZero…` integers are stored as `INTEGER`s with a `CHECK (… <> 0)` constraint.
+//!
+//! [`i128`] & [`u128`] don't fit into SQLite's 64-bit `INTEGER`s, so they are stored as 16-byte big-endian `BLOB`s instead.
+//! For [`i128`], the sign bit is flipped so that negative numbers come first.
+//! Since SQLite compares `BLOB`s byte by byte, this keeps their order, so they can be used as keys, sorted, and compared in range queries.
+
+use std::num::{
+	NonZeroI8,
+	NonZeroI16,
+	NonZeroI32,
+	NonZeroI64,
+	NonZeroU8,
+	NonZeroU16,
+	NonZeroU32,
+	NonZeroU64,
+	NonZeroUsize
+};
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::Type;
+
+use crate::{
+	Bind,
+	Binder,
+	Fetch,
+	Value
+};
+use crate::column::{
+	Affinity,
+	ColumnDef
+};
+use crate::types::Fetcher;
+use crate::value::{
+	Check,
+	NestedValueDef,
+	ValueDef
+};
+
+const fn definition(affinity: Affinity, checks: &'static [Check]) -> ValueDef {
+	ValueDef {
+		unique: false,
+		nullable: false,
+		inner: NestedValueDef::Column(ColumnDef {
+			affinity,
+			nullable: false,
+			checks
+		}),
+		reference: None,
+		checks: &[]
+	}
+}
+
+fn zero() -> Error {
+	Error::FromSqlConversionFailure(
+		0,
+		Type::Integer,
+		"0 is not a valid non-zero integer".into()
+	)
+}
+
+macro_rules! non_zero {
+	($t:ty, $int:ty) => {
+		impl Value for $t {
+			const DEFINITION: ValueDef = definition(
+				Affinity::Integer,
+				&[Check::Sql("<> 0")]
+			);
+			type References = ();
+		}
+		impl Bind for $t {
+			const COLUMNS: usize = 1;
+			fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+				binder.bind_parameter(&self.get())
+			}
+		}
+		impl Fetch for $t {
+			fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+				<$t>::new(fetcher.fetch_column::<$int>()?).ok_or_else(zero)
+			}
+			fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+				fetcher.try_fetch_column::<$int>()?
+					.map(|int| <$t>::new(int).ok_or_else(zero))
+					.transpose()
+			}
+		}
+	};
+}
+
+non_zero!(NonZeroI8, i8);
+non_zero!(NonZeroI16, i16);
+non_zero!(NonZeroI32, i32);
+non_zero!(NonZeroI64, i64);
+
+non_zero!(NonZeroU8, u8);
+non_zero!(NonZeroU16, u16);
+non_zero!(NonZeroU32, u32);
+non_zero!(NonZeroU64, u64);
+non_zero!(NonZeroUsize, usize);
+
+macro_rules! big_int {
+	($t:ty, $encode:expr, $decode:expr) => {
+		impl Value for $t {
+			const DEFINITION: ValueDef = definition(Affinity::Blob, &[]);
+			type References = ();
+		}
+		impl Bind for $t {
+			const COLUMNS: usize = 1;
+			fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+				let bytes: [u8; 16] = $encode(*self);
+				binder.bind_parameter(&bytes)
+			}
+		}
+		impl Fetch for $t {
+			fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+				fetcher.fetch_column::<[u8; 16]>().map($decode)
+			}
+			fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+				fetcher.try_fetch_column::<[u8; 16]>()
+					.map(|bytes| bytes.map($decode))
+			}
+		}
+	};
+}
+
+big_int!(
+	i128,
+	|int: i128| (int ^ i128::MIN).to_be_bytes(),
+	|bytes: [u8; 16]| i128::from_be_bytes(bytes) ^ i128::MIN
+);
+big_int!(u128, u128::to_be_bytes, u128::from_be_bytes);
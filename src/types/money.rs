@@ -0,0 +1,197 @@
+//! Fixed-point amounts of money that don't round-trip through `f64`
+//!
+//! [`Money`] stores an amount as an `INTEGER` number of minor units (e.g. cents), so sums computed by SQLite are exact as well.
+//! With the `rust_decimal` feature, [`DecimalText`] stores an arbitrary [`Decimal`](rust_decimal::Decimal) as `TEXT` instead.
+
+use std::fmt;
+use std::ops::{
+	Add,
+	Neg,
+	Sub
+};
+
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+
+use crate::Column;
+use crate::column::Affinity;
+
+/// Amount with `SCALE` decimal places, stored as an `INTEGER` number of minor units
+///
+/// E.g. `Money::<2>(1234)` is `12.34`.
+/// `SCALE` can be at most 18, since `10^SCALE` has to fit into an `i64`: larger ones fail to compile.
+///
+/// Adding, subtracting & negating panic if the result doesn't fit into an `i64` (in release builds as well), since a wrapped amount of money is never what you want.
+/// Use [`checked_add`](Self::checked_add), [`checked_sub`](Self::checked_sub) & [`checked_neg`](Self::checked_neg) to handle that case instead.
+///
+///```
+/// use liter::types::money::Money;
+///
+/// let price = Money::<2>::from_minor(1999);
+/// assert_eq!((price + price).to_string(), "39.98");
+/// assert_eq!((-price).to_string(), "-19.99");
+///```
+///
+/// There are no more than 18 decimal places:
+///
+///```compile_fail
+/// use liter::types::money::Money;
+///
+/// let factor = Money::<19>::FACTOR;
+///```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money<const SCALE: u32>(pub i64);
+
+impl<const SCALE: u32> Money<SCALE> {
+	pub const ZERO: Self = Self(0);
+	/// Number of minor units in a major unit, i.e. `10^SCALE`
+	pub const FACTOR: i64 = {
+		assert!(SCALE <= 18, "Money can have at most 18 decimal places");
+		10_i64.pow(SCALE)
+	};
+
+	pub const fn from_minor(minor: i64) -> Self {
+		let _ = Self::FACTOR;
+		Self(minor)
+	}
+	/// Number of minor units, e.g. cents
+	pub const fn minor(self) -> i64 {
+		self.0
+	}
+	/// Whole major units (e.g. dollars), rounded towards zero
+	pub const fn major(self) -> i64 {
+		self.0 / Self::FACTOR
+	}
+	pub const fn checked_add(self, other: Self) -> Option<Self> {
+		match self.0.checked_add(other.0) {
+			Some(sum) => Some(Self(sum)),
+			None => None
+		}
+	}
+	pub const fn checked_sub(self, other: Self) -> Option<Self> {
+		match self.0.checked_sub(other.0) {
+			Some(difference) => Some(Self(difference)),
+			None => None
+		}
+	}
+	pub const fn checked_neg(self) -> Option<Self> {
+		match self.0.checked_neg() {
+			Some(negated) => Some(Self(negated)),
+			None => None
+		}
+	}
+}
+
+impl<const SCALE: u32> Add for Money<SCALE> {
+	type Output = Self;
+	fn add(self, other: Self) -> Self {
+		self.checked_add(other).expect("overflow when adding amounts of money")
+	}
+}
+impl<const SCALE: u32> Sub for Money<SCALE> {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self {
+		self.checked_sub(other).expect("overflow when subtracting amounts of money")
+	}
+}
+impl<const SCALE: u32> Neg for Money<SCALE> {
+	type Output = Self;
+	fn neg(self) -> Self {
+		self.checked_neg().expect("overflow when negating an amount of money")
+	}
+}
+
+impl<const SCALE: u32> fmt::Display for Money<SCALE> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let sign = if self.0 < 0 {"-"} else {""};
+		let minor = self.0.unsigned_abs();
+		let factor = Self::FACTOR.unsigned_abs();
+		match SCALE {
+			0 => write!(f, "{sign}{minor}"),
+			_ => write!(
+				f,
+				"{sign}{}.{:0width$}",
+				minor / factor,
+				minor % factor,
+				width = SCALE as usize
+			)
+		}
+	}
+}
+
+impl<const SCALE: u32> FromSql for Money<SCALE> {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		i64::column_result(value).map(Self)
+	}
+}
+impl<const SCALE: u32> ToSql for Money<SCALE> {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		self.0.to_sql()
+	}
+}
+impl<const SCALE: u32> crate::types::FromSql2 for Money<SCALE> {}
+impl<const SCALE: u32> crate::types::ToSql2 for Money<SCALE> {}
+
+impl<const SCALE: u32> Column for Money<SCALE> {
+	const AFFINITY: Affinity = {
+		// reject a SCALE that is too large for every table using it
+		let _ = Self::FACTOR;
+		Affinity::Integer
+	};
+}
+
+#[cfg(feature = "rust_decimal")]
+pub use decimal::DecimalText;
+
+#[cfg(feature = "rust_decimal")]
+mod decimal {
+	use std::str::FromStr;
+
+	use rust_decimal::Decimal;
+
+	use super::*;
+	use crate::value::Check;
+
+	/// [`Decimal`] stored as `TEXT`, e.g. `'12.340'`
+	///
+	/// `Decimal` can't implement [`Column`] itself, because neither it nor `rusqlite`'s traits are from this crate.
+	/// The `CHECK`s only allow text that looks like a decimal number: digits with at most one decimal point, optionally preceded by a minus sign.
+	#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub struct DecimalText(pub Decimal);
+
+	impl FromSql for DecimalText {
+		fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+			Decimal::from_str(value.as_str()?)
+				.map(Self)
+				.map_err(|err| rusqlite::types::FromSqlError::Other(err.into()))
+		}
+	}
+	impl ToSql for DecimalText {
+		fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+			Ok(self.0.to_string().into())
+		}
+	}
+	crate::types::impl_from_to_sql_2!(DecimalText);
+
+	impl Column for DecimalText {
+		const AFFINITY: Affinity = Affinity::Text;
+		const CHECKS: &'static [Check] = &[
+			Check::Sql("NOT GLOB '*[^0-9.-]*'"),
+			Check::Sql("GLOB '*[0-9]*'"),
+			Check::Sql("NOT GLOB '?*-*'"),
+			Check::Sql("NOT GLOB '*.*.*'")
+		];
+	}
+
+	impl From<Decimal> for DecimalText {
+		fn from(decimal: Decimal) -> Self {
+			Self(decimal)
+		}
+	}
+}
@@ -0,0 +1,102 @@
+//! [`Value`] implementations for standard library types stored as `TEXT`
+//!
+//! IP & socket addresses are stored as their [`Display`](std::fmt::Display) text and parsed again when fetched, so they stay readable in other tools.
+//! [`PathBuf`]s are stored as their text too, which means binding a path that isn't valid UTF-8 fails.
+//!
+//! These types can't implement [`Column`](crate::Column), since that requires [`rusqlite`]'s [`FromSql`](rusqlite::types::FromSql) & [`ToSql`](rusqlite::ToSql), so they implement [`Value`] directly.
+
+use std::net::{
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+	SocketAddr,
+	SocketAddrV4,
+	SocketAddrV6
+};
+use std::path::PathBuf;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::Type;
+
+use crate::{
+	Bind,
+	Binder,
+	Fetch,
+	Value
+};
+use crate::types::Fetcher;
+use crate::column::{
+	Affinity,
+	ColumnDef
+};
+use crate::value::{
+	NestedValueDef,
+	ValueDef
+};
+
+const TEXT: ValueDef = ValueDef {
+	unique: false,
+	nullable: false,
+	inner: NestedValueDef::Column(ColumnDef {
+		affinity: Affinity::Text,
+		nullable: false,
+		checks: &[]
+	}),
+	reference: None,
+	checks: &[]
+};
+
+macro_rules! parsed {
+	($t:ty, $to_text:expr, $from_text:expr) => {
+		impl Value for $t {
+			const DEFINITION: ValueDef = TEXT;
+			type References = ();
+		}
+		impl Bind for $t {
+			const COLUMNS: usize = 1;
+			fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+				let text: String = $to_text(self)?;
+				binder.bind_parameter(&text)
+			}
+		}
+		impl Fetch for $t {
+			fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+				$from_text(fetcher.fetch_column::<String>()?)
+			}
+			fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+				fetcher.try_fetch_column::<String>()?
+					.map($from_text)
+					.transpose()
+			}
+		}
+	};
+	($t:ty) => {
+		parsed!($t, |addr: &$t| Ok(addr.to_string()), |text: String| {
+			text.parse::<$t>().map_err(|err| Error::FromSqlConversionFailure(
+				0,
+				Type::Text,
+				Box::new(err)
+			))
+		});
+	};
+}
+
+parsed!(IpAddr);
+parsed!(Ipv4Addr);
+parsed!(Ipv6Addr);
+parsed!(SocketAddr);
+parsed!(SocketAddrV4);
+parsed!(SocketAddrV6);
+
+parsed!(
+	PathBuf,
+	|path: &PathBuf| path.to_str()
+		.map(str::to_string)
+		.ok_or_else(|| Error::ToSqlConversionFailure(
+			format!("path {} is not valid UTF-8", path.display()).into()
+		)),
+	|text: String| SqlResult::Ok(PathBuf::from(text))
+);
@@ -0,0 +1,106 @@
+//! [`Secret`] values that are zeroized on drop
+//!
+//! This module is only available with the `zeroize` feature.
+//!
+//! A [`Secret`] wraps a [`Column`] like a `String` or `Vec<u8>` holding a token or credential.
+//! Its memory is overwritten with zeroes when it's dropped and its [`Debug`](fmt::Debug) impl doesn't show the value, so it doesn't end up in logs.
+//!
+//! Note that this only applies to the value on the Rust side: SQLite keeps its own copies in its page cache and the database file, which you have to protect (e.g. encrypt) separately.
+//!
+//!```
+//! use liter::{database, Table};
+//! use liter::types::secret::Secret;
+//!
+//! #[database]
+//! struct Db (ApiToken);
+//!
+//! #[derive(Table, Debug)]
+//! struct ApiToken {
+//! 	#[key]
+//! 	service: String,
+//! 	token: Secret<String>
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&ApiToken {
+//! 	service: "mail".to_string(),
+//! 	token: Secret::new("hunter2".to_string())
+//! })?;
+//! let token = db.get::<ApiToken>("mail".to_string())?.unwrap();
+//! assert_eq!(token.token.expose_secret(), "hunter2");
+//! assert!(!format!("{token:?}").contains("hunter2"));
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+use zeroize::Zeroize;
+
+use crate::Column;
+use crate::column::Affinity;
+use crate::types::{
+	FromSql2,
+	ToSql2
+};
+use crate::value::Check;
+
+/// Value that is zeroized on drop and redacted in [`Debug`](fmt::Debug) output
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+	pub fn new(value: T) -> Self {
+		Self(value)
+	}
+	/// Access the secret value
+	///
+	/// Careful not to copy it somewhere it isn't zeroized.
+	pub fn expose_secret(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+	fn drop(&mut self) {
+		self.0.zeroize();
+	}
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("Secret([REDACTED])")
+	}
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+	fn from(value: T) -> Self {
+		Self(value)
+	}
+}
+
+impl<T: Zeroize + FromSql> FromSql for Secret<T> {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		T::column_result(value).map(Self)
+	}
+}
+impl<T: Zeroize + ToSql> ToSql for Secret<T> {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		self.0.to_sql()
+	}
+}
+impl<T: Zeroize + FromSql> FromSql2 for Secret<T> {}
+impl<T: Zeroize + ToSql> ToSql2 for Secret<T> {}
+
+impl<T: Zeroize + Column> Column for Secret<T> {
+	const AFFINITY: Affinity = T::AFFINITY;
+	const CHECKS: &'static [Check] = T::CHECKS;
+	const COLLATION: Option<&'static str> = T::COLLATION;
+}
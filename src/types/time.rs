@@ -0,0 +1,41 @@
+//! [`Column`] implementations for [`time`](::time) types
+//!
+//! This module is only available with the `time` feature.
+//!
+//! Like the [`chrono`](super::chrono) types, these are stored as ISO-8601 `TEXT` in the formats of `rusqlite`'s own `time` support, with a `CHECK` that the stored text starts out as a date or time.
+//! Note that an `OffsetDateTime` is stored with its offset, so values with different offsets don't sort chronologically as text: convert them to UTC first if that matters.
+
+use ::time::{
+	Date,
+	OffsetDateTime,
+	PrimitiveDateTime,
+	Time
+};
+
+use crate::Column;
+use crate::column::Affinity;
+use crate::value::Check;
+
+const DATE_GLOB: Check = Check::Sql("GLOB '[0-9][0-9][0-9][0-9]-[0-1][0-9]-[0-3][0-9]*'");
+const TIME_GLOB: Check = Check::Sql("GLOB '[0-2][0-9]:[0-5][0-9]*'");
+
+impl Column for OffsetDateTime {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[DATE_GLOB];
+}
+impl Column for PrimitiveDateTime {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[DATE_GLOB];
+}
+impl Column for Date {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[DATE_GLOB];
+}
+impl Column for Time {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[TIME_GLOB];
+}
+crate::types::impl_from_to_sql_2!(OffsetDateTime);
+crate::types::impl_from_to_sql_2!(PrimitiveDateTime);
+crate::types::impl_from_to_sql_2!(Date);
+crate::types::impl_from_to_sql_2!(Time);
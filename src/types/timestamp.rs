@@ -0,0 +1,124 @@
+//! [`Value`] implementations for [`SystemTime`] & [`Duration`]
+//!
+//! Both are stored as `INTEGER` nanoseconds: a [`SystemTime`] as nanoseconds since the Unix epoch (negative before it), a [`Duration`] as its length with a `CHECK (… >= 0)` constraint.
+//! This covers the years 1677 to 2262 and durations of up to 292 years, binding anything outside of that range fails.
+//!
+//! For timestamps that should be readable in other tools, use the `chrono` or `time` feature instead.
+
+use std::time::{
+	Duration,
+	SystemTime,
+	UNIX_EPOCH
+};
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::Type;
+
+use crate::{
+	Bind,
+	Binder,
+	Fetch,
+	Value
+};
+use crate::column::{
+	Affinity,
+	ColumnDef
+};
+use crate::types::Fetcher;
+use crate::value::{
+	Check,
+	NestedValueDef,
+	ValueDef
+};
+
+const fn definition(checks: &'static [Check]) -> ValueDef {
+	ValueDef {
+		unique: false,
+		nullable: false,
+		inner: NestedValueDef::Column(ColumnDef {
+			affinity: Affinity::Integer,
+			nullable: false,
+			checks
+		}),
+		reference: None,
+		checks: &[]
+	}
+}
+
+fn out_of_range(what: &str) -> Error {
+	Error::ToSqlConversionFailure(
+		format!("{what} doesn't fit into 64-bit nanoseconds").into()
+	)
+}
+
+fn nanos(duration: Duration) -> Option<i64> {
+	i64::try_from(duration.as_nanos()).ok()
+}
+
+impl Value for Duration {
+	const DEFINITION: ValueDef = definition(&[Check::Sql(">= 0")]);
+	type References = ();
+}
+impl Bind for Duration {
+	const COLUMNS: usize = 1;
+	fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+		let nanos = nanos(*self).ok_or_else(|| out_of_range("duration"))?;
+		binder.bind_parameter(&nanos)
+	}
+}
+impl Fetch for Duration {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		duration_from_nanos(fetcher.fetch_column()?)
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		fetcher.try_fetch_column()?
+			.map(duration_from_nanos)
+			.transpose()
+	}
+}
+
+fn duration_from_nanos(nanos: i64) -> SqlResult<Duration> {
+	u64::try_from(nanos)
+		.map(Duration::from_nanos)
+		.map_err(|err| Error::FromSqlConversionFailure(
+			0,
+			Type::Integer,
+			Box::new(err)
+		))
+}
+
+impl Value for SystemTime {
+	const DEFINITION: ValueDef = definition(&[]);
+	type References = ();
+}
+impl Bind for SystemTime {
+	const COLUMNS: usize = 1;
+	fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+		let nanos = match self.duration_since(UNIX_EPOCH) {
+			Ok(since) => nanos(since),
+			Err(before) => nanos(before.duration()).map(|nanos| -nanos)
+		};
+		let nanos = nanos.ok_or_else(|| out_of_range("time"))?;
+		binder.bind_parameter(&nanos)
+	}
+}
+impl Fetch for SystemTime {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		fetcher.fetch_column().map(time_from_nanos)
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		fetcher.try_fetch_column()
+			.map(|nanos| nanos.map(time_from_nanos))
+	}
+}
+
+fn time_from_nanos(nanos: i64) -> SystemTime {
+	let since = Duration::from_nanos(nanos.unsigned_abs());
+	match nanos < 0 {
+		true => UNIX_EPOCH - since,
+		false => UNIX_EPOCH + since
+	}
+}
@@ -0,0 +1,15 @@
+//! [`Column`] implementation for [`Url`]s
+//!
+//! This module is only available with the `url` feature.
+//!
+//! A [`Url`] is stored as `TEXT` and parsed again when fetched, so fetching text that isn't a valid URL fails.
+
+use ::url::Url;
+
+use crate::Column;
+use crate::column::Affinity;
+
+impl Column for Url {
+	const AFFINITY: Affinity = Affinity::Text;
+}
+crate::types::impl_from_to_sql_2!(Url);
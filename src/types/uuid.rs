@@ -0,0 +1,64 @@
+//! [`Column`] implementations for [`Uuid`]s
+//!
+//! This module is only available with the `uuid` feature.
+//!
+//! A [`Uuid`] is stored as a 16-byte `BLOB`, with a `CHECK` on that length, and fetching a `BLOB` of any other length fails.
+//! To store it as hyphenated `TEXT` instead, e.g. to keep it readable in other tools, wrap it in [`HyphenatedUuid`].
+
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlError,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+use ::uuid::Uuid;
+
+use crate::Column;
+use crate::column::Affinity;
+use crate::value::Check;
+
+impl Column for Uuid {
+	const AFFINITY: Affinity = Affinity::Blob;
+	const CHECKS: &'static [Check] = &[Check::Length(16)];
+}
+crate::types::impl_from_to_sql_2!(Uuid);
+
+/// [`Uuid`] stored as lowercase hyphenated `TEXT`, like `67e55044-10b1-426f-9247-bb680e5fe0c8`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HyphenatedUuid(pub Uuid);
+
+impl FromSql for HyphenatedUuid {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		let text = value.as_str()?;
+		Uuid::try_parse(text)
+			.map(Self)
+			.map_err(|err| FromSqlError::Other(err.into()))
+	}
+}
+impl ToSql for HyphenatedUuid {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		Ok(self.0.hyphenated().to_string().into())
+	}
+}
+crate::types::impl_from_to_sql_2!(HyphenatedUuid);
+
+impl Column for HyphenatedUuid {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[Check::Sql("GLOB '\
+		[0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f]-\
+		[0-9a-f][0-9a-f][0-9a-f][0-9a-f]-\
+		[0-9a-f][0-9a-f][0-9a-f][0-9a-f]-\
+		[0-9a-f][0-9a-f][0-9a-f][0-9a-f]-\
+		[0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f]\
+		[0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f]'"
+	)];
+}
+
+impl From<Uuid> for HyphenatedUuid {
+	fn from(uuid: Uuid) -> Self {
+		Self(uuid)
+	}
+}
@@ -0,0 +1,201 @@
+//! Embedding [`Vector`]s and nearest-neighbor search
+//!
+//! This module is only available with the `vector` feature.
+//!
+//! A [`Vector`] of `D` dimensions is stored as a `BLOB` of `D` little-endian `f32`s, with a `CHECK` on its length.
+//! [`Database::nearest`] finds the entries whose vector is closest to a query vector, by Euclidean distance.
+//! This scans the whole table, which is fast enough for many thousands of vectors, but isn't an index.
+//!
+//! The distance is computed by the `vec_distance_l2` function of [sqlite-vec](https://github.com/asg017/sqlite-vec) if that extension is loaded (e.g. in [`Schema::on_open`]), since the vectors are stored in its format.
+//! Otherwise, liter registers a function of its own.
+//!
+//!```
+//! use liter::{database, Id, Table};
+//! use liter::types::vector::Vector;
+//!
+//! #[database]
+//! struct Db (Document);
+//!
+//! #[derive(Table, Debug)]
+//! struct Document {
+//! 	#[key]
+//! 	id: Id,
+//! 	title: String,
+//! 	embedding: Vector<3>
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! for (title, embedding) in [("cats", [1.0, 0.0, 0.0]), ("dogs", [0.8, 0.2, 0.0]), ("tax law", [0.0, 0.1, 1.0])] {
+//! 	db.insert(&Document { id: Id::NULL, title: title.to_string(), embedding: Vector(embedding) })?;
+//! }
+//!
+//! let nearest = db.nearest::<Document>(&[1.0, 0.05, 0.0], 2)?;
+//! let titles: Vec<&str> = nearest.iter().map(|(doc, _distance)| doc.title.as_str()).collect();
+//! assert_eq!(titles, ["cats", "dogs"]);
+//!
+//! // the length of the BLOB is checked
+//! assert!(db.execute("UPDATE document SET embedding = x'00'", &()).is_err());
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::ops::Deref;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::functions::{
+	Context,
+	FunctionFlags
+};
+use rusqlite::types::{
+	FromSql,
+	FromSqlError,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+
+use crate::{
+	Binder,
+	Column,
+	Database,
+	Entry,
+	Fetch,
+	Schema,
+	Table
+};
+use crate::column::Affinity;
+use crate::meta::PartOf;
+use crate::table;
+use crate::types::{
+	FromSql2,
+	ToSql2
+};
+use crate::value::Check;
+
+/// Distance function of sqlite-vec
+const SQLITE_VEC_DISTANCE: &str = "vec_distance_l2";
+/// Distance function registered by liter, if sqlite-vec isn't loaded
+const DISTANCE: &str = "liter_distance_l2";
+
+/// Vector of `D` `f32`s, stored as a `BLOB` of `4 * D` bytes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector<const D: usize>(pub [f32; D]);
+
+impl<const D: usize> Deref for Vector<D> {
+	type Target = [f32; D];
+	fn deref(&self) -> &Self::Target {&self.0}
+}
+impl<const D: usize> From<[f32; D]> for Vector<D> {
+	fn from(array: [f32; D]) -> Self {
+		Self(array)
+	}
+}
+
+impl<const D: usize> Column for Vector<D> {
+	const AFFINITY: Affinity = Affinity::Blob;
+	const CHECKS: &'static [Check] = &[Check::Length(D * 4)];
+}
+impl<const D: usize> FromSql for Vector<D> {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		let blob = value.as_blob()?;
+		if blob.len() != D * 4 {
+			return Err(FromSqlError::InvalidBlobSize {
+				expected_size: D * 4,
+				blob_size: blob.len()
+			});
+		}
+		let mut vector = [0.0; D];
+		for (float, bytes) in vector.iter_mut().zip(blob.chunks_exact(4)) {
+			*float = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+		}
+		Ok(Self(vector))
+	}
+}
+impl<const D: usize> ToSql for Vector<D> {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		Ok(to_blob(&self.0).into())
+	}
+}
+impl<const D: usize> FromSql2 for Vector<D> {}
+impl<const D: usize> ToSql2 for Vector<D> {}
+
+impl<S: Schema> Database<S> {
+	/// Get the `k` entries whose vector is closest to the query, together with their distance, closest first
+	///
+	/// The table has to contain exactly one [`Vector`] with as many dimensions as the query, otherwise this fails with [`Error::InvalidQuery`].
+	/// Entries whose vector is `NULL` are skipped.
+	pub fn nearest<T>(&self, query: &[f32], k: usize) -> SqlResult<Vec<(T, f64)>>
+		where T: Table + Entry + PartOf<S>
+	{
+		let columns = table::columns_with_check(
+			&T::DEFINITION,
+			T::ALL_COLUMNS,
+			Check::Length(query.len() * 4)
+		);
+		let [column] = columns[..] else {
+			return Err(Error::InvalidQuery);
+		};
+		let sql = format!(
+			"SELECT *, {distance}({column}, ?) AS liter_distance FROM \"{table}\" \
+			WHERE {column} IS NOT NULL ORDER BY liter_distance LIMIT ?",
+			distance = self.distance_function()?,
+			table = T::NAME
+		);
+		let mut stmt = self.prepare(&sql)?;
+		let mut binder = Binder::make(&mut stmt);
+		binder.bind(&to_blob(query))?;
+		binder.bind(&(k as i64))?;
+		let mut rows = stmt.raw_query();
+		let mut nearest = Vec::new();
+		while let Some(row) = rows.next()? {
+			nearest.push(<(T, f64)>::from_row(row)?);
+		}
+		Ok(nearest)
+	}
+
+	/// Name of the function computing the distance between vectors, registering it if necessary
+	fn distance_function(&self) -> SqlResult<&'static str> {
+		for name in [SQLITE_VEC_DISTANCE, DISTANCE] {
+			if self.prepare(&format!("SELECT {name}(NULL, NULL)")).is_ok() {
+				return Ok(name);
+			}
+		}
+		self.create_scalar_function(
+			DISTANCE,
+			2,
+			FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+			distance
+		)?;
+		Ok(DISTANCE)
+	}
+}
+
+fn to_blob(vector: &[f32]) -> Vec<u8> {
+	vector.iter()
+		.flat_map(|float| float.to_le_bytes())
+		.collect()
+}
+
+/// Euclidean distance between two vectors, `NULL` if either of them is
+fn distance(ctx: &Context<'_>) -> SqlResult<Option<f64>> {
+	let (Some(a), Some(b)) = (ctx.get_raw(0).as_blob_or_null()?, ctx.get_raw(1).as_blob_or_null()?) else {
+		return Ok(None);
+	};
+	if a.len() != b.len() || a.len() % 4 != 0 {
+		return Err(Error::UserFunctionError(
+			format!("{DISTANCE}: vectors of {} and {} bytes", a.len(), b.len()).into()
+		));
+	}
+	let sum: f64 = a.chunks_exact(4)
+		.zip(b.chunks_exact(4))
+		.map(|(a, b)| {
+			let a = f32::from_le_bytes([a[0], a[1], a[2], a[3]]) as f64;
+			let b = f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64;
+			(a - b) * (a - b)
+		})
+		.sum();
+	Ok(Some(sum.sqrt()))
+}
@@ -0,0 +1,133 @@
+//! Updating some of the columns of all entries matching a condition
+//!
+//! [`Database::update`] replaces all non-key values of a single entry.
+//! [`Database::update_where`] instead starts an [`UpdateWhere`], which only [`set`](UpdateWhere::set)s the given columns, in all rows matching its [`filter`](UpdateWhere::filter).
+//! Columns are given by [`TypedColumn`](crate::column::TypedColumn) handles declared with [`columns!`](crate::columns), so setting a column of another table or a value of the wrong type doesn't compile.
+//! All values are bound as parameters.
+//!
+//! Like [`execute`](Database::execute), this is limited by [`require_where_for_bulk_ops`](Database::require_where_for_bulk_ops).
+//!
+//!```
+//! use liter::{columns, database, Id, Table};
+//!
+//! #[database]
+//! struct Db (User);
+//!
+//! #[derive(Table)]
+//! struct User {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String,
+//! 	active: bool,
+//! 	last_login: i64
+//! }
+//! columns!(struct UserColumns for User { active: bool });
+//!
+//! let db = Db::create_in_memory()?;
+//! for (name, last_login) in [("ada", 100), ("grace", 500), ("alan", 200)] {
+//! 	db.insert(&User { id: Id::NULL, name: name.to_string(), active: true, last_login })?;
+//! }
+//!
+//! let deactivated = db.update_where::<User>()
+//! 	.set(User::columns().active.eq(false))
+//! 	.filter("last_login < ?", 300)
+//! 	.execute()?;
+//! assert_eq!(deactivated, 2);
+//! assert_eq!(db.count_where::<User, _>("active", &())?, 1);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::marker::PhantomData;
+
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::ToSql;
+
+use crate::{
+	Bind,
+	Binder,
+	Database,
+	Schema,
+	Table
+};
+use crate::column::Assign;
+use crate::meta::PartOf;
+
+/// Update of the rows of `T` matching a condition, see the [module documentation](self)
+#[must_use = "the update only runs when calling execute()"]
+pub struct UpdateWhere<'d, S: Schema, T, P = ()> {
+	db: &'d Database<S>,
+	set: Vec<(&'static str, Box<dyn ToSql>)>,
+	condition: Option<String>,
+	params: P,
+	table: PhantomData<T>
+}
+
+impl<S: Schema> Database<S> {
+	/// Start updating some columns of the entries matching a condition, see [`UpdateWhere`]
+	pub fn update_where<T>(&self) -> UpdateWhere<'_, S, T>
+		where T: Table + PartOf<S>
+	{
+		UpdateWhere {
+			db: self,
+			set: Vec::new(),
+			condition: None,
+			params: (),
+			table: PhantomData
+		}
+	}
+}
+
+impl<'d, S: Schema, T: Table, P: Bind> UpdateWhere<'d, S, T, P> {
+	/// Set a column to a value, replacing any previous value of the same column
+	pub fn set(mut self, assign: Assign<T>) -> Self {
+		let Assign { column, value, .. } = assign;
+		match self.set.iter_mut().find(|(name, _value)| *name == column) {
+			Some((_name, previous)) => *previous = value,
+			None => self.set.push((column, value))
+		}
+		self
+	}
+	/// Only update the rows matching the condition, i.e. the SQL following `WHERE`, replacing any previous filter
+	///
+	/// The condition may contain parameters, which are bound to `params`.
+	pub fn filter<Q: Bind>(self, condition: &str, params: Q) -> UpdateWhere<'d, S, T, Q> {
+		UpdateWhere {
+			db: self.db,
+			set: self.set,
+			condition: Some(condition.to_string()),
+			params,
+			table: PhantomData
+		}
+	}
+	/// Run the update, returning the number of updated rows
+	///
+	/// Without a [`filter`](Self::filter), all rows are updated.
+	/// Fails with [`Error::InvalidQuery`] if no column was [`set`](Self::set).
+	#[track_caller]
+	pub fn execute(self) -> SqlResult<usize> {
+		if self.set.is_empty() {
+			return Err(Error::InvalidQuery);
+		}
+		let assignments: Vec<String> = self.set.iter()
+			.map(|(column, _value)| format!("{column} = ?"))
+			.collect();
+		let mut sql = format!("UPDATE \"{}\" SET {}", T::NAME, assignments.join(", "));
+		if let Some(condition) = &self.condition {
+			sql.push_str(" WHERE ");
+			sql.push_str(condition);
+		}
+		let db = self.db;
+		db.track_table_write::<T, _>("update_where", || {
+			let mut stmt = db.prepare(&sql)?;
+			let mut binder = Binder::make(&mut stmt);
+			for (_column, value) in &self.set {
+				binder.bind_parameter(value)?;
+			}
+			binder.bind(&self.params)?;
+			db.guard_bulk(&sql, &mut stmt)
+		})
+	}
+}
@@ -0,0 +1,170 @@
+//! Opening databases by [URI filenames](https://sqlite.org/uri.html), with query parameters
+//!
+//! A URI like `file:data.db?mode=ro&immutable=1` names the database file along with options for opening it, e.g. that it's on read-only media and thus never changes.
+//! [`Database::open_uri`] opens one given as a string, and [`DatabaseUri`] builds one from typed parameters, escaping the path & values.
+//!
+//!```
+//! use liter::{database, Database, Table};
+//! use liter::uri::{DatabaseUri, UriMode};
+//!
+//! #[database]
+//! struct Db (Station);
+//!
+//! #[derive(Table)]
+//! struct Station {
+//! 	name: String
+//! }
+//!
+//! let path = std::env::temp_dir().join("liter_uri_doctest.db");
+//! # let _ = std::fs::remove_file(&path);
+//! # if cfg!(unix) {
+//! let db = Db::init(&path)?;
+//! db.insert(&Station { name: "north".to_string() })?;
+//! drop(db);
+//!
+//! let uri = DatabaseUri::file(path.to_str().unwrap())
+//! 	.mode(UriMode::ReadOnly)
+//! 	.immutable();
+//! assert!(uri.as_str().ends_with("?mode=ro&immutable=1"));
+//! let archive = Database::<Db>::open_uri(&uri)?;
+//! assert_eq!(archive.get_all::<Station>()?.len(), 1);
+//! assert!(archive.insert(&Station { name: "south".to_string() }).is_err());
+//! # }
+//! # let _ = std::fs::remove_file(&path);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::fmt;
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	OpenOptions,
+	Schema
+};
+
+/// `mode` parameter of a [`DatabaseUri`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UriMode {
+	ReadOnly,
+	ReadWrite,
+	/// Read-write, creating the database if it doesn't exist, which also needs the `SQLITE_OPEN_CREATE` flag
+	ReadWriteCreate,
+	/// A database in memory, which is only shared between connections using the same name & [`UriCache::Shared`]
+	Memory
+}
+
+impl UriMode {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			UriMode::ReadOnly => "ro",
+			UriMode::ReadWrite => "rw",
+			UriMode::ReadWriteCreate => "rwc",
+			UriMode::Memory => "memory"
+		}
+	}
+}
+
+/// `cache` parameter of a [`DatabaseUri`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UriCache {
+	Shared,
+	Private
+}
+
+impl UriCache {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			UriCache::Shared => "shared",
+			UriCache::Private => "private"
+		}
+	}
+}
+
+/// Builder for a `file:` URI with query parameters
+///
+/// The path & parameter values are percent-encoded where they contain characters that are special in URIs, but are otherwise taken as they are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatabaseUri {
+	uri: String,
+	has_query: bool
+}
+
+impl DatabaseUri {
+	/// URI of the database file at the path, which is relative to the working directory unless it starts with a `/`
+	pub fn file(path: &str) -> Self {
+		let mut uri = String::from("file:");
+		// `//` would start an authority, which SQLite only accepts when empty
+		if path.starts_with("//") {
+			uri.push_str("//");
+		}
+		push_encoded(&mut uri, path, b"?#");
+		Self { uri, has_query: false }
+	}
+	pub fn mode(self, mode: UriMode) -> Self {
+		self.param("mode", mode.as_str())
+	}
+	pub fn cache(self, cache: UriCache) -> Self {
+		self.param("cache", cache.as_str())
+	}
+	/// Open the database with this VFS, like [`OpenOptions::vfs`]
+	pub fn vfs(self, name: &str) -> Self {
+		self.param("vfs", name)
+	}
+	/// Assume the database can't change, e.g. because it's on read-only media, so SQLite doesn't lock it or check for changes
+	///
+	/// If it changes anyway, queries may return wrong results or fail with `SQLITE_CORRUPT`.
+	pub fn immutable(self) -> Self {
+		self.param("immutable", "1")
+	}
+	/// Don't lock the database, which is only safe if no other connection writes to it
+	pub fn nolock(self) -> Self {
+		self.param("nolock", "1")
+	}
+	/// Add any other query parameter, see [the SQLite documentation](https://sqlite.org/uri.html#recognized_query_parameters)
+	pub fn param(mut self, name: &str, value: &str) -> Self {
+		self.uri.push(if self.has_query {'&'} else {'?'});
+		self.has_query = true;
+		push_encoded(&mut self.uri, name, b"&=#");
+		self.uri.push('=');
+		push_encoded(&mut self.uri, value, b"&=#");
+		self
+	}
+	pub fn as_str(&self) -> &str {
+		&self.uri
+	}
+}
+
+impl AsRef<str> for DatabaseUri {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl fmt::Display for DatabaseUri {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.uri)
+	}
+}
+
+/// Push the text, percent-encoding `%` and the special characters
+fn push_encoded(uri: &mut String, text: &str, special: &[u8]) {
+	for c in text.chars() {
+		match u8::try_from(c) {
+			Ok(b) if b == b'%' || special.contains(&b) => {
+				uri.push_str(&format!("%{b:02X}"));
+			},
+			_ => uri.push(c)
+		}
+	}
+}
+
+impl<S: Schema> Database<S> {
+	/// Open the (existing) database by a URI filename, like `file:data.db?mode=ro&immutable=1`
+	///
+	/// This is the same as [`OpenOptions::open_uri`] with the default options.
+	pub fn open_uri(uri: impl AsRef<str>) -> SqlResult<Self> {
+		OpenOptions::new().open_uri(uri)
+	}
+}
@@ -2,10 +2,13 @@
 //!
 //! This module is an internal implementation detail, there are absolutely no stability guarantees.
 //!
-//! The things here are used by the [`#[database]`](crate::database) and [`#[derive(Table)]`](liter_derive::Table) procedural macros.
+//! The things here are used by the [`#[database]`](crate::database) and [`#[derive(Table)]`](liter_derive::Table) procedural macros, as well as [`bitflags_column!`](crate::bitflags_column).
 //! Because the code they generate does not belong to this crate (or [`liter_derive`]), but to the crate they were invoked in, it will not have access to this [`crate`]'s (`liter`) dependencies.
 //! So, whatever they use is re-exported here so that it can be accessed under the `liter` namespace.
+//!
+//! It also holds a few SQL snippets shared by the modules of this crate.
 
+pub use rusqlite;
 pub use rusqlite::Result as SqlResult;
 pub use construe::construe;
 
@@ -16,3 +19,6 @@ pub fn invalid_variant(msg: String) -> rusqlite::Error {
 		msg.into()
 	)
 }
+
+/// Current time as (fractional) UNIX timestamp, evaluated by SQLite
+pub(crate) const NOW: &str = "((julianday('now') - 2440587.5) * 86400.0)";
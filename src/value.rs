@@ -31,26 +31,70 @@ pub struct ValueDef {
 	pub nullable: bool,
 	pub inner: NestedValueDef,
 	pub reference: Option<ForeignKey>,
-	pub checks: &'static [Check]
+	pub checks: &'static [Check],
+}
+
+/// `GENERATED ALWAYS AS (…)` clause of a computed [`Column`]
+///
+/// Set with [`ValueDef::generated`].
+/// Generated columns are left out of the `INSERT`, `UPDATE` & `UPSERT` statements of tables with [`Entry::HAS_GENERATED`](crate::Entry::HAS_GENERATED) (see e.g. [`table::insert_for`](crate::table::insert_for)), but they are still fetched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Generated {
+	/// SQL expression, which may refer to other columns of the same row
+	pub sql: &'static str,
+	/// Whether the value is computed on write and stored (`STORED`), or computed on read (`VIRTUAL`)
+	pub stored: bool
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum NestedValueDef {
 	Column (ColumnDef),
+	/// A single [`Column`] with [`ColumnClauses`] beyond its [`ColumnDef`]
+	ColumnWith (ColumnDef, ColumnClauses),
 	//Columns (&'static [(&'static str, ColumnDef)]),
 	Value (&'static ValueDef),
 	Values (&'static [(&'static str, ValueDef)]),
 }
 
+/// Clauses of a single [`Column`] that aren't part of its [`ColumnDef`]
+///
+/// These are set with the `const fn`s of [`ValueDef`], like [`collate`](ValueDef::collate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnClauses {
+	/// Collation (`COLLATE …`) of the column, [`Column::COLLATION`] unless overridden
+	pub collation: Option<&'static str>,
+	/// SQL expression used as the `DEFAULT` of the column
+	pub default: Option<&'static str>,
+	/// Expression that the column is computed from, instead of being stored
+	pub generated: Option<Generated>
+}
+
+impl ColumnClauses {
+	pub const NONE: Self = Self {
+		collation: None,
+		default: None,
+		generated: None
+	};
+	const fn is_none(&self) -> bool {
+		self.collation.is_none() && self.default.is_none() && self.generated.is_none()
+	}
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Check {
 	// SQL string that will be prepended with the name of the column
-	Sql(&'static str)
+	Sql(&'static str),
+	/// `length(…)` of the column has to be exactly this, i.e. the number of bytes of a `BLOB`, or characters of a `TEXT`
+	Length(usize),
+	/// The column has to match the regular expression, using SQLite's `REGEXP` operator
+	///
+	/// SQLite has no built-in `REGEXP`, the `regex` feature registers one on every connection.
+	/// Otherwise, a `regexp()` function has to be registered in [`Schema::on_open`](crate::Schema::on_open).
+	Regex(&'static str)
 }
 
 // Note: The Value does not know the Type that is being referenced
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ForeignKey {
 	pub table_name: &'static str,
 	pub deferrable: bool,
@@ -67,13 +111,31 @@ impl ForeignKey {
 			on_update: FkConflictAction::Restrict
 		}
 	}
+	/// Override the `on_delete` field with the action
+	pub const fn on_delete(self, action: FkConflictAction) -> Self {
+		Self {on_delete: action, ..self}
+	}
+	/// Override the `on_update` field with the action
+	pub const fn on_update(self, action: FkConflictAction) -> Self {
+		Self {on_update: action, ..self}
+	}
+	/// Override the `deferrable` field as `false`, so the constraint is checked after each statement instead of at the end of the transaction
+	pub const fn immediate(self) -> Self {
+		Self {deferrable: false, ..self}
+	}
 }
 
+/// Action taken on the referencing rows when the referenced key is deleted or updated
+///
+/// Set on the [`ValueDef`] of a reference with e.g. `<Ref<T> as Value>::DEFINITION.on_delete(FkConflictAction::Cascade)`.
+/// [`SetNull`](Self::SetNull) can only be used on an optional reference, i.e. an `Option<Ref<T>>` field.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FkConflictAction {
 	Cascade,
 	Restrict,
-	SetNull
+	SetNull,
+	SetDefault,
+	NoAction
 }
 
 /// Linked list of [`&str`]
@@ -136,6 +198,8 @@ impl FkConflictAction {
 			Self::Cascade => "CASCADE",
 			Self::Restrict => "RESTRICT",
 			Self::SetNull => "SET NULL",
+			Self::SetDefault => "SET DEFAULT",
+			Self::NoAction => "NO ACTION",
 		}
 	}
 }
@@ -146,7 +210,13 @@ impl<T: Column> Value for T {
 	const DEFINITION: ValueDef = ValueDef {
 		unique: false,
 		nullable: false,
-		inner: NestedValueDef::Column(<Self as Column>::DEFINITION),
+		inner: NestedValueDef::column_with(
+			<Self as Column>::DEFINITION,
+			ColumnClauses {
+				collation: <Self as Column>::COLLATION,
+				..ColumnClauses::NONE
+			}
+		),
 		reference: None,
 		checks: &[],
 	};
@@ -165,6 +235,88 @@ impl ValueDef {
 	pub const fn unique(self) -> Self {
 		Self {unique: true, ..self}
 	}
+	/// Set the collation of the (single) column, overriding its [`Column::COLLATION`]
+	///
+	/// This panics if the value has more than one column.
+	pub const fn collate(self, collation: &'static str) -> Self {
+		let (column, clauses) = self.inner.single_column();
+		Self {
+			inner: NestedValueDef::ColumnWith(column, ColumnClauses {
+				collation: Some(collation),
+				..clauses
+			}),
+			..self
+		}
+	}
+	/// Set the SQL expression used as the `DEFAULT` of the (single) column
+	///
+	/// [`Database::insert_with_defaults`](crate::Database::insert_with_defaults) leaves the column out, so SQLite fills it in.
+	/// This panics if the value has more than one column, or if it is generated.
+	pub const fn with_default(self, sql: &'static str) -> Self {
+		let (column, clauses) = self.inner.single_column();
+		assert!(clauses.generated.is_none(), "generated columns can't have a DEFAULT");
+		Self {
+			inner: NestedValueDef::ColumnWith(column, ColumnClauses {
+				default: Some(sql),
+				..clauses
+			}),
+			..self
+		}
+	}
+	/// Compute the (single) column from the SQL expression, see [`Generated`]
+	///
+	/// This panics if the value has more than one column, or if it has a `DEFAULT`.
+	pub const fn generated(self, sql: &'static str, stored: bool) -> Self {
+		let (column, clauses) = self.inner.single_column();
+		assert!(clauses.default.is_none(), "generated columns can't have a DEFAULT");
+		Self {
+			inner: NestedValueDef::ColumnWith(column, ColumnClauses {
+				generated: Some(Generated {sql, stored}),
+				..clauses
+			}),
+			..self
+		}
+	}
+	/// [`ColumnClauses`] of the value, which only a single column can have
+	pub const fn clauses(&self) -> ColumnClauses {
+		self.inner.clauses()
+	}
+	/// Override the `reference` field's `ON DELETE` action
+	///
+	/// Being `const`, this can build the [`Value::DEFINITION`] of a hand-written value or table.
+	/// It panics if the value isn't a reference, or if the action is [`SetNull`](FkConflictAction::SetNull) and the value isn't nullable, e.g. a `Ref<T>` instead of an `Option<Ref<T>>`.
+	pub const fn on_delete(self, action: FkConflictAction) -> Self {
+		let Some(fk) = self.reference else {
+			panic!("foreign key action set on a value that isn't a reference")
+		};
+		self.check_set_null(action);
+		Self {reference: Some(fk.on_delete(action)), ..self}
+	}
+	/// Override the `reference` field's `ON UPDATE` action
+	///
+	/// This is `const` and panics like [`on_delete`](Self::on_delete).
+	pub const fn on_update(self, action: FkConflictAction) -> Self {
+		let Some(fk) = self.reference else {
+			panic!("foreign key action set on a value that isn't a reference")
+		};
+		self.check_set_null(action);
+		Self {reference: Some(fk.on_update(action)), ..self}
+	}
+	/// Make the `reference` field's constraint not `DEFERRABLE`
+	///
+	/// This is `const` and panics if the value isn't a reference.
+	pub const fn immediate_reference(self) -> Self {
+		let Some(fk) = self.reference else {
+			panic!("foreign key action set on a value that isn't a reference")
+		};
+		Self {reference: Some(fk.immediate()), ..self}
+	}
+	/// `SET NULL` on a `NOT NULL` column would fail every time the referenced key changes
+	const fn check_set_null(&self, action: FkConflictAction) {
+		if matches!(action, FkConflictAction::SetNull) && !self.nullable {
+			panic!("SET NULL action on a reference that isn't nullable, use an Option<Ref<…>>")
+		}
+	}
 	pub(crate) const fn push_sql<const N: usize>(
 		&self,
 		name: &str,
@@ -197,7 +349,7 @@ impl ValueDef {
 			sc = fk_ref.push_sql(sc)
 		}
 		match self.inner {
-			NestedValueDef::Column(_) => {},
+			NestedValueDef::Column(_) | NestedValueDef::ColumnWith(..) => {},
 			NestedValueDef::Value(v) => sc = v.push_constraint_sql(chain, sc),
 			NestedValueDef::Values(mut values) => {
 				while let [(name, def), rest @ ..] = values {
@@ -214,6 +366,30 @@ impl ValueDef {
 }
 
 impl NestedValueDef {
+	/// A single [`Column`], with the [`ColumnClauses`] if there are any
+	pub const fn column_with(def: ColumnDef, clauses: ColumnClauses) -> Self {
+		match clauses.is_none() {
+			true => Self::Column(def),
+			false => Self::ColumnWith(def, clauses)
+		}
+	}
+	/// The [`ColumnDef`] & [`ColumnClauses`] of a single [`Column`]
+	///
+	/// This panics for values that aren't a single column.
+	pub(crate) const fn single_column(&self) -> (ColumnDef, ColumnClauses) {
+		match *self {
+			Self::Column(def) => (def, ColumnClauses::NONE),
+			Self::ColumnWith(def, clauses) => (def, clauses),
+			_ => panic!("column clause used on a value that isn't a single column")
+		}
+	}
+	pub(crate) const fn clauses(&self) -> ColumnClauses {
+		match *self {
+			Self::ColumnWith(_def, clauses) => clauses,
+			Self::Value(def) => def.inner.clauses(),
+			Self::Column(_) | Self::Values(_) => ColumnClauses::NONE
+		}
+	}
 	pub(crate) const fn push_column_names<const N: usize>(
 		&self,
 		chain: &StrChain<'_>,
@@ -221,7 +397,7 @@ impl NestedValueDef {
 		-> StrConstrue<N>
 	{
 		match self {
-			Self::Column(_def) => chain.join(sc, "_"),
+			Self::Column(_def) | Self::ColumnWith(_def, _) => chain.join(sc, "_"),
 			Self::Value(def) => def.inner.push_column_names(chain, sc),
 			// this matches only on the last definition
 			Self::Values([(name, def)]) =>
@@ -246,8 +422,12 @@ impl NestedValueDef {
 		-> StrConstrue<N>
 	{
 		match self {
-			Self::Column(def) if nullable => def.nullable().push_sql(chain, sc),
-			Self::Column(def) => def.push_sql(chain, sc),
+			Self::Column(def) if nullable =>
+				def.nullable().push_sql(chain, &ColumnClauses::NONE, sc),
+			Self::Column(def) => def.push_sql(chain, &ColumnClauses::NONE, sc),
+			Self::ColumnWith(def, clauses) if nullable =>
+				def.nullable().push_sql(chain, clauses, sc),
+			Self::ColumnWith(def, clauses) => def.push_sql(chain, clauses, sc),
 			Self::Value(def) => def.inner
 				.push_sql(nullable | def.nullable, chain, sc),
 			// this matches only on the last definition
@@ -268,10 +448,10 @@ impl NestedValueDef {
 			Self::Values([]) => panic!("empty Values([])")
 		}
 	}
-	const fn count_columns(&self) -> usize {
+	pub(crate) const fn count_columns(&self) -> usize {
 		match self {
 			// base case
-			Self::Column(_def) => 1,
+			Self::Column(_def) | Self::ColumnWith(_def, _) => 1,
 			// multi-column Value implementation on a struct
 			// define each with name prepended
 			//Self::Columns(_) => todo!(),
@@ -0,0 +1,187 @@
+//! Read-only SQL views, defined next to the [`Table`](crate::Table)s of the schema
+//!
+//! A [`View`] is a named `SELECT` statement, e.g. a read model joining or aggregating tables.
+//! The [`view!`] macro defines a struct for its rows, which can be fetched with [`Database::get_all_view`].
+//! The views listed in [`Schema::VIEWS`](crate::Schema::VIEWS) are created right after the tables, when the database is initialized.
+//! `#[database]` can't set them, so this takes a manual implementation of [`Schema`](crate::Schema).
+//! A view that isn't listed can be created with [`Database::create_view`].
+//!
+//!```
+//! use liter::{view, Database, Id, Ref, Schema, Table};
+//! use liter::table::TableDef;
+//! use liter::util::construe;
+//! use liter::view::{View, ViewDef};
+//!
+//! struct Db;
+//! impl Schema for Db {
+//! 	type Tables = (Author, (Book, ));
+//! 	const DEFINITIONS: &'static [TableDef] = &[Author::DEFINITION, Book::DEFINITION];
+//! 	const CREATE: &'static str = construe!(
+//! 		&str => liter::schema::define(&[Author::CREATE_TABLE, Book::CREATE_TABLE])
+//! 	);
+//! 	const VIEWS: &'static [ViewDef] = &[AuthorPages::DEFINITION];
+//! }
+//!
+//! #[derive(Table)]
+//! struct Author {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String
+//! }
+//! #[derive(Table)]
+//! struct Book {
+//! 	#[key]
+//! 	id: Id,
+//! 	author: Ref<Author>,
+//! 	pages: u32
+//! }
+//!
+//! view! {
+//! 	#[view(name = "author_pages", sql = "SELECT a.name, sum(b.pages) AS pages \
+//! 		FROM author AS a JOIN book AS b ON b.author = a.id GROUP BY a.id")]
+//! 	#[derive(Debug, PartialEq)]
+//! 	struct AuthorPages {
+//! 		name: String,
+//! 		pages: u32
+//! 	}
+//! }
+//!
+//! let db = Database::<Db>::create_in_memory()?;
+//! db.insert(&Author { id: Id::new(1), name: "Ursula".to_string() })?;
+//! db.insert(&Book { id: Id::NULL, author: Ref(Id::new(1)), pages: 183 })?;
+//! db.insert(&Book { id: Id::NULL, author: Ref(Id::new(1)), pages: 387 })?;
+//!
+//! let totals = db.get_all_view::<AuthorPages>()?;
+//! assert_eq!(totals, [AuthorPages { name: "Ursula".to_string(), pages: 570 }]);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use construe::StrConstrue;
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Fetch,
+	Schema
+};
+use crate::value::{
+	StrChain,
+	ValueDef
+};
+
+/// Read-only view of the database, defined with [`view!`]
+pub trait View: Fetch {
+	/// Name of the view
+	const NAME: &'static str;
+	/// `SELECT` statement defining the view
+	const SQL: &'static str;
+	/// `CREATE VIEW IF NOT EXISTS ... AS SELECT ...;`
+	const CREATE: &'static str;
+	/// `SELECT (...) FROM ...`
+	///
+	/// Select the view's columns (in the order of the fields) of all its rows.
+	const GET_ALL: &'static str;
+
+	const DEFINITION: ViewDef = ViewDef {
+		name: Self::NAME,
+		create: Self::CREATE
+	};
+}
+
+/// Definition of a [`View`], as listed in [`Schema::VIEWS`](crate::Schema::VIEWS)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewDef {
+	pub name: &'static str,
+	pub create: &'static str
+}
+
+impl<S: Schema> Database<S> {
+	/// Create the view if it doesn't exist yet, which is only needed if it's not one of the [`Schema::VIEWS`](crate::Schema::VIEWS)
+	pub fn create_view<V: View>(&self) -> SqlResult<()> {
+		self.execute_batch(V::CREATE)
+	}
+	/// Get all rows of the view
+	pub fn get_all_view<V: View>(&self) -> SqlResult<Vec<V>> {
+		self.query_all(V::GET_ALL)
+	}
+}
+
+/// Define a [`View`], see the [module documentation](crate::view)
+///
+/// The first attribute has to be `#[view(name = "…", sql = "…")]`, any others (like doc comments or derives) are applied to the struct.
+/// The fields have to be named like the view's columns.
+#[macro_export]
+macro_rules! view {
+	(
+		#[view(name = $view:literal, sql = $sql:literal)]
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident {
+			$( $(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty ),+ $(,)?
+		}
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			$( $(#[$field_attr])* $field_vis $field: $ty ),+
+		}
+		impl $crate::view::View for $name {
+			const NAME: &'static str = $view;
+			const SQL: &'static str = $sql;
+			const CREATE: &'static str = $crate::util::construe!(
+				&str => $crate::view::define($view, $sql)
+			);
+			const GET_ALL: &'static str = $crate::util::construe!(
+				&str => $crate::view::get_all(
+					$view,
+					&[$( (::std::stringify!($field), &<$ty as $crate::Value>::DEFINITION) ),+]
+				)
+			);
+		}
+		impl $crate::Fetch for $name {
+			fn fetch(fetcher: &mut $crate::types::Fetcher<'_>)
+				-> $crate::util::SqlResult<Self>
+			{
+				Ok(Self {
+					$( $field: fetcher.fetch()? ),+
+				})
+			}
+			fn try_fetch(fetcher: &mut $crate::types::Fetcher<'_>)
+				-> $crate::util::SqlResult<::std::option::Option<Self>>
+			{
+				<Self as $crate::Fetch>::fetch(fetcher).map(::std::option::Option::Some)
+			}
+		}
+	};
+}
+
+#[doc(inline)]
+pub use view;
+
+/// `CREATE VIEW IF NOT EXISTS "name" AS ...;`
+#[doc(hidden)]
+pub const fn define<const N: usize>(name: &str, sql: &str) -> StrConstrue<N> {
+	StrConstrue::new()
+		.push_str("CREATE VIEW IF NOT EXISTS \"")
+		.push_str(name)
+		.push_str("\" AS ")
+		.push_str(sql)
+		.push_str(";")
+}
+
+/// `SELECT (...) FROM "name"` for the fields of a view
+#[doc(hidden)]
+pub const fn get_all<const N: usize>(name: &str, mut fields: &[(&str, &ValueDef)])
+	-> StrConstrue<N>
+{
+	let mut sc = StrConstrue::new();
+	sc = sc.push_str("SELECT ");
+	let mut is_first = true;
+	while let [(field, def), rest @ ..] = fields {
+		fields = rest;
+		if !is_first {
+			sc = sc.push_str(", ");
+		}
+		else {is_first = false;}
+		sc = def.inner.push_column_names(&StrChain::start(field), sc);
+	}
+	sc.push_str(" FROM \"").push_str(name).push_str("\"")
+}
@@ -0,0 +1,174 @@
+//! Virtual tables exposing Rust collections to SQL
+//!
+//! This module is only available with the `vtab` feature.
+//!
+//! [`Database::register_collection`] makes a slice of entries available to queries as a read-only table, e.g. to `JOIN` in-memory data (like a request's payload) against the tables of the database.
+//! It uses an [eponymous virtual table](https://sqlite.org/vtab.html#eponymous_virtual_tables), so it doesn't need to be created and doesn't change the database file.
+//! Its columns are named like those of the [`Table`] the entries are of, which doesn't have to be part of the [`Schema`].
+//!
+//!```
+//! use liter::{database, Id, Ref, Table};
+//!
+//! #[database]
+//! struct Db (Product);
+//!
+//! #[derive(Table)]
+//! struct Product {
+//! 	#[key]
+//! 	id: Id,
+//! 	name: String,
+//! 	cents: u64
+//! }
+//! #[derive(Table)]
+//! struct CartLine {
+//! 	product: Ref<Product>,
+//! 	quantity: u32
+//! }
+//!
+//! let db = Db::create_in_memory()?;
+//! db.insert(&Product { id: Id::new(1), name: "pen".to_string(), cents: 150 })?;
+//! db.insert(&Product { id: Id::new(2), name: "ink".to_string(), cents: 800 })?;
+//!
+//! let cart = [
+//! 	CartLine { product: Ref(Id::new(1)), quantity: 4 },
+//! 	CartLine { product: Ref(Id::new(2)), quantity: 1 }
+//! ];
+//! db.register_collection("cart", &cart)?;
+//! let total: u64 = db.query_one(
+//! 	"SELECT sum(p.cents * c.quantity) FROM cart AS c JOIN product AS p ON p.id = c.product"
+//! )?;
+//! assert_eq!(total, 1400);
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::sync::Arc;
+
+use rusqlite::{
+	ffi,
+	Error,
+	Result as SqlResult
+};
+use rusqlite::types::Value;
+use rusqlite::vtab::{
+	eponymous_only_module,
+	Context,
+	IndexInfo,
+	VTab,
+	VTabConnection,
+	VTabCursor,
+	Values
+};
+
+use crate::{
+	Binder,
+	Database,
+	Entry,
+	Schema,
+	Table
+};
+
+/// Snapshot of the entries of a collection, converted to SQL values
+struct Collection {
+	columns: &'static [&'static str],
+	rows: Vec<Vec<Value>>
+}
+
+impl<S: Schema> Database<S> {
+	/// Make the entries available to queries on this connection, as the read-only table `name`
+	///
+	/// The entries are copied, so later changes to them aren't visible to queries.
+	/// Registering a collection under the same name again replaces it for statements prepared afterwards.
+	pub fn register_collection<T>(&self, name: &str, entries: &[T]) -> SqlResult<()>
+		where T: Table + Entry
+	{
+		let placeholders: Vec<String> = (1..=T::ALL_COLUMNS.len())
+			.map(|idx| format!("?{idx}"))
+			.collect();
+		let mut stmt = self.prepare(&format!("SELECT {}", placeholders.join(", ")))?;
+		let mut rows = Vec::with_capacity(entries.len());
+		for entry in entries {
+			stmt.clear_bindings();
+			Binder::make(&mut stmt).bind(entry)?;
+			let mut values = stmt.raw_query();
+			let Some(row) = values.next()? else {continue};
+			let row = (0..T::ALL_COLUMNS.len())
+				.map(|idx| row.get::<_, Value>(idx))
+				.collect::<SqlResult<_>>()?;
+			rows.push(row);
+		}
+		let collection = Collection { columns: T::ALL_COLUMNS, rows };
+		self.create_module(name, eponymous_only_module::<CollectionTab>(), Some(Arc::new(collection)))
+	}
+}
+
+#[repr(C)]
+struct CollectionTab {
+	/// Base class, must be first
+	base: ffi::sqlite3_vtab,
+	collection: Arc<Collection>
+}
+
+// SAFETY: CollectionTab is #[repr(C)] and starts with its sqlite3_vtab base
+unsafe impl<'vtab> VTab<'vtab> for CollectionTab {
+	type Aux = Arc<Collection>;
+	type Cursor = CollectionCursor<'vtab>;
+
+	fn connect(_db: &mut VTabConnection, aux: Option<&Self::Aux>, _args: &[&[u8]])
+		-> SqlResult<(String, Self)>
+	{
+		let Some(collection) = aux else {
+			return Err(Error::ModuleError("collection without entries".to_string()));
+		};
+		let schema = format!("CREATE TABLE x({})", collection.columns.join(", "));
+		Ok((schema, Self { base: ffi::sqlite3_vtab::default(), collection: Arc::clone(collection) }))
+	}
+	fn best_index(&self, info: &mut IndexInfo) -> SqlResult<()> {
+		// there are no indexes, SQLite checks the constraints on each row itself
+		info.set_estimated_cost(self.collection.rows.len() as f64);
+		info.set_estimated_rows(self.collection.rows.len() as i64);
+		Ok(())
+	}
+	fn open(&'vtab mut self) -> SqlResult<Self::Cursor> {
+		Ok(CollectionCursor {
+			base: ffi::sqlite3_vtab_cursor::default(),
+			collection: Arc::clone(&self.collection),
+			idx: 0,
+			vtab: PhantomData
+		})
+	}
+}
+
+#[repr(C)]
+struct CollectionCursor<'vtab> {
+	/// Base class, must be first
+	base: ffi::sqlite3_vtab_cursor,
+	collection: Arc<Collection>,
+	/// Index of the current row
+	idx: usize,
+	vtab: PhantomData<&'vtab CollectionTab>
+}
+
+// SAFETY: CollectionCursor is #[repr(C)] and starts with its sqlite3_vtab_cursor base
+unsafe impl VTabCursor for CollectionCursor<'_> {
+	fn filter(&mut self, _idx_num: c_int, _idx_str: Option<&str>, _args: &Values<'_>)
+		-> SqlResult<()>
+	{
+		self.idx = 0;
+		Ok(())
+	}
+	fn next(&mut self) -> SqlResult<()> {
+		self.idx += 1;
+		Ok(())
+	}
+	fn eof(&self) -> bool {
+		self.idx >= self.collection.rows.len()
+	}
+	fn column(&self, ctx: &mut Context, i: c_int) -> SqlResult<()> {
+		ctx.set_result(&self.collection.rows[self.idx][i as usize])
+	}
+	fn rowid(&self) -> SqlResult<i64> {
+		Ok(self.idx as i64 + 1)
+	}
+}
@@ -0,0 +1,119 @@
+//! Write-ahead log: checkpoints & auto-checkpoint configuration
+//!
+//! In [WAL mode](https://sqlite.org/wal.html), changes are appended to a separate `-wal` file, so readers (on other connections) can continue while there's a writer.
+//! The changes are moved back into the database file by a [checkpoint](https://sqlite.org/wal.html#checkpointing), which SQLite runs automatically once the log has grown to a number of pages (1000 by default).
+//!
+//! Open the database with [`OpenOptions::wal`](crate::OpenOptions::wal) to use WAL mode, and with [`OpenOptions::auto_checkpoint`](crate::OpenOptions::auto_checkpoint) to change when checkpoints run automatically.
+//! [`Database::checkpoint`] runs one right away, e.g. while the application is idle, so the log doesn't keep growing while readers prevent the automatic ones from completing.
+//!
+//!```
+//! use liter::{database, Database, Table};
+//! use liter::wal::CheckpointMode;
+//!
+//! #[database]
+//! struct Db (Event);
+//!
+//! #[derive(Table)]
+//! struct Event {
+//! 	name: String
+//! }
+//!
+//! let path = std::env::temp_dir().join("liter_wal_doctest.db");
+//! # let _ = std::fs::remove_file(&path);
+//! let db = Database::<Db>::options()
+//! 	.wal()
+//! 	.auto_checkpoint(0)
+//! 	.init(&path)?;
+//! assert!(db.is_wal()?);
+//! db.insert(&Event { name: "started".to_string() })?;
+//!
+//! // a reader sees the committed changes while they are still in the log
+//! let reader = Database::<Db>::options().read_only().open(&path)?;
+//! assert_eq!(reader.get_all::<Event>()?.len(), 1);
+//!
+//! let checkpoint = db.checkpoint(CheckpointMode::Truncate)?;
+//! assert!(!checkpoint.busy);
+//! assert_eq!(checkpoint.log_frames, Some(0));
+//! # drop(reader);
+//! # drop(db);
+//! # for suffix in ["", "-wal", "-shm"] {
+//! # 	let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+//! # }
+//! # Ok::<(), rusqlite::Error>(())
+//!```
+
+use rusqlite::Result as SqlResult;
+
+use crate::{
+	Database,
+	Schema
+};
+use crate::open::JournalMode;
+
+/// How much a [checkpoint](Database::checkpoint) does, see [the SQLite documentation](https://sqlite.org/c3ref/wal_checkpoint_v2.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointMode {
+	/// Checkpoint as much as possible without waiting for readers or writers (what the automatic checkpoints do)
+	Passive,
+	/// Wait for the writer (up to the busy timeout), then checkpoint everything
+	Full,
+	/// Like [`Full`](Self::Full), but also wait for the readers, so the next writer starts at the beginning of the log again
+	Restart,
+	/// Like [`Restart`](Self::Restart), but also truncate the log file to zero bytes
+	Truncate
+}
+
+impl CheckpointMode {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			CheckpointMode::Passive => "PASSIVE",
+			CheckpointMode::Full => "FULL",
+			CheckpointMode::Restart => "RESTART",
+			CheckpointMode::Truncate => "TRUNCATE"
+		}
+	}
+}
+
+/// Result of [`Database::checkpoint`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+	/// Whether the checkpoint couldn't be completed because of other connections
+	pub busy: bool,
+	/// Number of frames (i.e. modified pages) in the log, `None` if the database isn't in WAL mode
+	pub log_frames: Option<u64>,
+	/// Number of frames of the log that were moved into the database file, `None` if the database isn't in WAL mode
+	pub checkpointed_frames: Option<u64>
+}
+
+impl<S: Schema> Database<S> {
+	/// Run a checkpoint of the write-ahead log
+	///
+	/// This does nothing if the database isn't in WAL mode.
+	pub fn checkpoint(&self, mode: CheckpointMode) -> SqlResult<Checkpoint> {
+		let sql = format!("PRAGMA main.wal_checkpoint({})", mode.as_str());
+		self.query_row(&sql, [], |row| {
+			let frames = |idx: usize| row.get::<_, i64>(idx)
+				.map(|frames| u64::try_from(frames).ok());
+			Ok(Checkpoint {
+				busy: row.get(0)?,
+				log_frames: frames(1)?,
+				checkpointed_frames: frames(2)?
+			})
+		})
+	}
+	/// Whether the database is in WAL mode
+	pub fn is_wal(&self) -> SqlResult<bool> {
+		let mode: String = self.pragma_query_value(None, "journal_mode", |row| row.get(0))?;
+		Ok(mode.eq_ignore_ascii_case(JournalMode::Wal.as_str()))
+	}
+	/// Number of pages the log can grow to before a checkpoint runs automatically, `0` if they are turned off
+	pub fn auto_checkpoint(&self) -> SqlResult<u32> {
+		self.pragma_query_value(None, "wal_autocheckpoint", |row| row.get(0))
+	}
+	/// Run a checkpoint automatically once the log has grown to this many pages, or never if `pages` is `0`
+	///
+	/// This only applies to this connection: if checkpoints are turned off here, another connection has to run them.
+	pub fn set_auto_checkpoint(&self, pages: u32) -> SqlResult<()> {
+		self.pragma_update(None, "wal_autocheckpoint", pages)
+	}
+}
@@ -0,0 +1,127 @@
+//! Watching a settings table for changes made by any connection or process
+//!
+//! [`Database::watch_settings`] opens a second connection to the database file on a background thread, which checks [`PRAGMA data_version`](https://sqlite.org/pragma.html#pragma_data_version) to notice commits made by other connections (including those in other processes).
+//! When the settings row changed, the new row is sent to the returned [`SettingsWatch`], which dereferences to a [`Receiver`].
+
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{
+	AtomicBool,
+	Ordering
+};
+use std::sync::mpsc::{
+	self,
+	Receiver
+};
+use std::thread::{
+	self,
+	JoinHandle
+};
+use std::time::Duration;
+
+use rusqlite::{
+	Connection,
+	Error,
+	OpenFlags,
+	Result as SqlResult
+};
+
+use crate::{
+	Database,
+	Entry,
+	Schema,
+	Table
+};
+use crate::meta::PartOf;
+
+impl<S: Schema> Database<S> {
+	/// Watch a single-row settings table
+	///
+	/// The current settings are sent right away, and afterwards whenever the row changes, checking every `interval`.
+	/// If the table has no rows, nothing is sent until one is inserted.
+	/// The background thread stops at its next check after the [`SettingsWatch`] was dropped (or right away with [`SettingsWatch::stop`]), or if reading the settings fails.
+	///
+	/// This returns an [`Error::InvalidPath`] for in-memory databases, since they can't be opened by another connection.
+	pub fn watch_settings<T>(&self, interval: Duration) -> SqlResult<SettingsWatch<T>>
+		where T: Table + Entry + PartOf<S> + Clone + PartialEq + Send + 'static
+	{
+		let path = match self.connection.path() {
+			Some(path) if !path.is_empty() => PathBuf::from(path),
+			_ => return Err(Error::InvalidPath(PathBuf::from(":memory:")))
+		};
+		let watcher = Connection::open_with_flags(
+			&path,
+			OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX
+		)?;
+		S::on_open(&watcher)?;
+		let mut last = read_settings::<T>(&watcher)?;
+		let mut version = data_version(&watcher)?;
+
+		let (sender, receiver) = mpsc::channel();
+		if let Some(settings) = &last {
+			let _ = sender.send(settings.clone());
+		}
+		let stop = Arc::new(AtomicBool::new(false));
+		let stopped = stop.clone();
+		let thread = thread::spawn(move || loop {
+			thread::sleep(interval);
+			if stopped.load(Ordering::Relaxed) {return}
+			let Ok(new_version) = data_version(&watcher) else {return};
+			if new_version == version {
+				continue;
+			}
+			version = new_version;
+			let Ok(settings) = read_settings::<T>(&watcher) else {return};
+			if settings == last {
+				continue;
+			}
+			if let Some(new) = &settings {
+				if sender.send(new.clone()).is_err() {return}
+			}
+			last = settings;
+		});
+		Ok(SettingsWatch { receiver, stop, thread: Some(thread) })
+	}
+}
+
+/// Settings sent by the background thread of [`Database::watch_settings`], which stops when this is dropped
+///
+/// This dereferences to the [`Receiver`] of the settings.
+#[derive(Debug)]
+pub struct SettingsWatch<T> {
+	receiver: Receiver<T>,
+	stop: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>
+}
+
+impl<T> SettingsWatch<T> {
+	/// Stop the background thread and wait for it to finish, which takes up to one interval
+	pub fn stop(mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+impl<T> Deref for SettingsWatch<T> {
+	type Target = Receiver<T>;
+	fn deref(&self) -> &Self::Target {&self.receiver}
+}
+
+impl<T> Drop for SettingsWatch<T> {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+	}
+}
+
+fn data_version(connection: &Connection) -> SqlResult<i64> {
+	connection.pragma_query_value(None, "data_version", |row| row.get(0))
+}
+
+fn read_settings<T: Entry>(connection: &Connection) -> SqlResult<Option<T>> {
+	let mut stmt = connection.prepare(T::GET_ALL)?;
+	let mut rows = stmt.query([])?;
+	rows.next()?.map(T::from_row).transpose()
+}
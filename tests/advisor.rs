@@ -0,0 +1,76 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::advisor::{
+	AdviceReason,
+	IndexAdvice
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn where_clause() -> SqlResult<()> {
+	#[database]
+	struct Db (Song);
+
+	#[derive(Table)]
+	struct Song {
+		#[key]
+		id: Id,
+		title: String,
+		year: u16,
+		plays: u32
+	}
+
+	let db = Db::create_in_memory()?;
+	let query = "SELECT * FROM song where year = 1999 AND title = ? ORDER BY plays LIMIT 3";
+	let advice = db.advise_indexes_for(&[query, query])?;
+	// columns in order of appearance, only from the WHERE clause, and only once
+	assert_eq!(advice, [IndexAdvice {
+		table: "song",
+		columns: vec!["year".to_string(), "title".to_string()],
+		reason: AdviceReason::Scan { query: query.to_string() }
+	}]);
+	assert_eq!(
+		advice[0].to_string(),
+		format!("CREATE INDEX song_year_title_index ON song ( year, title ); -- table scanned by: {query}")
+	);
+
+	db.execute_batch(&advice[0].create_index_sql())?;
+	assert!(db.advise_indexes_for(&[query])?.is_empty());
+	// an index is used for its leading columns in any order
+	assert!(db.advise_indexes_for(&["SELECT * FROM song WHERE title = ? AND year = 1"])?.is_empty());
+	Ok(())
+}
+
+#[test]
+fn no_advice() -> SqlResult<()> {
+	#[database]
+	struct Db (Song);
+
+	#[derive(Table)]
+	struct Song {
+		#[key]
+		id: Id,
+		#[unique]
+		title: String,
+		plays: u32
+	}
+
+	let db = Db::create_in_memory()?;
+	assert!(db.advise_indexes_for(&[
+		// there's nothing to index without a WHERE clause
+		"SELECT * FROM song",
+		"SELECT * FROM song ORDER BY plays",
+		// lookups by key or unique column
+		"SELECT * FROM song WHERE id = 3",
+		"SELECT * FROM song WHERE title = ?",
+		// tables that aren't part of the schema
+		"SELECT * FROM sqlite_schema WHERE name = 'song'"
+	])?.is_empty());
+	// invalid queries are errors
+	assert!(db.advise_indexes_for(&["SELECT * FROM album WHERE year = 1"]).is_err());
+	Ok(())
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "async")]
+
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{
+	Context,
+	Poll,
+	Wake
+};
+use std::thread::{
+	self,
+	Thread
+};
+use std::time::{
+	Duration,
+	Instant
+};
+
+use liter::{
+	AsyncDatabase,
+	Database,
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+struct Unpark(Thread);
+impl Wake for Unpark {
+	fn wake(self: Arc<Self>) {
+		self.0.unpark();
+	}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+	let waker = Arc::new(Unpark(thread::current())).into();
+	let mut cx = Context::from_waker(&waker);
+	let mut future = pin!(future);
+	loop {
+		match future.as_mut().poll(&mut cx) {
+			Poll::Ready(output) => return output,
+			Poll::Pending => thread::park()
+		}
+	}
+}
+
+#[database]
+struct Db (Note);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Note {
+	#[key]
+	id: Id,
+	text: String
+}
+
+fn note(text: &str) -> Note {
+	Note { id: Id::NULL, text: text.to_string() }
+}
+
+#[test]
+fn create_and_get() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+
+	let first = block_on(db.create(note("first")))?;
+	assert_eq!(first.id, Id::new(1));
+	let second = block_on(db.create(note("second")))?;
+	assert_eq!(second.id, Id::new(2));
+
+	assert_eq!(block_on(db.get::<Note>(Id::new(2)))?, Some(second.clone()));
+	assert_eq!(block_on(db.get::<Note>(Id::new(3)))?, None);
+	assert_eq!(block_on(db.get_all::<Note>())?, [first, second]);
+
+	// an entry that already has an id isn't created
+	assert!(block_on(db.create(Note { id: Id::new(5), text: "id".to_string() })).is_err());
+	Ok(())
+}
+
+#[test]
+fn call() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+	let count = db.call(|db| {
+		let tx = db.transaction()?;
+		tx.insert(&note("a"))?;
+		tx.insert(&note("b"))?;
+		tx.commit()?;
+		db.query_one::<i64>("SELECT count(*) FROM note")
+	});
+	assert_eq!(block_on(count)?, 2);
+
+	// jobs run in the order they were sent, even if awaited in another order
+	let insert = db.insert(note("c"));
+	let count = db.call(|db| db.query_one::<i64>("SELECT count(*) FROM note"));
+	assert_eq!(block_on(count)?, 3);
+	assert_eq!(block_on(insert)?, 1);
+
+	let error = db.call(|db| db.query_one::<i64>("SELECT * FROM missing"));
+	assert!(block_on(error).is_err());
+	Ok(())
+}
+
+#[test]
+fn jobs_run_after_drop() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+	let slow = db.call(|db| {
+		thread::sleep(Duration::from_millis(50));
+		db.insert(&note("slow"))
+	});
+	let notes = db.get_all::<Note>();
+	drop(db);
+
+	assert_eq!(block_on(slow)?, 1);
+	assert_eq!(block_on(notes)?.len(), 1);
+	Ok(())
+}
+
+#[test]
+fn closes_after_drop() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_background_close.db");
+	let wal = PathBuf::from(format!("{}-wal", path.display()));
+	let _ = fs::remove_file(&path);
+	let _ = fs::remove_file(&wal);
+
+	let db = AsyncDatabase::new(Database::<Db>::options().wal().init(&path)?);
+	block_on(db.insert(note("logged")))?;
+	assert!(wal.exists());
+
+	// closing the last connection checkpoints the log and removes it
+	drop(db);
+	let start = Instant::now();
+	while wal.exists() {
+		assert!(start.elapsed() < Duration::from_secs(5), "database wasn't closed");
+		thread::sleep(Duration::from_millis(10));
+	}
+
+	let reopened = Db::open(&path)?;
+	assert_eq!(reopened.get_all::<Note>()?.len(), 1);
+	drop(reopened);
+	let _ = fs::remove_file(&path);
+	Ok(())
+}
+
+#[test]
+fn panicking_job_stops_thread() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+	let panicked = db.call(|_db| -> SqlResult<()> {
+		panic!("job failed")
+	});
+	let after = db.get_all::<Note>();
+
+	assert!(block_on(panicked).is_err());
+	assert!(block_on(after).is_err());
+	assert!(block_on(db.insert(note("never"))).is_err());
+	Ok(())
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::Path;
+
+use liter::{
+	Database,
+	Table,
+	database
+};
+use liter::backup::PAGES_PER_STEP;
+use rusqlite::Result as SqlResult;
+
+#[database]
+struct Db (Entry);
+
+#[derive(Table, Debug, PartialEq)]
+struct Entry {
+	text: String
+}
+
+fn remove(path: &Path) {
+	for suffix in ["", "-journal"] {
+		let _ = fs::remove_file(format!("{}{suffix}", path.display()));
+	}
+}
+
+fn entry(text: &str) -> Entry {
+	Entry { text: text.to_string() }
+}
+
+/// Fill the database with enough rows to take several backup steps to copy
+fn fill(db: &Database<Db>) -> SqlResult<()> {
+	db.execute(
+		"WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 5000)
+		INSERT INTO entry SELECT printf('%0500d', i) FROM n",
+		&()
+	)?;
+	let pages: i32 = db.query_one("PRAGMA page_count")?;
+	assert!(pages > 2 * PAGES_PER_STEP, "only {pages} pages");
+	Ok(())
+}
+
+#[test]
+fn clone_empty() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let copy = db.clone_to_memory()?;
+	assert_eq!(copy.get_all::<Entry>()?, []);
+
+	// the copy has the schema
+	copy.insert(&entry("new"))?;
+	assert_eq!(copy.get_all::<Entry>()?, [entry("new")]);
+	assert_eq!(db.get_all::<Entry>()?, []);
+	Ok(())
+}
+
+#[test]
+fn clone_in_several_steps() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	fill(&db)?;
+
+	let copy = db.clone_to_memory()?;
+	let count: i64 = copy.query_one("SELECT count(*) FROM entry")?;
+	assert_eq!(count, 5000);
+	let last: String = copy.query_one("SELECT max(text) FROM entry")?;
+	assert_eq!(last, format!("{:0500}", 5000));
+
+	let check: String = copy.query_one("PRAGMA integrity_check")?;
+	assert_eq!(check, "ok");
+	Ok(())
+}
+
+#[test]
+fn clone_is_independent() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	db.insert(&entry("fixture"))?;
+
+	let copy = db.clone_to_memory()?;
+	db.insert(&entry("original"))?;
+	copy.execute("DELETE FROM entry", &())?;
+
+	assert_eq!(db.get_all::<Entry>()?, [entry("fixture"), entry("original")]);
+	assert_eq!(copy.get_all::<Entry>()?, []);
+	Ok(())
+}
+
+#[test]
+fn restore_over_live_database() -> SqlResult<()> {
+	let live_path = std::env::temp_dir().join("liter_backup_live.db");
+	let backup_path = std::env::temp_dir().join("liter_backup_snapshot.db");
+	remove(&live_path);
+	remove(&backup_path);
+
+	let mut db = Db::init(&live_path)?;
+	db.insert(&entry("snapshot"))?;
+	db.backup_to(&backup_path, |_progress| {})?;
+	// the restored database is much smaller than this one
+	fill(&db)?;
+
+	// another connection to the same file, which isn't in a transaction
+	let other = Db::open(&live_path)?;
+	assert_eq!(other.query_one::<i64>("SELECT count(*) FROM entry")?, 5001);
+
+	db.restore_from(&backup_path)?;
+	assert_eq!(db.get_all::<Entry>()?, [entry("snapshot")]);
+	assert_eq!(other.get_all::<Entry>()?, [entry("snapshot")]);
+	let check: String = db.query_one("PRAGMA integrity_check")?;
+	assert_eq!(check, "ok");
+
+	// both connections keep working with the restored contents
+	other.insert(&entry("after"))?;
+	assert_eq!(db.get_all::<Entry>()?, [entry("snapshot"), entry("after")]);
+
+	drop((db, other));
+	remove(&live_path);
+	remove(&backup_path);
+	Ok(())
+}
+
+#[test]
+fn restore_from_missing_file() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_backup_missing.db");
+	remove(&path);
+	let mut db = Db::create_in_memory()?;
+	db.insert(&entry("kept"))?;
+
+	assert!(db.restore_from(&path).is_err());
+	assert!(!path.exists());
+	assert_eq!(db.get_all::<Entry>()?, [entry("kept")]);
+	Ok(())
+}
@@ -0,0 +1,74 @@
+use std::fs;
+
+use liter::{
+	Database,
+	Id,
+	Ref,
+	Schema,
+	Table,
+	database
+};
+use liter::build::generate;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn replaces_file() -> SqlResult<()> {
+	#[database]
+	struct Db (Word);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Word {
+		#[key]
+		word: String
+	}
+
+	let path = std::env::temp_dir().join("liter_build_replaces.db");
+	fs::write(&path, b"stale").unwrap();
+	generate(Db::CREATE, |conn| {
+		conn.execute("INSERT INTO word VALUES ('liter')", [])?;
+		Ok(())
+	}, &path)?;
+	let bytes: &'static [u8] = fs::read(&path).unwrap().leak();
+	let db = Database::<Db>::open_from_bytes(bytes)?;
+	assert_eq!(db.get_all::<Word>()?, [Word { word: "liter".to_string() }]);
+	// never in WAL mode
+	let mode: String = db.pragma_query_value(None, "journal_mode", |row| row.get(0))?;
+	assert_ne!(mode, "wal");
+	fs::remove_file(&path).unwrap();
+	Ok(())
+}
+
+#[test]
+fn failures_keep_file() -> SqlResult<()> {
+	#[database]
+	struct Db (Artist, Song);
+
+	#[derive(Table)]
+	struct Artist {
+		#[key]
+		id: Id
+	}
+	#[derive(Table)]
+	struct Song {
+		#[key]
+		id: Id,
+		artist: Ref<Artist>
+	}
+
+	let path = std::env::temp_dir().join("liter_build_failures.db");
+	fs::write(&path, b"previous").unwrap();
+	// foreign keys are enforced while seeding
+	assert!(generate(Db::CREATE, |conn| {
+		conn.execute("INSERT INTO song VALUES (1, 7)", [])?;
+		Ok(())
+	}, &path).is_err());
+	assert!(generate("CREATE TABLE (", |_conn| Ok(()), &path).is_err());
+	assert_eq!(fs::read(&path).unwrap(), b"previous");
+
+	// a missing directory
+	let nested = std::env::temp_dir().join("liter_build_missing").join("out.db");
+	assert!(generate(Db::CREATE, |_conn| Ok(()), &nested).is_err());
+	fs::remove_file(&path).unwrap();
+	Ok(())
+}
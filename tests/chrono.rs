@@ -0,0 +1,141 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{
+	DateTime,
+	NaiveDate,
+	NaiveDateTime,
+	NaiveTime,
+	TimeZone,
+	Utc
+};
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::types::chrono::UnixTime;
+use rusqlite::Result as SqlResult;
+
+mod common;
+
+
+#[database]
+struct Db (Event);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Event {
+	#[key]
+	id: Id,
+	at: DateTime<Utc>,
+	local: NaiveDateTime,
+	day: NaiveDate,
+	starts: NaiveTime,
+	unix: UnixTime,
+	ended: Option<DateTime<Utc>>
+}
+
+fn utc(year: i32, month: u32, day: u32, hms: (u32, u32, u32), milli: u32) -> DateTime<Utc> {
+	let naive = NaiveDate::from_ymd_opt(year, month, day).unwrap()
+		.and_hms_milli_opt(hms.0, hms.1, hms.2, milli).unwrap();
+	Utc.from_utc_datetime(&naive)
+}
+
+fn event(id: i64, at: DateTime<Utc>) -> Event {
+	Event {
+		id: Id::from_i64(id),
+		at,
+		local: at.naive_utc(),
+		day: at.date_naive(),
+		starts: at.time(),
+		unix: UnixTime(at),
+		ended: None
+	}
+}
+
+#[test]
+fn round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let leap = event(1, utc(2024, 2, 29, (23, 59, 58), 0));
+	let old = Event {
+		ended: Some(utc(1969, 12, 31, (0, 0, 1), 0)),
+		..event(2, utc(1969, 7, 20, (20, 17, 40), 0))
+	};
+	db.insert(&leap)?;
+	db.insert(&old)?;
+	assert_eq!(db.get_all::<Event>()?, [leap, old]);
+
+	let text: (String, String, String, String, i64) = db.query_one(
+		"SELECT at, local, day, starts, unix FROM event WHERE id = 1"
+	)?;
+	assert_eq!(text, (
+		"2024-02-29 23:59:58+00:00".to_string(),
+		"2024-02-29 23:59:58".to_string(),
+		"2024-02-29".to_string(),
+		"23:59:58".to_string(),
+		1709251198
+	));
+	Ok(())
+}
+
+#[test]
+fn unix_time_truncates() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let at = utc(2024, 1, 1, (12, 0, 0), 750);
+	db.insert(&event(1, at))?;
+
+	let stored = db.get::<Event>(Id::from_i64(1))?.unwrap();
+	assert_eq!(stored.at, at);
+	assert_eq!(stored.starts, at.time());
+	assert_eq!(stored.unix, UnixTime(utc(2024, 1, 1, (12, 0, 0), 0)));
+	Ok(())
+}
+
+#[test]
+fn check() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	db.insert(&event(1, utc(2024, 1, 1, (12, 0, 0), 0)))?;
+	common::assert_checked(&db, "event", "ended", &[
+		"at = 'tomorrow'",
+		"at = '12:00:00'",
+		"local = '1/1/2024 12:00'",
+		"day = ''",
+		"starts = '2024-01-01'",
+		"starts = 'noon'",
+		"unix = '2024-01-01'",
+		"ended = 'never'"
+	])?;
+	assert_eq!(db.get_all::<Event>()?, [event(1, utc(2024, 1, 1, (12, 0, 0), 0))]);
+	Ok(())
+}
+
+#[test]
+fn ordering() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let times = [
+		utc(1969, 12, 31, (23, 59, 59), 0),
+		utc(2024, 1, 1, (9, 0, 0), 0),
+		utc(2024, 1, 1, (10, 0, 0), 0),
+		utc(2024, 1, 1, (10, 0, 0), 123),
+		utc(2024, 1, 1, (10, 0, 0), 500),
+		utc(2024, 1, 2, (0, 0, 0), 0),
+		utc(2038, 1, 19, (3, 14, 8), 0)
+	];
+	// inserted in another order than they sort
+	for (id, at) in times.iter().enumerate().rev() {
+		db.insert(&event(id as i64, *at))?;
+	}
+
+	common::assert_sorted(&db, "event", &["at", "local", "unix"], times.len())?;
+	let days: Vec<NaiveDate> = db.query_all("SELECT DISTINCT day FROM event ORDER BY day")?;
+	assert_eq!(days, [
+		NaiveDate::from_ymd_opt(1969, 12, 31).unwrap(),
+		NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+		NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+		NaiveDate::from_ymd_opt(2038, 1, 19).unwrap()
+	]);
+	let starts: Vec<NaiveTime> = db.query_all("SELECT starts FROM event ORDER BY starts")?;
+	let mut expected_starts: Vec<NaiveTime> = times.iter().map(|at| at.time()).collect();
+	expected_starts.sort();
+	assert_eq!(starts, expected_starts);
+	Ok(())
+}
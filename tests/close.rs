@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use liter::{
+	Database,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+fn remove(path: &Path) {
+	for suffix in ["", "-wal", "-shm"] {
+		let _ = fs::remove_file(format!("{}{suffix}", path.display()));
+	}
+}
+
+#[test]
+fn in_memory() -> SqlResult<()> {
+	#[database]
+	struct Db (Event);
+
+	#[derive(Table)]
+	struct Event {
+		name: String
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Event { name: "closed".to_string() })?;
+	db.close().map_err(|(_db, e)| e)?;
+	Ok(())
+}
+
+#[test]
+fn checkpoints_wal() -> SqlResult<()> {
+	#[database]
+	struct Db (Event);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Event {
+		name: String
+	}
+
+	let path = std::env::temp_dir().join("liter_close_wal.db");
+	let wal = format!("{}-wal", path.display());
+	remove(&path);
+	let db = Database::<Db>::options()
+		.wal()
+		.auto_checkpoint(0)
+		.init(&path)?;
+	db.insert(&Event { name: "logged".to_string() })?;
+	assert!(fs::metadata(&wal).unwrap().len() > 0);
+
+	// another connection keeps the log file around, but it is emptied
+	let other = Db::open(&path)?;
+	db.close().map_err(|(_db, e)| e)?;
+	assert_eq!(fs::metadata(&wal).unwrap().len(), 0);
+	assert_eq!(other.get_all::<Event>()?, [Event { name: "logged".to_string() }]);
+
+	// the last connection removes it
+	other.close().map_err(|(_db, e)| e)?;
+	assert!(!Path::new(&wal).exists());
+	assert_eq!(Db::open(&path)?.count::<Event>()?, 1);
+	remove(&path);
+	Ok(())
+}
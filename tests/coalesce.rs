@@ -0,0 +1,117 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{
+	Context,
+	Poll,
+	Wake
+};
+use std::thread::{
+	self,
+	Thread
+};
+
+use liter::{
+	AsyncDatabase,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+struct Unpark(Thread);
+impl Wake for Unpark {
+	fn wake(self: Arc<Self>) {
+		self.0.unpark();
+	}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+	let waker = Arc::new(Unpark(thread::current())).into();
+	let mut cx = Context::from_waker(&waker);
+	let mut future = pin!(future);
+	loop {
+		match future.as_mut().poll(&mut cx) {
+			Poll::Ready(output) => return output,
+			Poll::Pending => thread::park()
+		}
+	}
+}
+
+#[database]
+struct Db (Counter);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Counter {
+	#[key]
+	name: String,
+	value: u64
+}
+
+fn counter(name: &str, value: u64) -> Counter {
+	Counter { name: name.to_string(), value }
+}
+
+#[test]
+fn upsert_storm() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+	// keep the background thread busy, so the upserts pile up
+	let busy = db.call(|_db| {
+		thread::sleep(std::time::Duration::from_millis(100));
+		Ok(())
+	});
+
+	let upserts: Vec<_> = (1..=100)
+		.map(|value| db.upsert_coalesced(counter(["a", "b"][value as usize % 2], value)))
+		.collect();
+	block_on(busy)?;
+	for upsert in upserts {
+		assert_eq!(block_on(upsert)?, 1);
+	}
+
+	let mut counters = block_on(db.get_all::<Counter>())?;
+	counters.sort_by(|a, b| a.name.cmp(&b.name));
+	assert_eq!(counters, [counter("a", 100), counter("b", 99)]);
+	Ok(())
+}
+
+#[test]
+fn ordered_with_other_writes() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+	let first = db.upsert_coalesced(counter("a", 1));
+	let delete = db.delete::<Counter>("a".to_string());
+	let second = db.upsert_coalesced(counter("a", 2));
+
+	assert_eq!(block_on(first)?, 1);
+	assert!(block_on(delete)?);
+	assert_eq!(block_on(second)?, 1);
+	assert_eq!(block_on(db.get_all::<Counter>())?, [counter("a", 2)]);
+	Ok(())
+}
+
+#[test]
+fn failure_rolls_back_batch() -> SqlResult<()> {
+	let db = AsyncDatabase::new(Db::create_in_memory()?);
+	let busy = db.call(|_db| {
+		thread::sleep(std::time::Duration::from_millis(100));
+		Ok(())
+	});
+
+	let ok = db.upsert_coalesced(counter("a", 1));
+	// too large for an INTEGER
+	let failing = db.upsert_coalesced(counter("b", u64::MAX));
+	let after = db.upsert_coalesced(counter("c", 3));
+	block_on(busy)?;
+
+	assert!(block_on(failing).is_err());
+	assert!(block_on(ok).is_err());
+	assert!(block_on(after).is_err());
+	assert_eq!(block_on(db.get_all::<Counter>())?, []);
+
+	// the next batch is written as usual
+	assert_eq!(block_on(db.upsert_coalesced(counter("a", 2)))?, 1);
+	assert_eq!(block_on(db.get_all::<Counter>())?, [counter("a", 2)]);
+	Ok(())
+}
@@ -0,0 +1,93 @@
+use std::cmp::Ordering;
+
+use liter::{
+	Id,
+	Table,
+	Value,
+	database
+};
+use liter::collation::{
+	Collated,
+	Collation
+};
+use liter::value::{
+	ColumnClauses,
+	NestedValueDef,
+	ValueDef
+};
+use rusqlite::Result as SqlResult;
+
+/// SQLite's built-in case-insensitive collation, which doesn't have to be registered
+struct NoCase;
+impl Collation for NoCase {
+	const NAME: &'static str = "NOCASE";
+	fn compare(a: &str, b: &str) -> Ordering {
+		a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+	}
+}
+
+#[database]
+struct Db (User);
+
+#[derive(Table, Debug, PartialEq)]
+struct User {
+	#[key]
+	id: Id,
+	name: Collated<NoCase>,
+	nickname: Option<Collated<NoCase>>,
+	email: String
+}
+
+fn user(id: i64, name: &str, email: &str) -> User {
+	User {
+		id: Id::from_i64(id),
+		name: Collated::new(name.to_string()),
+		nickname: None,
+		email: email.to_string()
+	}
+}
+
+fn clauses(def: &ValueDef) -> ColumnClauses {
+	match def.inner {
+		NestedValueDef::Column(_) => ColumnClauses::NONE,
+		NestedValueDef::ColumnWith(_, clauses) => clauses,
+		_ => panic!("not a single column: {def:?}")
+	}
+}
+
+#[test]
+fn collate_clause() {
+	let sql = User::CREATE_TABLE;
+	assert!(sql.contains("name TEXT NOT NULL COLLATE NOCASE"), "{sql}");
+	assert!(sql.contains("nickname TEXT COLLATE NOCASE"), "{sql}");
+	assert!(sql.contains("email TEXT NOT NULL"), "{sql}");
+	assert_eq!(sql.matches("COLLATE").count(), 2, "{sql}");
+
+	// columns without a collation don't get any clauses
+	assert!(matches!(String::DEFINITION.inner, NestedValueDef::Column(_)));
+	assert_eq!(clauses(&Collated::<NoCase>::DEFINITION).collation, Some("NOCASE"));
+}
+
+#[test]
+fn value_def_collate() {
+	const RTRIM: ValueDef = <String as Value>::DEFINITION.collate("RTRIM");
+	assert_eq!(clauses(&RTRIM).collation, Some("RTRIM"));
+
+	// overrides the Column::COLLATION
+	const BINARY: ValueDef = <Collated<NoCase> as Value>::DEFINITION.collate("BINARY");
+	assert_eq!(clauses(&BINARY).collation, Some("BINARY"));
+}
+
+#[test]
+fn compares_with_collation() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	db.insert(&user(1, "Alice", "alice@example.com"))?;
+	db.insert(&user(2, "bob", "bob@example.com"))?;
+
+	assert_eq!(db.count_where::<User, _>("name = ?", &"ALICE".to_string())?, 1);
+	assert_eq!(db.count_where::<User, _>("email = ?", &"ALICE@EXAMPLE.COM".to_string())?, 0);
+
+	let sorted: Vec<String> = db.query("SELECT name FROM user ORDER BY name", &())?;
+	assert_eq!(sorted, ["Alice", "bob"]);
+	Ok(())
+}
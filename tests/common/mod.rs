@@ -0,0 +1,32 @@
+//! Checks shared by the tests of the value types
+#![allow(dead_code)]
+
+use liter::{
+	Database,
+	Schema
+};
+use rusqlite::Result as SqlResult;
+
+
+/// Each of the `SET` clauses has to violate a constraint of the table, while setting the `nullable` column to `NULL` doesn't
+pub fn assert_checked<S: Schema>(db: &Database<S>, table: &str, nullable: &str, invalid: &[&str])
+	-> SqlResult<()>
+{
+	for update in invalid {
+		assert!(db.execute(&format!("UPDATE {table} SET {update}"), &()).is_err(), "{update}");
+	}
+	db.execute(&format!("UPDATE {table} SET {nullable} = NULL"), &())?;
+	Ok(())
+}
+
+/// Ordering the table by each of the columns has to give the ids `0..count` in order
+pub fn assert_sorted<S: Schema>(db: &Database<S>, table: &str, columns: &[&str], count: usize)
+	-> SqlResult<()>
+{
+	let expected: Vec<i64> = (0..count as i64).collect();
+	for column in columns {
+		let sorted: Vec<i64> = db.query_all(&format!("SELECT id FROM {table} ORDER BY {column}, id"))?;
+		assert_eq!(sorted, expected, "{column}");
+	}
+	Ok(())
+}
@@ -0,0 +1,62 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::compat::OrDefault;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn missing_column() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Item {
+		#[key]
+		id: Id,
+		name: String,
+		stock: OrDefault<u32>,
+		note: OrDefault<Option<String>>
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Item {
+		id: Id::new(1),
+		name: "chair".to_string(),
+		stock: OrDefault(4),
+		note: OrDefault(Some("wobbly".to_string()))
+	})?;
+	// the column exists, so it's fetched as usual
+	assert_eq!(db.get_by_name::<Item>(&Id::new(1))?.unwrap().stock, OrDefault(4));
+
+	db.execute_batch("ALTER TABLE item DROP COLUMN stock; ALTER TABLE item DROP COLUMN note")?;
+	db.insert_by_name(&Item {
+		id: Id::new(2),
+		name: "table".to_string(),
+		stock: OrDefault(1),
+		note: OrDefault(None)
+	})?;
+	assert_eq!(db.get_all_by_name::<Item>()?, [
+		Item { id: Id::new(1), name: "chair".to_string(), stock: OrDefault(0), note: OrDefault(None) },
+		Item { id: Id::new(2), name: "table".to_string(), stock: OrDefault(0), note: OrDefault(None) }
+	]);
+	assert_eq!(db.get_by_name::<Item>(&Id::new(3))?, None);
+	// the statements naming every column fail
+	assert!(db.get_all::<Item>().is_err());
+	Ok(())
+}
+
+#[test]
+fn column_definition() {
+	#[derive(Table)]
+	struct Item {
+		stock: OrDefault<u32>,
+		note: OrDefault<Option<String>>
+	}
+
+	let sql = Item::CREATE_TABLE;
+	assert!(sql.contains("stock INTEGER NOT NULL"), "{sql}");
+	assert!(!sql.contains("note TEXT NOT NULL"), "{sql}");
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "crashsim")]
+
+use std::cell::Cell;
+use std::fs;
+use std::path::PathBuf;
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::crashsim::CrashSim;
+use rusqlite::Result as SqlResult;
+
+
+#[derive(Table)]
+struct Account {
+	#[key]
+	id: Id,
+	balance: i64
+}
+
+/// Fixture database with two accounts holding 100 in total
+fn fixture(name: &str) -> SqlResult<PathBuf> {
+	#[database]
+	struct Db (Account);
+
+	let path = std::env::temp_dir().join(name);
+	let _ = fs::remove_file(&path);
+	let db = Db::init(&path)?;
+	db.insert(&Account { id: Id::from_i64(1), balance: 100 })?;
+	db.insert(&Account { id: Id::from_i64(2), balance: 0 })?;
+	Ok(path)
+}
+
+#[test]
+fn broken_invariant() -> SqlResult<()> {
+	#[database]
+	struct Db (Account);
+
+	let path = fixture("liter_crashsim_broken.db")?;
+	let failure = CrashSim::<Db>::new(&path).run(
+		// without a transaction, money is lost at the second point
+		|db, crash| {
+			crash.point()?;
+			db.execute("UPDATE account SET balance = balance - 10 WHERE id = 1", &())?;
+			crash.point()?;
+			db.execute("UPDATE account SET balance = balance + 10 WHERE id = 2", &())?;
+			crash.point()
+		},
+		|db| {
+			let total: i64 = db.query_one("SELECT sum(balance) FROM account")?;
+			match total {
+				100 => Ok(()),
+				_ => Err(rusqlite::Error::InvalidQuery)
+			}
+		}
+	).unwrap_err();
+	assert_eq!(failure.point, Some(1));
+	assert!(matches!(failure.error, rusqlite::Error::InvalidQuery));
+	assert!(failure.to_string().starts_with("after crash at point 1: "));
+	fs::remove_file(&path).unwrap();
+	Ok(())
+}
+
+#[test]
+fn workload_errors() -> SqlResult<()> {
+	#[database]
+	struct Db (Account);
+
+	let path = fixture("liter_crashsim_errors.db")?;
+	let sim = CrashSim::<Db>::new(&path);
+	let verify = |_db: &_| Ok(());
+
+	// no crash points at all
+	assert_eq!(sim.run(|_db, _crash| Ok(()), verify).unwrap(), 0);
+
+	let failure = sim.run(
+		|db, _crash| db.execute("UPDATE missing SET x = 1", &()).map(|_| ()),
+		verify
+	).unwrap_err();
+	assert_eq!(failure.point, None);
+	assert!(failure.to_string().starts_with("without crashing: "));
+
+	// a workload that takes a different path on each run
+	let runs = Cell::new(0);
+	let failure = sim.run(
+		|_db, crash| {
+			runs.set(runs.get() + 1);
+			if runs.get() == 1 {
+				crash.point()?;
+			}
+			Ok(())
+		},
+		verify
+	).unwrap_err();
+	assert_eq!(failure.point, Some(0));
+
+	let missing = CrashSim::<Db>::new(&std::env::temp_dir().join("liter_crashsim_missing.db"));
+	assert_eq!(missing.run(|_db, _crash| Ok(()), verify).unwrap_err().point, None);
+	fs::remove_file(&path).unwrap();
+	Ok(())
+}
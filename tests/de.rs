@@ -0,0 +1,108 @@
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use liter::{
+	Table,
+	database
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[test]
+fn structs() -> SqlResult<()> {
+	#[database]
+	struct Db (Sale);
+
+	#[derive(Table)]
+	struct Sale {
+		product: String,
+		amount: u32,
+		note: Option<String>
+	}
+
+	#[derive(Deserialize, Debug, PartialEq)]
+	enum Kind {
+		Tea,
+		Coffee
+	}
+
+	#[derive(Deserialize, Debug, PartialEq)]
+	struct Row {
+		kind: Kind,
+		amount: u32,
+		note: Option<String>,
+		large: bool
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Sale { product: "Tea".to_string(), amount: 3, note: None })?;
+	db.insert(&Sale { product: "Coffee".to_string(), amount: 9, note: Some("x".to_string()) })?;
+
+	// columns are matched by name, not position, and unknown ones are ignored
+	let rows: Vec<Row> = db.query_as(
+		"SELECT note, amount > 5 AS large, 1 AS extra, amount, product AS kind \
+		FROM sale WHERE amount > ? ORDER BY amount",
+		&0
+	)?;
+	assert_eq!(rows, [
+		Row { kind: Kind::Tea, amount: 3, note: None, large: false },
+		Row { kind: Kind::Coffee, amount: 9, note: Some("x".to_string()), large: true }
+	]);
+	Ok(())
+}
+
+#[test]
+fn tuples() -> SqlResult<()> {
+	#[database]
+	struct Db (Sale);
+
+	#[derive(Table)]
+	struct Sale {
+		product: String,
+		amount: u32
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Sale { product: "tea".to_string(), amount: 3 })?;
+	let rows: Vec<(u32, String)> = db.query_as("SELECT amount, product FROM sale", &())?;
+	assert_eq!(rows, [(3, "tea".to_string())]);
+	let rows: Vec<Vec<f64>> = db.query_as("SELECT amount, 0.5 FROM sale", &())?;
+	assert_eq!(rows, [vec![3.0, 0.5]]);
+	let none: Vec<(u32, )> = db.query_as("SELECT amount FROM sale WHERE amount > 5", &())?;
+	assert!(none.is_empty());
+	Ok(())
+}
+
+#[test]
+fn errors() -> SqlResult<()> {
+	#[database]
+	struct Db (Sale);
+
+	#[derive(Table)]
+	struct Sale {
+		product: String,
+		amount: u32
+	}
+
+	#[derive(Deserialize, Debug)]
+	#[allow(dead_code)]
+	struct Total {
+		product: String,
+		total: u32
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Sale { product: "tea".to_string(), amount: 3 })?;
+	// a missing column
+	assert!(matches!(
+		db.query_as::<Total, _>("SELECT product FROM sale", &()),
+		Err(Error::FromSqlConversionFailure(..))
+	));
+	// a value of the wrong type
+	assert!(db.query_as::<Total, _>("SELECT product, product AS total FROM sale", &()).is_err());
+	assert!(db.query_as::<(u32, ), _>("SELECT -1", &()).is_err());
+	Ok(())
+}
@@ -0,0 +1,112 @@
+use liter::{
+	Database,
+	Id,
+	Ref,
+	Table,
+	database
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[database]
+struct Db (Person, Team, Loan);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Person {
+	#[key]
+	id: Id,
+	name: String
+}
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Team {
+	#[key]
+	name: String,
+	lead: Option<Ref<Person>>
+}
+
+/// References `Person` twice, so it can't be a target
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Loan {
+	#[key]
+	id: Id,
+	lender: Ref<Person>,
+	borrower: Ref<Person>,
+	team: Ref<Team>
+}
+
+fn setup() -> SqlResult<Database<Db>> {
+	let db = Db::create_in_memory()?;
+	db.insert(&person(1, "Ada"))?;
+	db.insert(&person(2, "Grace"))?;
+	db.insert(&team("compilers", Some(1)))?;
+	db.insert(&team("unstaffed", None))?;
+	db.insert(&Loan {
+		id: Id::new(1),
+		lender: Ref(Id::new(1)),
+		borrower: Ref(Id::new(2)),
+		team: Ref("compilers".to_string())
+	})?;
+	Ok(db)
+}
+
+fn person(id: i64, name: &str) -> Person {
+	Person { id: Id::new(id), name: name.to_string() }
+}
+
+fn team(name: &str, lead: Option<i64>) -> Team {
+	Team { name: name.to_string(), lead: lead.map(|lead| Ref(Id::new(lead))) }
+}
+
+#[test]
+fn missing_entry() -> SqlResult<()> {
+	let db = setup()?;
+	assert_eq!(db.get_with::<Team, Person>(&"missing".to_string())?, None);
+	assert_eq!(db.get_with::<Loan, Team>(&Id::new(2))?, None);
+	Ok(())
+}
+
+#[test]
+fn null_and_missing_references() -> SqlResult<()> {
+	let db = setup()?;
+	assert_eq!(
+		db.get_with::<Team, Person>(&"compilers".to_string())?,
+		Some((team("compilers", Some(1)), Some(person(1, "Ada"))))
+	);
+	assert_eq!(
+		db.get_with::<Team, Person>(&"unstaffed".to_string())?,
+		Some((team("unstaffed", None), None))
+	);
+
+	// a reference to a row that doesn't exist is fetched like a NULL one
+	db.execute("PRAGMA foreign_keys = OFF", &())?;
+	db.insert(&team("orphaned", Some(99)))?;
+	assert_eq!(
+		db.get_with::<Team, Person>(&"orphaned".to_string())?,
+		Some((team("orphaned", Some(99)), None))
+	);
+	Ok(())
+}
+
+#[test]
+fn ambiguous_targets() -> SqlResult<()> {
+	let db = setup()?;
+	// Loan references Person twice, Team doesn't reference Loan at all
+	assert!(matches!(db.get_with::<Loan, Person>(&Id::new(1)), Err(Error::InvalidQuery)));
+	assert!(matches!(
+		db.get_with::<Loan, (Team, Person)>(&Id::new(1)),
+		Err(Error::InvalidQuery)
+	));
+	assert!(matches!(
+		db.get_with::<Team, Loan>(&"compilers".to_string()),
+		Err(Error::InvalidQuery)
+	));
+
+	// the single reference to Team is fine
+	let (_loan, team) = db.get_with::<Loan, Team>(&Id::new(1))?.unwrap();
+	assert_eq!(team.unwrap().name, "compilers");
+	Ok(())
+}
@@ -0,0 +1,57 @@
+use liter::{
+	Id,
+	Table,
+	attributes,
+	database
+};
+use liter::types::any::AnyValue;
+use rusqlite::Result as SqlResult;
+use rusqlite::types::Value;
+
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Contact {
+	#[key]
+	id: Id,
+	name: String
+}
+attributes!(struct ContactField(Contact));
+
+#[database]
+struct Db (Contact, ContactField);
+
+#[test]
+fn custom_fields() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let mut ada = Contact { id: Id::NULL, name: "Ada".to_string() };
+	db.create(&mut ada)?;
+	let mut alan = Contact { id: Id::NULL, name: "Alan".to_string() };
+	db.create(&mut alan)?;
+
+	db.set_attr(&ada, "born", &1815)?;
+	db.set_attr(&ada, "avatar", &vec![1u8, 2, 3])?;
+	db.set_attr(&alan, "born", &1912)?;
+
+	assert_eq!(db.get_attrs(&ada)?, [
+		("avatar".to_string(), AnyValue(Value::Blob(vec![1, 2, 3]))),
+		("born".to_string(), AnyValue(Value::Integer(1815)))
+	]);
+	assert_eq!(db.get_attr(&alan, "born")?, Some(AnyValue(Value::Integer(1912))));
+	assert_eq!(db.get_attr(&alan, "avatar")?, None);
+
+	assert!(db.remove_attr(&ada, "avatar")?);
+	assert!(!db.remove_attr(&ada, "avatar")?);
+	assert_eq!(db.get_attrs(&ada)?.len(), 1);
+
+	db.execute("DELETE FROM contact WHERE id = ?", &ada.id)?;
+	assert_eq!(db.query_one::<i64>("SELECT count(*) FROM contactfield")?, 1);
+	Ok(())
+}
+
+#[test]
+fn attribute_table() {
+	assert_eq!(ContactField::NAME, "contactfield");
+	assert_eq!(ContactField::KEY_COLUMNS.last(), Some(&"name"));
+	assert_eq!(ContactField::OTHER_COLUMNS, ["value"]);
+	assert_eq!(ContactField::CREATE_TABLE.matches("ON DELETE CASCADE").count(), 1);
+}
@@ -0,0 +1,99 @@
+use liter::{
+	database,
+	event_log,
+	snapshot_table,
+	Value
+};
+use rusqlite::Result as SqlResult;
+
+#[derive(Value, Clone, Debug, PartialEq)]
+enum Event {
+	Add(i64),
+	Reset
+}
+
+event_log!(struct Counter(Event));
+snapshot_table!(struct CounterState(i64));
+
+#[database]
+struct Db (Counter, CounterState);
+
+#[test]
+fn append_and_replay() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let log = db.event_log::<Counter>();
+	assert_eq!(log.last_seq()?, 0);
+
+	assert_eq!(log.append(&Event::Add(3))?, 1);
+	assert_eq!(log.append(&Event::Reset)?, 2);
+	assert_eq!(log.append(&Event::Add(7))?, 3);
+	assert_eq!(log.last_seq()?, 3);
+
+	let mut events = Vec::new();
+	let last = log.replay_from(0, |seq, event| events.push((seq, event)))?;
+	assert_eq!(last, 3);
+	assert_eq!(
+		events,
+		vec![(1, Event::Add(3)), (2, Event::Reset), (3, Event::Add(7))]
+	);
+
+	let mut events = Vec::new();
+	assert_eq!(log.replay_from(2, |_, event| events.push(event))?, 3);
+	assert_eq!(events, vec![Event::Add(7)]);
+	assert_eq!(log.replay_from(3, |_, _| panic!("no more events"))?, 3);
+
+	Ok(())
+}
+
+#[test]
+fn snapshots() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let log = db.event_log::<Counter>();
+	assert!(log.latest_snapshot::<CounterState>()?.is_none());
+
+	log.append(&Event::Add(1))?;
+	let seq = log.append(&Event::Add(2))?;
+	log.save_snapshot::<CounterState>(seq, &3)?;
+	log.append(&Event::Add(4))?;
+	assert_eq!(log.truncate(seq)?, 2);
+
+	let (from, mut count) = log.latest_snapshot::<CounterState>()?.unwrap();
+	assert_eq!(from, 2);
+	log.replay_from(from, |_, event| match event {
+		Event::Add(n) => count += n,
+		Event::Reset => count = 0
+	})?;
+	assert_eq!(count, 7);
+
+	// sequence numbers keep increasing after truncating
+	assert_eq!(log.append(&Event::Reset)?, 4);
+
+	Ok(())
+}
+
+#[test]
+fn truncate_keeps_latest() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let log = db.event_log::<Counter>();
+	// nothing to delete
+	assert_eq!(log.truncate(0)?, 0);
+
+	for n in 1..=3 {
+		log.append(&Event::Add(n))?;
+	}
+	assert!(log.truncate(3).is_err());
+	assert!(log.truncate(10).is_err());
+	assert_eq!(log.last_seq()?, 3);
+
+	assert_eq!(log.truncate(2)?, 2);
+	assert_eq!(log.last_seq()?, 3);
+	// the sequence number of the deleted events isn't reused
+	assert_eq!(log.append(&Event::Reset)?, 4);
+	assert_eq!(log.truncate(3)?, 1);
+	assert_eq!(log.append(&Event::Add(5))?, 5);
+
+	let mut events = Vec::new();
+	log.replay_from(0, |seq, event| events.push((seq, event)))?;
+	assert_eq!(events, vec![(4, Event::Reset), (5, Event::Add(5))]);
+	Ok(())
+}
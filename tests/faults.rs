@@ -0,0 +1,81 @@
+#![cfg(feature = "testing")]
+
+use std::time::{
+	Duration,
+	Instant
+};
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::testing::{
+	Fault,
+	FaultyDb
+};
+use rusqlite::ErrorCode;
+
+#[database]
+struct Db (Author, Book);
+
+#[derive(Table, Debug, PartialEq)]
+struct Author {
+	#[key]
+	id: Id,
+	name: String
+}
+
+#[derive(Table, Debug, PartialEq)]
+struct Book {
+	#[key]
+	id: Id,
+	title: String
+}
+
+fn code(error: rusqlite::Error) -> Option<ErrorCode> {
+	error.sqlite_error_code()
+}
+
+#[test]
+fn table_and_operation() {
+	let db = FaultyDb::new(Db::create_in_memory().unwrap());
+	db.inject(Fault::io_error().on("insert").on("get_all").table::<Book>());
+
+	let author = Author { id: Id::new(1), name: "Le Guin".to_string() };
+	db.insert(&author).unwrap();
+	assert_eq!(db.get_all::<Author>().unwrap(), [author]);
+	let book = Book { id: Id::new(1), title: "The Dispossessed".to_string() };
+	assert_eq!(code(db.insert(&book).unwrap_err()), Some(ErrorCode::SystemIoFailure));
+	assert_eq!(code(db.get_all::<Book>().unwrap_err()), Some(ErrorCode::SystemIoFailure));
+
+	// the failed insert didn't run
+	assert_eq!(db.database().get_all::<Book>().unwrap(), []);
+	// neither did the table-less fault apply to execute
+	db.execute("INSERT INTO book VALUES (2, 'Lathe of Heaven')", &()).unwrap();
+
+	db.clear();
+	db.insert(&book).unwrap();
+	assert_eq!(db.get_all::<Book>().unwrap().len(), 2);
+}
+
+#[test]
+fn times() {
+	let db = FaultyDb::new(Db::create_in_memory().unwrap());
+	db.inject(Fault::busy().times(1));
+	db.inject(Fault::io_error().on("query_one").times(1));
+
+	assert_eq!(code(db.query_one::<i64>("SELECT 1").unwrap_err()), Some(ErrorCode::DatabaseBusy));
+	assert_eq!(code(db.query_one::<i64>("SELECT 1").unwrap_err()), Some(ErrorCode::SystemIoFailure));
+	assert_eq!(db.query_one::<i64>("SELECT 1").unwrap(), 1);
+}
+
+#[test]
+fn delay() {
+	let db = FaultyDb::new(Db::create_in_memory().unwrap());
+	db.inject(Fault::delay(Duration::from_millis(50)).on("get"));
+
+	let start = Instant::now();
+	assert_eq!(db.get::<Author>(Id::new(1)).unwrap(), None);
+	assert!(start.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "files")]
+
+use std::io::{
+	Read,
+	Write
+};
+
+use liter::database;
+use liter::files::{
+	FileChunk,
+	StoredFile
+};
+use rusqlite::Result as SqlResult;
+
+#[database]
+struct Db (StoredFile, FileChunk);
+
+#[test]
+fn chunked_round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let contents: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+
+	let mut writer = db.write_file("numbers")?.with_chunk_size(64);
+	writer.write_all(&contents[..500]).unwrap();
+	writer.write_all(&contents[500..]).unwrap();
+	let file = writer.finish()?;
+
+	assert_eq!(file.size, 1000);
+	assert_eq!(db.get_all::<FileChunk>()?.len(), 16);
+
+	let mut read = Vec::new();
+	db.read_file(file.id.clone())?.unwrap().read_to_end(&mut read).unwrap();
+	assert_eq!(read, contents);
+
+	assert!(db.delete_file(file.id.clone())?);
+	assert!(db.read_file(file.id)?.is_none());
+	assert!(db.get_all::<FileChunk>()?.is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn corrupted_chunk() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+
+	let mut writer = db.write_file("text")?;
+	writer.write_all(b"original contents").unwrap();
+	let file = writer.finish()?;
+
+	db.execute("UPDATE filechunk SET data = ?", &b"tampered".to_vec())?;
+
+	let mut read = Vec::new();
+	let err = db.read_file(file.id)?.unwrap().read_to_end(&mut read)
+		.expect_err("hash mismatch must be detected");
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+	Ok(())
+}
+
+#[test]
+fn incomplete_file() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+
+	let mut writer = db.write_file("unfinished")?;
+	writer.write_all(b"never finished").unwrap();
+	drop(writer);
+
+	let file = db.get_all::<StoredFile>()?.pop().unwrap();
+	assert!(file.sha256.is_none());
+	db.read_file(file.id).expect_err("incomplete files can't be read");
+
+	Ok(())
+}
+
+#[test]
+fn delete_in_transaction() -> SqlResult<()> {
+	let mut db = Db::create_in_memory()?;
+	let mut writer = db.write_file("kept")?.with_chunk_size(4);
+	writer.write_all(b"0123456789").unwrap();
+	let file = writer.finish()?;
+	assert!(!db.delete_file(liter::Id::new(99))?);
+
+	let tx = db.transaction()?;
+	assert!(tx.delete_file(file.id.clone())?);
+	assert!(tx.get_all::<FileChunk>()?.is_empty());
+	tx.rollback()?;
+
+	// the file & all of its chunks are back
+	let mut read = Vec::new();
+	db.read_file(file.id)?.unwrap().read_to_end(&mut read).unwrap();
+	assert_eq!(read, b"0123456789");
+	assert_eq!(db.get_all::<FileChunk>()?.len(), 3);
+	Ok(())
+}
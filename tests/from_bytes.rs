@@ -0,0 +1,78 @@
+use std::fs;
+
+use liter::{
+	Database,
+	Schema,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+/// File contents of a new database, after `fill`ing it
+fn bytes<S: Schema>(name: &str, fill: impl FnOnce(&Database<S>) -> SqlResult<()>)
+	-> SqlResult<&'static [u8]>
+{
+	let path = std::env::temp_dir().join(name);
+	let _ = fs::remove_file(&path);
+	let db = Database::<S>::init(&path)?;
+	fill(&db)?;
+	drop(db);
+	let bytes = fs::read(&path).unwrap();
+	fs::remove_file(&path).unwrap();
+	Ok(bytes.leak())
+}
+
+#[test]
+fn read_only() -> SqlResult<()> {
+	#[database]
+	struct Db (Word);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Word {
+		#[key]
+		word: String,
+		meaning: String
+	}
+
+	let word = |word: &str| Word { word: word.to_string(), meaning: String::new() };
+	let bytes = bytes::<Db>("liter_from_bytes_read_only.db", |db| {
+		db.insert(&word("a"))?;
+		db.insert(&word("b"))
+	})?;
+	let copy = bytes.to_vec();
+	let db = Database::<Db>::open_from_bytes(bytes)?;
+	// the same bytes can back several databases
+	let other = Database::<Db>::open_from_bytes(bytes)?;
+	assert_eq!(db.get_all::<Word>()?, [word("a"), word("b")]);
+	assert_eq!(other.count::<Word>()?, 2);
+
+	assert!(db.insert(&word("c")).is_err());
+	assert!(db.delete::<Word>(&"a".to_string()).is_err());
+	assert!(db.execute("UPDATE word SET meaning = 'x'", &()).is_err());
+	assert!(db.execute_batch("CREATE TABLE extra (x)").is_err());
+	assert_eq!(bytes, copy);
+	assert_eq!(other.get::<Word>("a".to_string())?, Some(word("a")));
+	Ok(())
+}
+
+#[test]
+fn not_a_database() -> SqlResult<()> {
+	#[database]
+	struct Db (Word);
+
+	#[derive(Table)]
+	struct Word {
+		#[key]
+		word: String
+	}
+
+	// an empty database, without any tables
+	let db = Database::<Db>::open_from_bytes(&[])?;
+	assert!(db.count::<Word>().is_err());
+
+	let garbage: &'static [u8] = &[0x55; 4096];
+	let opened = Database::<Db>::open_from_bytes(garbage);
+	assert!(opened.map_or(true, |db| db.count::<Word>().is_err()));
+	Ok(())
+}
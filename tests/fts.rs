@@ -0,0 +1,113 @@
+use liter::{
+	Database,
+	Id,
+	Table,
+	database
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[database]
+struct Db (Note);
+
+#[derive(Table, Clone, Debug, PartialEq)]
+struct Note {
+	#[key]
+	id: Id,
+	title: String,
+	body: String
+}
+
+fn note(id: i64, title: &str, body: &str) -> Note {
+	Note { id: Id::new(id), title: title.to_string(), body: body.to_string() }
+}
+
+fn indexed() -> SqlResult<Database<Db>> {
+	let db = Db::create_in_memory()?;
+	// existing entries are indexed too
+	db.insert(&note(1, "Groceries", "milk, eggs and bread"))?;
+	db.create_fts_index::<Note>(&["title", "body"])?;
+	db.insert(&note(2, "Recipe", "whisk the eggs with milk"))?;
+	Ok(db)
+}
+
+fn found(db: &Database<Db>, query: &str) -> SqlResult<Vec<i64>> {
+	let mut ids: Vec<i64> = db.search::<Note>(query)
+		.fetch()?
+		.into_iter()
+		.map(|hit| hit.entry.id.get().unwrap())
+		.collect();
+	ids.sort();
+	Ok(ids)
+}
+
+#[test]
+fn create_index() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	assert!(matches!(
+		db.create_fts_index::<Note>(&["title", "missing"]),
+		Err(Error::InvalidColumnName(column)) if column == "missing"
+	));
+	// searching without an index fails
+	assert!(db.search::<Note>("milk").fetch().is_err());
+
+	db.create_fts_index::<Note>(&["body"])?;
+	// creating it again keeps the existing one
+	db.create_fts_index::<Note>(&["title", "body"])?;
+	db.insert(&note(1, "Milk", "to buy"))?;
+	assert!(found(&db, "milk")?.is_empty());
+	assert_eq!(found(&db, "buy")?, [1]);
+	Ok(())
+}
+
+#[test]
+fn kept_up_to_date() -> SqlResult<()> {
+	let db = indexed()?;
+	assert_eq!(found(&db, "eggs")?, [1, 2]);
+
+	db.execute("UPDATE note SET body = 'flour and sugar' WHERE id = 1", &())?;
+	assert_eq!(found(&db, "eggs")?, [2]);
+	assert_eq!(found(&db, "sugar")?, [1]);
+
+	db.execute("DELETE FROM note WHERE id = 2", &())?;
+	assert!(found(&db, "eggs")?.is_empty());
+	assert_eq!(found(&db, "groceries")?, [1]);
+	Ok(())
+}
+
+#[test]
+fn queries() -> SqlResult<()> {
+	let db = indexed()?;
+	assert!(found(&db, "nothing")?.is_empty());
+	assert_eq!(found(&db, "title:recipe")?, [2]);
+	assert_eq!(found(&db, "milk NOT bread")?, [2]);
+	assert_eq!(found(&db, "\"eggs and\"")?, [1]);
+	// FTS5 syntax errors are returned, not panicked on
+	assert!(db.search::<Note>("\"unterminated").fetch().is_err());
+	assert!(db.search::<Note>("AND").fetch().is_err());
+
+	assert!(db.search::<Note>("milk").limit(0).fetch()?.is_empty());
+	assert_eq!(db.search::<Note>("milk").limit(1).fetch()?.len(), 1);
+	Ok(())
+}
+
+#[test]
+fn excerpts() -> SqlResult<()> {
+	let db = indexed()?;
+	let hits = db.search::<Note>("recipe").highlight("title", "*", "*").fetch()?;
+	assert_eq!(hits[0].excerpt.as_deref(), Some("*Recipe*"));
+	// an excerpt of a column that isn't indexed
+	assert!(matches!(
+		db.search::<Note>("milk").highlight("id", "*", "*").fetch(),
+		Err(Error::InvalidColumnName(_))
+	));
+
+	// one weight for each of the indexed columns
+	let hits = db.search::<Note>("whisk").weights(&[10.0, 1.0]).fetch()?;
+	assert_eq!(hits.len(), 1);
+	assert_eq!(hits[0].excerpt, None);
+	Ok(())
+}
@@ -0,0 +1,175 @@
+use liter::{
+	Bind,
+	Database,
+	Entry,
+	Fetch,
+	Schema,
+	Table,
+	Value
+};
+use liter::table::{
+	TableDef,
+	Values
+};
+use liter::types::{
+	Binder,
+	Fetcher
+};
+use liter::util::construe;
+use rusqlite::Result as SqlResult;
+
+const LINE_VALUES: Values = &[
+	("price", <u64 as Value>::DEFINITION),
+	("quantity", <u64 as Value>::DEFINITION.with_default("1")),
+	("total", <u64 as Value>::DEFINITION.generated("price * quantity", false))
+];
+
+/// Table with a `DEFAULT` & a generated column, which `#[derive(Table)]` can't declare (yet)
+#[derive(Debug, PartialEq)]
+struct Line {
+	price: u64,
+	quantity: u64,
+	total: u64
+}
+
+impl Table for Line {
+	const NAME: &'static str = "line";
+	const DEFINITION: TableDef = TableDef {
+		name: "line",
+		primary_key: &[],
+		values: LINE_VALUES,
+		key_values: &[],
+		other_values: LINE_VALUES,
+		constraints: &[]
+	};
+	const CREATE_TABLE: &'static str = construe!(&str => TableDef::define(&Line::DEFINITION));
+	const ALL_COLUMNS: &'static [&'static str] = &["price", "quantity", "total"];
+	const KEY_COLUMNS: &'static [&'static str] = &[];
+	const OTHER_COLUMNS: &'static [&'static str] = Self::ALL_COLUMNS;
+	type References = ();
+}
+
+impl Entry for Line {
+	const GET_ALL: &'static str = "SELECT * FROM line";
+	const INSERT: &'static str = construe!(
+		&str => liter::table::insert_for(&Line::DEFINITION, Line::ALL_COLUMNS)
+	);
+	const HAS_GENERATED: bool = true;
+}
+
+impl Bind for Line {
+	const COLUMNS: usize = 3;
+	fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+		binder.bind(&self.price)?;
+		binder.bind(&self.quantity)?;
+		binder.bind(&self.total)
+	}
+}
+
+impl Fetch for Line {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		Ok(Self {
+			price: fetcher.fetch()?,
+			quantity: fetcher.fetch()?,
+			total: fetcher.fetch()?
+		})
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		Self::fetch(fetcher).map(Some)
+	}
+}
+
+/// Same table & statements as [`Line`], but without [`Entry::HAS_GENERATED`]
+struct Unmarked(Line);
+
+impl Table for Unmarked {
+	const NAME: &'static str = Line::NAME;
+	const DEFINITION: TableDef = Line::DEFINITION;
+	const CREATE_TABLE: &'static str = Line::CREATE_TABLE;
+	const ALL_COLUMNS: &'static [&'static str] = Line::ALL_COLUMNS;
+	const KEY_COLUMNS: &'static [&'static str] = Line::KEY_COLUMNS;
+	const OTHER_COLUMNS: &'static [&'static str] = Line::OTHER_COLUMNS;
+	type References = ();
+}
+
+impl Entry for Unmarked {
+	const GET_ALL: &'static str = Line::GET_ALL;
+	const INSERT: &'static str = Line::INSERT;
+}
+
+impl Bind for Unmarked {
+	const COLUMNS: usize = Line::COLUMNS;
+	fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+		self.0.bind(binder)
+	}
+}
+
+impl Fetch for Unmarked {
+	fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+		Line::fetch(fetcher).map(Self)
+	}
+	fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+		Line::try_fetch(fetcher).map(|line| line.map(Self))
+	}
+}
+
+struct Db;
+
+impl Schema for Db {
+	type Tables = (Line, );
+	const DEFINITIONS: &'static [TableDef] = &[Line::DEFINITION];
+	const CREATE: &'static str = construe!(
+		&str => liter::schema::define(&[Line::CREATE_TABLE])
+	);
+}
+
+fn line(price: u64, quantity: u64) -> Line {
+	Line { price, quantity, total: 0 }
+}
+
+#[test]
+fn column_clauses() {
+	let sql = Line::CREATE_TABLE;
+	assert!(sql.contains("quantity INTEGER NOT NULL DEFAULT (1)"), "{sql}");
+	assert!(
+		sql.contains("total INTEGER NOT NULL GENERATED ALWAYS AS (price * quantity) VIRTUAL"),
+		"{sql}"
+	);
+	assert_eq!(Line::INSERT, "INSERT INTO \"line\" (price, quantity) VALUES (?1, ?2)");
+}
+
+#[test]
+#[should_panic]
+fn generated_with_default() {
+	let _ = <u64 as Value>::DEFINITION
+		.with_default("0")
+		.generated("1", true);
+}
+
+#[test]
+fn generated_column_is_skipped() -> SqlResult<()> {
+	let db = Database::<Db>::create_in_memory()?;
+	db.insert(&line(3, 2))?;
+	assert_eq!(db.get_all::<Line>()?, [Line { price: 3, quantity: 2, total: 6 }]);
+
+	let returned = db.insert_returning(&line(4, 5))?;
+	assert_eq!(returned, Line { price: 4, quantity: 5, total: 20 });
+	Ok(())
+}
+
+#[test]
+fn default_is_filled_in() -> SqlResult<()> {
+	let db = Database::<Db>::create_in_memory()?;
+	db.insert_with_defaults(&line(5, 99))?;
+	assert_eq!(db.get_all::<Line>()?, [Line { price: 5, quantity: 1, total: 5 }]);
+	Ok(())
+}
+
+#[test]
+fn extra_parameters_are_an_error() -> SqlResult<()> {
+	let db = Database::<Db>::create_in_memory()?;
+	// the statement has no parameter for the generated column, which is only skipped for tables marked as having generated columns
+	assert!(db.insert(&Unmarked(line(1, 1))).is_err());
+	assert!(db.get_all::<Line>()?.is_empty());
+	Ok(())
+}
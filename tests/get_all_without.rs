@@ -0,0 +1,49 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[test]
+fn skip_blobs() -> SqlResult<()> {
+	#[database]
+	struct Db (Attachment);
+
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Attachment {
+		#[key]
+		id: Id,
+		name: String,
+		data: Vec<u8>,
+		thumbnail: Option<Vec<u8>>,
+		size: u64
+	}
+	let db = Db::create_in_memory()?;
+	let mut attachment = Attachment {
+		id: Id::NULL,
+		name: "photo.jpg".to_string(),
+		data: vec![0xff; 1 << 20],
+		thumbnail: Some(vec![0xff; 1 << 10]),
+		size: 1 << 20
+	};
+	db.create(&mut attachment)?;
+
+	let listed: Vec<Attachment> = db.get_all_without(&["data", "thumbnail"])?;
+	assert_eq!(listed, [Attachment {
+		data: Vec::new(),
+		thumbnail: None,
+		..attachment.clone()
+	}]);
+	assert_eq!(db.get_all_without::<Attachment>(&[])?, [attachment]);
+
+	assert!(matches!(
+		db.get_all_without::<Attachment>(&["content"]),
+		Err(Error::InvalidColumnName(column)) if column == "content"
+	));
+	Ok(())
+}
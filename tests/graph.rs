@@ -0,0 +1,117 @@
+use liter::{
+	Database,
+	Id,
+	Ref,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[database]
+struct Db (Author, Book);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Author {
+	#[key]
+	id: Id,
+	name: String
+}
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Book {
+	#[key]
+	id: Id,
+	author: Ref<Author>,
+	editor: Option<Ref<Author>>,
+	title: String
+}
+
+fn library() -> SqlResult<Database<Db>> {
+	let db = Db::create_in_memory()?;
+	for (id, name) in [(1, "Le Guin"), (2, "Pratchett"), (3, "Gaiman")] {
+		db.insert(&Author { id: Id::new(id), name: name.to_string() })?;
+	}
+	db.insert(&book(1, 1, None))?;
+	db.insert(&book(2, 2, Some(3)))?;
+	db.insert(&book(3, 3, Some(2)))?;
+	Ok(db)
+}
+
+fn book(id: i64, author: i64, editor: Option<i64>) -> Book {
+	Book {
+		id: Id::new(id),
+		author: Ref(Id::new(author)),
+		editor: editor.map(|editor| Ref(Id::new(editor))),
+		title: format!("book {id}")
+	}
+}
+
+fn ids<'a>(books: impl Iterator<Item = &'a Book>) -> Vec<i64> {
+	books.map(|book| book.id.get().unwrap()).collect()
+}
+
+#[test]
+fn empty_keys() -> SqlResult<()> {
+	let db = library()?;
+	let graph = db.load_graph::<Book>(&[], 3)?;
+	assert_eq!(graph.roots().count(), 0);
+	assert!(graph.all::<Book>().is_empty());
+	assert!(graph.all::<Author>().is_empty());
+	Ok(())
+}
+
+#[test]
+fn roots_in_key_order() -> SqlResult<()> {
+	let db = library()?;
+	// missing keys are skipped, repeated ones only show up once
+	let keys = [3, 99, 1, 3].map(Id::new);
+	let graph = db.load_graph::<Book>(&keys, 0)?;
+	assert_eq!(ids(graph.roots()), [3, 1]);
+
+	// a depth of 0 doesn't load any references
+	assert!(graph.all::<Author>().is_empty());
+	assert_eq!(graph.resolve(&Ref::<Author>(Id::new(3))), None);
+	Ok(())
+}
+
+#[test]
+fn every_foreign_key() -> SqlResult<()> {
+	let db = library()?;
+	let graph = db.load_graph::<Book>(&[Id::new(2)], 1)?;
+	let book = graph.roots().next().unwrap();
+	assert_eq!(graph.resolve(&book.author).unwrap().name, "Pratchett");
+	assert_eq!(graph.resolve(book.editor.as_ref().unwrap()).unwrap().name, "Gaiman");
+	assert_eq!(graph.all::<Author>().len(), 2);
+	Ok(())
+}
+
+#[test]
+fn missing_references() -> SqlResult<()> {
+	let db = library()?;
+	// only possible without enforced foreign keys, e.g. in a database written by another tool
+	db.execute("PRAGMA foreign_keys = OFF", &())?;
+	db.insert(&book(4, 42, Some(1)))?;
+
+	let graph = db.load_graph::<Book>(&[Id::new(4)], 1)?;
+	let book = graph.roots().next().unwrap();
+	assert_eq!(graph.resolve(&book.author), None);
+	assert_eq!(graph.resolve(book.editor.as_ref().unwrap()).unwrap().name, "Le Guin");
+	Ok(())
+}
+
+#[test]
+fn across_batches() -> SqlResult<()> {
+	let db = library()?;
+	for id in 4..1200 {
+		db.insert(&book(id, id % 3 + 1, None))?;
+	}
+	let keys: Vec<Id> = (1..1200).rev().map(Id::new).collect();
+	let graph = db.load_graph::<Book>(&keys, 1)?;
+	assert_eq!(ids(graph.roots()), (1..1200).rev().collect::<Vec<_>>());
+	assert_eq!(graph.all::<Author>().len(), 3);
+	for book in graph.roots() {
+		assert!(graph.resolve(&book.author).is_some(), "{book:?}");
+	}
+	Ok(())
+}
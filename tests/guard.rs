@@ -0,0 +1,83 @@
+use liter::{
+	Database,
+	Id,
+	Table,
+	database
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+#[database]
+struct Db (Item);
+
+#[derive(Table, Debug, PartialEq)]
+struct Item {
+	#[key]
+	id: Id,
+	done: bool
+}
+
+fn guarded() -> SqlResult<Database<Db>> {
+	let db = Db::create_in_memory()?;
+	for id in 1..=3 {
+		db.insert(&Item { id: Id::from_i64(id), done: false })?;
+	}
+	db.require_where_for_bulk_ops(Some(1));
+	Ok(db)
+}
+
+fn done(db: &Database<Db>) -> SqlResult<i64> {
+	db.query_one("SELECT count(*) FROM item WHERE done")
+}
+
+#[test]
+fn dml_is_guarded() -> SqlResult<()> {
+	let db = guarded()?;
+	for sql in [
+		"UPDATE item SET done = 1",
+		"  update item SET done = 1",
+		"-- all of them\nUPDATE item SET done = 1",
+		"/* all of them */ UPDATE item SET done = 1",
+		"WITH ids AS (SELECT id FROM item) UPDATE item SET done = 1 WHERE id IN ids",
+		"REPLACE INTO item SELECT id, 1 FROM item"
+	] {
+		let result = db.execute(sql, &());
+		assert!(matches!(result, Err(Error::StatementChangedRows(3))), "{sql}: {result:?}");
+		assert_eq!(done(&db)?, 0, "{sql}");
+	}
+	assert!(matches!(
+		db.delete_where::<Item, _>("id > ?", &0),
+		Err(Error::StatementChangedRows(3))
+	));
+	assert_eq!(db.get_all::<Item>()?.len(), 3);
+	Ok(())
+}
+
+#[test]
+fn other_statements_are_not_guarded() -> SqlResult<()> {
+	let db = guarded()?;
+
+	// neither of these works inside a savepoint
+	db.execute("VACUUM", &())?;
+	db.execute("PRAGMA foreign_keys = OFF", &())?;
+	assert_eq!(db.query_one::<i64>("PRAGMA foreign_keys")?, 0);
+
+	db.execute("CREATE TABLE other (value INTEGER)", &())?;
+	db.execute("DROP TABLE other", &())?;
+	Ok(())
+}
+
+#[test]
+fn in_transaction() -> SqlResult<()> {
+	let mut db = guarded()?;
+	let tx = db.transaction()?;
+	tx.execute("UPDATE item SET done = 1 WHERE id = ?", &Id::from_i64(1))?;
+	assert!(tx.execute("UPDATE item SET done = 1", &()).is_err());
+	// only the statement that changed too many rows was rolled back
+	assert_eq!(done(&tx)?, 1);
+	tx.commit()?;
+	assert_eq!(done(&db)?, 1);
+	Ok(())
+}
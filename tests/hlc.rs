@@ -0,0 +1,64 @@
+use liter::database;
+use liter::hlc::{
+	ClockState,
+	Hlc
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn components() {
+	let hlc = Hlc::new(1_700_000_000_123, 7);
+	assert_eq!(hlc.physical_ms(), 1_700_000_000_123);
+	assert_eq!(hlc.counter(), 7);
+	assert_eq!(Hlc::from_u64(hlc.as_u64()), hlc);
+	assert_eq!(hlc.to_string(), "1700000000123.00007");
+
+	// the physical time is compared first
+	assert!(Hlc::new(1, u16::MAX) < Hlc::new(2, 0));
+	assert!(Hlc::new(2, 0) < Hlc::new(2, 1));
+	assert_eq!(Hlc::new(0, u16::MAX).as_u64() + 1, Hlc::new(1, 0).as_u64());
+}
+
+#[test]
+fn observe() -> SqlResult<()> {
+	#[database]
+	struct Db (ClockState);
+
+	let db = Db::create_in_memory()?;
+	let local = db.now_hlc()?;
+	// older timestamps don't hold the clock back
+	assert!(db.observe_hlc(Hlc::new(1, 0))? > local);
+
+	let remote = Hlc::new(local.physical_ms() + 3_600_000, 5);
+	let observed = db.observe_hlc(remote)?;
+	assert_eq!(observed, Hlc::new(remote.physical_ms(), 6));
+	// the wall clock is behind now, so the counter keeps going
+	assert_eq!(db.now_hlc()?, Hlc::new(remote.physical_ms(), 7));
+	assert_eq!(db.count::<ClockState>()?, 1);
+	Ok(())
+}
+
+#[test]
+fn clock_backwards() -> SqlResult<()> {
+	#[database]
+	struct Db (ClockState);
+
+	let db = Db::create_in_memory()?;
+	let future = Hlc::new(db.now_hlc()?.physical_ms() + 60_000, u16::MAX);
+	db.execute("UPDATE clockstate SET last = ?", &future)?;
+	// the counter overflows into the physical time
+	assert_eq!(db.now_hlc()?, Hlc::new(future.physical_ms() + 1, 0));
+	Ok(())
+}
+
+#[test]
+fn out_of_range() -> SqlResult<()> {
+	#[database]
+	struct Db (ClockState);
+
+	let db = Db::create_in_memory()?;
+	assert!(db.query_one::<Hlc>("SELECT -1").is_err());
+	assert!(db.execute("INSERT INTO clockstate VALUES (1, 0)", &()).is_err());
+	Ok(())
+}
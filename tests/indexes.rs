@@ -0,0 +1,89 @@
+use liter::{
+	Database,
+	Id,
+	Schema,
+	Table
+};
+use liter::table::{
+	Index,
+	TableDef,
+	define_indexes
+};
+use liter::util::construe;
+use rusqlite::Result as SqlResult;
+
+#[derive(Table, Debug, PartialEq)]
+struct Item {
+	#[key]
+	id: Id,
+	name: String,
+	category: String
+}
+
+const ITEM_INDEXES: &[Index] = &[
+	Index { unique: true, values: &["name"] },
+	Index { unique: false, values: &["category", "name"] }
+];
+const CREATE_ITEM_INDEXES: &str = construe!(
+	&str => define_indexes(&Item::DEFINITION, ITEM_INDEXES)
+);
+
+struct Db;
+
+impl Schema for Db {
+	type Tables = (Item, );
+	const DEFINITIONS: &'static [TableDef] = &[Item::DEFINITION];
+	const CREATE: &'static str = construe!(
+		&str => liter::schema::define(&[Item::CREATE_TABLE, CREATE_ITEM_INDEXES])
+	);
+}
+
+fn item(id: i64, name: &str, category: &str) -> Item {
+	Item {
+		id: Id::from_i64(id),
+		name: name.to_string(),
+		category: category.to_string()
+	}
+}
+
+#[test]
+fn no_indexes_by_default() {
+	assert!(Item::INDEXES.is_empty());
+	assert!(!Item::CREATE_TABLE.contains("CREATE INDEX"));
+}
+
+#[test]
+fn create_index_sql() {
+	assert_eq!(
+		CREATE_ITEM_INDEXES,
+		"CREATE UNIQUE INDEX item_name_index ON item ( name );\n\
+		CREATE INDEX item_category_name_index ON item ( category, name );"
+	);
+	assert!(Db::CREATE.contains("CREATE UNIQUE INDEX item_name_index"), "{}", Db::CREATE);
+	assert!(Db::CREATE.contains("CREATE INDEX item_category_name_index"), "{}", Db::CREATE);
+}
+
+#[test]
+fn indexes_are_created() -> SqlResult<()> {
+	let db = Database::<Db>::create_in_memory()?;
+
+	let mut names: Vec<String> = db.query_all(
+		"SELECT name FROM pragma_index_list('item') WHERE origin = 'c'"
+	)?;
+	names.sort();
+	assert_eq!(names, ["item_category_name_index", "item_name_index"]);
+
+	db.insert(&item(1, "lamp", "light"))?;
+	// the unique index rejects a second item with the same name
+	assert!(db.insert(&item(2, "lamp", "furniture")).is_err());
+	assert_eq!(db.get_all::<Item>()?, vec![item(1, "lamp", "light")]);
+
+	let plan: Vec<(i64, i64, i64, String)> = db.query_all(
+		"EXPLAIN QUERY PLAN SELECT * FROM item WHERE category = 'light'"
+	)?;
+	assert!(
+		plan.iter().any(|(.., detail)| detail.contains("item_category_name_index")),
+		"{plan:?}"
+	);
+	Ok(())
+}
@@ -0,0 +1,53 @@
+use std::num::NonZeroU32;
+
+use liter::{
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn big_int_order() -> SqlResult<()> {
+	#[database]
+	struct Db (Hash);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Hash {
+		#[key]
+		signed: i128,
+		unsigned: u128
+	}
+	let db = Db::create_in_memory()?;
+
+	let values = [i128::MIN, -300, -1, 0, 1, 1 << 80, i128::MAX];
+	for signed in values.iter().rev() {
+		db.insert(&Hash { signed: *signed, unsigned: signed.unsigned_abs() })?;
+	}
+	let sorted: Vec<i128> = db.query_all("SELECT signed FROM hash ORDER BY signed")?;
+	assert_eq!(sorted, values);
+
+	let sorted: Vec<u128> = db.query_all("SELECT unsigned FROM hash ORDER BY unsigned")?;
+	let mut expected: Vec<u128> = values.iter().map(|v| v.unsigned_abs()).collect();
+	expected.sort();
+	assert_eq!(sorted, expected);
+	Ok(())
+}
+
+#[test]
+fn non_zero() -> SqlResult<()> {
+	#[database]
+	struct Db (Counter);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Counter {
+		step: NonZeroU32
+	}
+	let db = Db::create_in_memory()?;
+
+	let step = NonZeroU32::new(5).unwrap();
+	db.insert(&Counter { step })?;
+	assert_eq!(db.get_all::<Counter>()?, [Counter { step }]);
+	assert!(db.execute("INSERT INTO counter VALUES (0)", &()).is_err());
+	Ok(())
+}
@@ -0,0 +1,134 @@
+use liter::{
+	Database,
+	Id,
+	Schema,
+	Table,
+	database
+};
+use liter::invariant::{
+	Invariant,
+	InvariantDef
+};
+use liter::table::TableDef;
+use liter::util::construe;
+use rusqlite::Result as SqlResult;
+use rusqlite::types::Value;
+
+
+#[database]
+struct Db (Account);
+
+#[derive(Table, Clone, Debug, PartialEq)]
+struct Account {
+	#[key]
+	id: Id,
+	balance: i64
+}
+
+struct NotOverdrawn;
+impl Invariant for NotOverdrawn {
+	const NAME: &'static str = "no account is overdrawn";
+	const SQL: &'static str = "SELECT id, balance FROM account WHERE balance < 0 ORDER BY id";
+}
+
+struct Broken;
+impl Invariant for Broken {
+	const NAME: &'static str = "refers to a missing table";
+	const SQL: &'static str = "SELECT * FROM missing";
+}
+
+fn accounts(db: &Database<Db>, balances: impl IntoIterator<Item = i64>) -> SqlResult<()> {
+	for (id, balance) in balances.into_iter().enumerate() {
+		db.insert(&Account { id: Id::new(id as i64 + 1), balance })?;
+	}
+	Ok(())
+}
+
+#[test]
+fn holds_on_empty_database() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	assert_eq!(db.check_invariant::<NotOverdrawn>()?, None);
+	// no invariants registered with the schema
+	assert!(db.check_invariants()?.is_empty());
+	Ok(())
+}
+
+#[test]
+fn examples_are_limited() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	accounts(&db, (0..25).map(|idx| if idx % 2 == 0 { -idx } else { idx }))?;
+
+	let violation = db.check_invariant::<NotOverdrawn>()?.unwrap();
+	assert_eq!(violation.invariant, NotOverdrawn::NAME);
+	// 0 isn't overdrawn, -2 to -24 are
+	assert_eq!(violation.rows, 12);
+	assert_eq!(violation.examples.len(), 10);
+	assert_eq!(violation.examples[0], [Value::Integer(3), Value::Integer(-2)]);
+	assert_eq!(
+		violation.to_string(),
+		"invariant \"no account is overdrawn\" is broken by 12 rows"
+	);
+
+	db.execute("UPDATE account SET balance = 0 WHERE balance < 0", &())?;
+	assert_eq!(db.check_invariant::<NotOverdrawn>()?, None);
+	Ok(())
+}
+
+#[test]
+fn invalid_sql() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	assert!(db.check_invariant::<Broken>().is_err());
+	Ok(())
+}
+
+#[test]
+fn listed_in_schema() -> SqlResult<()> {
+	#[derive(Table)]
+	struct Stock {
+		#[key]
+		item: String,
+		count: i64
+	}
+
+	struct NoNegativeStock;
+	impl Invariant for NoNegativeStock {
+		const NAME: &'static str = "no stock is negative";
+		const SQL: &'static str = "SELECT item FROM stock WHERE count < 0";
+	}
+	struct Restocked;
+	impl Invariant for Restocked {
+		const NAME: &'static str = "everything is in stock";
+		const SQL: &'static str = "SELECT item FROM stock WHERE count = 0";
+	}
+
+	struct Db;
+	impl Schema for Db {
+		type Tables = (Stock, );
+		const DEFINITIONS: &'static [TableDef] = &[Stock::DEFINITION];
+		const CREATE: &'static str = construe!(
+			&str => liter::schema::define(&[Stock::CREATE_TABLE])
+		);
+		const INVARIANTS: &'static [InvariantDef] = &[
+			NoNegativeStock::DEFINITION,
+			Restocked::DEFINITION
+		];
+	}
+
+	let db = Database::<Db>::create_in_memory()?;
+	db.insert(&Stock { item: "nails".to_string(), count: 100 })?;
+	assert!(db.check_invariants()?.is_empty());
+
+	db.insert(&Stock { item: "screws".to_string(), count: -3 })?;
+	let violations = db.check_invariants()?;
+	assert_eq!(violations.len(), 1);
+	assert_eq!(violations[0].invariant, NoNegativeStock::NAME);
+	assert_eq!(violations[0].examples, [[Value::Text("screws".to_string())]]);
+
+	db.execute("UPDATE stock SET count = 0", &())?;
+	let names: Vec<&str> = db.check_invariants()?
+		.iter()
+		.map(|violation| violation.invariant)
+		.collect();
+	assert_eq!(names, [Restocked::NAME]);
+	Ok(())
+}
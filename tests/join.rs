@@ -0,0 +1,74 @@
+use liter::{
+	Id,
+	Ref,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn null_references() -> SqlResult<()> {
+	#[database]
+	struct Db (Folder, File);
+
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Folder {
+		#[key]
+		id: Id,
+		name: String
+	}
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct File {
+		#[key]
+		id: Id,
+		folder: Option<Ref<Folder>>
+	}
+
+	let db = Db::create_in_memory()?;
+	let folder = |id: i64| Folder { id: Id::new(id), name: format!("folder {id}") };
+	let file = |id, folder: Option<i64>| File {
+		id: Id::new(id),
+		folder: folder.map(|folder| Ref(Id::new(folder)))
+	};
+	db.insert(&folder(1))?;
+	// isn't referenced by any file
+	db.insert(&folder(2))?;
+	for f in [file(1, Some(1)), file(2, None), file(3, Some(1))] {
+		db.insert(&f)?;
+	}
+	let pairs: Vec<(File, Folder)> = db.join()?;
+	assert_eq!(pairs, [(file(1, Some(1)), folder(1)), (file(3, Some(1)), folder(1))]);
+	Ok(())
+}
+
+#[test]
+fn composite_key() -> SqlResult<()> {
+	#[database]
+	struct Db (Shelf, Book);
+
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Shelf {
+		#[key]
+		room: String,
+		#[key]
+		number: u8
+	}
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Book {
+		#[key]
+		id: Id,
+		shelf: Ref<Shelf>
+	}
+
+	let db = Db::create_in_memory()?;
+	let shelf = |room: &str, number| Shelf { room: room.to_string(), number };
+	for s in [shelf("attic", 1), shelf("attic", 2), shelf("study", 1)] {
+		db.insert(&s)?;
+	}
+	let book = Book { id: Id::new(1), shelf: Ref::make_ref(&shelf("attic", 2)) };
+	db.insert(&book)?;
+	// both columns of the key have to match
+	assert_eq!(db.join::<Book, Shelf, _>()?, [(book, shelf("attic", 2))]);
+	Ok(())
+}
@@ -0,0 +1,66 @@
+use liter::{
+	Id,
+	Table,
+	database,
+	junction
+};
+use rusqlite::Result as SqlResult;
+
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Student {
+	#[key]
+	id: Id,
+	name: String
+}
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Course {
+	#[key]
+	code: String
+}
+junction!(struct Enrollment(student: Student, course: Course));
+
+#[database]
+struct Db (Student, Course, Enrollment);
+
+#[test]
+fn relate_and_unrelate() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let mut ada = Student { id: Id::NULL, name: "Ada".to_string() };
+	db.create(&mut ada)?;
+	let mut alan = Student { id: Id::NULL, name: "Alan".to_string() };
+	db.create(&mut alan)?;
+	let math = Course { code: "MATH101".to_string() };
+	db.insert(&math)?;
+	let logic = Course { code: "LOG200".to_string() };
+	db.insert(&logic)?;
+
+	assert!(db.relate::<Student, Course>(&ada.id, &math.code)?);
+	assert!(!db.relate::<Student, Course>(&ada.id, &math.code)?);
+	// either side works
+	assert!(db.relate::<Course, Student>(&logic.code, &ada.id)?);
+	assert!(db.relate::<Course, Student>(&math.code, &alan.id)?);
+
+	let mut courses = db.related::<Student, Course>(&ada.id)?;
+	courses.sort_by(|a, b| a.code.cmp(&b.code));
+	assert_eq!(courses, [logic.clone(), math.clone()]);
+	assert_eq!(db.related::<Course, Student>(&logic.code)?, [ada.clone()]);
+
+	assert!(db.unrelate::<Course, Student>(&logic.code, &ada.id)?);
+	assert!(!db.unrelate::<Student, Course>(&ada.id, &logic.code)?);
+	assert_eq!(db.related::<Course, Student>(&logic.code)?, []);
+
+	// relations are deleted along with the entries
+	db.execute("DELETE FROM course WHERE code = ?", &math.code)?;
+	assert_eq!(db.related::<Student, Course>(&alan.id)?, []);
+	assert_eq!(db.query_one::<i64>("SELECT count(*) FROM enrollment")?, 0);
+	Ok(())
+}
+
+#[test]
+fn junction_table() {
+	assert_eq!(Enrollment::NAME, "enrollment");
+	assert_eq!(Enrollment::KEY_COLUMNS.len(), 2);
+	assert!(Enrollment::OTHER_COLUMNS.is_empty());
+	assert_eq!(Enrollment::CREATE_TABLE.matches("ON DELETE CASCADE").count(), 2);
+}
@@ -0,0 +1,80 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use liter::database;
+use liter::leader::{
+	Election,
+	Leadership
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn expired_lease() -> SqlResult<()> {
+	#[database]
+	struct Db (Leadership);
+
+	let db = Db::create_in_memory()?;
+	let lease = Duration::from_secs(60);
+	let lost = Cell::new(0);
+	let mut first = Election::new(&db, "cron", lease)
+		.on_loss(|| lost.set(lost.get() + 1));
+	let mut second = Election::new(&db, "cron", lease);
+
+	assert!(first.heartbeat()?);
+	assert!(first.is_leader());
+	assert!(!second.heartbeat()?);
+
+	// the leader stopped sending heartbeats
+	db.execute("UPDATE leadership SET expires = 0", &())?;
+	assert!(second.heartbeat()?);
+	assert!(first.is_leader());
+	assert_eq!(lost.get(), 0);
+	// only noticed at the next heartbeat
+	assert!(!first.heartbeat()?);
+	assert!(!first.is_leader());
+	assert_eq!(lost.get(), 1);
+	assert!(!first.heartbeat()?);
+	assert_eq!(lost.get(), 1);
+	Ok(())
+}
+
+#[test]
+fn resign_and_drop() -> SqlResult<()> {
+	#[database]
+	struct Db (Leadership);
+
+	let db = Db::create_in_memory()?;
+	let lease = Duration::from_secs(60);
+	let lost = Cell::new(0);
+	let mut first = Election::new(&db, "cron", lease)
+		.on_loss(|| lost.set(lost.get() + 1));
+	// resigning without leading changes nothing
+	first.resign()?;
+	assert_eq!(lost.get(), 0);
+	assert!(first.heartbeat()?);
+	first.resign()?;
+	assert_eq!(lost.get(), 1);
+	assert_eq!(db.count::<Leadership>()?, 0);
+
+	let mut second = Election::new(&db, "cron", lease);
+	assert!(second.heartbeat()?);
+	drop(second);
+	assert!(first.heartbeat()?);
+	Ok(())
+}
+
+#[test]
+fn roles() -> SqlResult<()> {
+	#[database]
+	struct Db (Leadership);
+
+	let db = Db::create_in_memory()?;
+	let lease = Duration::from_secs(60);
+	let mut cron = Election::new(&db, "cron", lease);
+	let mut mailer = Election::new(&db, "mailer", lease);
+	assert!(cron.heartbeat()?);
+	assert!(mailer.heartbeat()?);
+	assert_eq!(db.count::<Leadership>()?, 2);
+	Ok(())
+}
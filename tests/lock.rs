@@ -0,0 +1,59 @@
+use std::time::{
+	Duration,
+	Instant
+};
+
+use liter::database;
+use liter::lock::AdvisoryLock;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn released_on_drop() -> SqlResult<()> {
+	#[database]
+	struct Db (AdvisoryLock);
+
+	let db = Db::create_in_memory()?;
+	let guard = db.try_advisory_lock("a")?.unwrap();
+	assert_eq!(guard.name(), "a");
+	// locks are independent of each other
+	let other = db.try_advisory_lock("b")?.unwrap();
+	assert!(db.try_advisory_lock("a")?.is_none());
+	drop(guard);
+	assert_eq!(db.get_all::<AdvisoryLock>()?.len(), 1);
+	assert!(db.try_advisory_lock("a")?.is_some());
+	assert!(other.release()?);
+	assert_eq!(db.count::<AdvisoryLock>()?, 0);
+	Ok(())
+}
+
+#[test]
+fn timeout() -> SqlResult<()> {
+	#[database]
+	struct Db (AdvisoryLock);
+
+	let db = Db::create_in_memory()?;
+	let _guard = db.advisory_lock("a", Duration::ZERO)?.unwrap();
+	let start = Instant::now();
+	assert!(db.advisory_lock("a", Duration::from_millis(120))?.is_none());
+	assert!(start.elapsed() >= Duration::from_millis(120));
+	Ok(())
+}
+
+#[test]
+fn force_unlock() -> SqlResult<()> {
+	#[database]
+	struct Db (AdvisoryLock);
+
+	let db = Db::create_in_memory()?;
+	assert!(!db.force_unlock("a")?);
+	let stale = db.try_advisory_lock("a")?.unwrap();
+	assert!(db.force_unlock("a")?);
+
+	let current = db.try_advisory_lock("a")?.unwrap();
+	// the stale guard doesn't release a lock it no longer holds
+	assert!(!stale.release()?);
+	assert!(db.try_advisory_lock("a")?.is_none());
+	assert!(current.release()?);
+	Ok(())
+}
@@ -0,0 +1,136 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::types::money::Money;
+use rusqlite::Result as SqlResult;
+
+
+#[database]
+struct Db (Payment);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Payment {
+	#[key]
+	id: Id,
+	amount: Money<2>
+}
+
+fn payment(id: i64, cents: i64) -> Payment {
+	Payment { id: Id::from_i64(id), amount: Money::from_minor(cents) }
+}
+
+#[test]
+fn money_round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let amounts = [i64::MIN, -1999, -1, 0, 1, 1999, 10_000_000, i64::MAX];
+	for (id, cents) in amounts.iter().enumerate().rev() {
+		db.insert(&payment(id as i64, *cents))?;
+	}
+	let mut payments = db.get_all::<Payment>()?;
+	payments.sort_by_key(|payment| payment.id.get());
+	let expected: Vec<Payment> = amounts.iter()
+		.enumerate()
+		.map(|(id, cents)| payment(id as i64, *cents))
+		.collect();
+	assert_eq!(payments, expected);
+
+	let sorted: Vec<Money<2>> = db.query_all("SELECT amount FROM payment ORDER BY amount")?;
+	assert_eq!(sorted, amounts.map(Money::from_minor));
+	Ok(())
+}
+
+#[test]
+fn money_is_exact() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	// 0.1 + 0.2 isn't 0.3 as f64
+	db.insert(&payment(1, 10))?;
+	db.insert(&payment(2, 20))?;
+	let total: Money<2> = db.query_one("SELECT sum(amount) FROM payment")?;
+	assert_eq!(total, Money::from_minor(30));
+	assert_eq!(total.to_string(), "0.30");
+	Ok(())
+}
+
+#[test]
+fn money_rejects_fractions() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	assert!(db.execute("INSERT INTO payment VALUES (1, '12.34')", &()).is_err());
+	assert!(db.execute("INSERT INTO payment VALUES (1, 12.34)", &()).is_err());
+	assert!(db.get_all::<Payment>()?.is_empty());
+	Ok(())
+}
+
+#[test]
+fn money_arithmetic() {
+	let max = Money::<2>::from_minor(i64::MAX);
+	let min = Money::<2>::from_minor(i64::MIN);
+	assert_eq!(max.checked_add(Money::from_minor(1)), None);
+	assert_eq!(min.checked_sub(Money::from_minor(1)), None);
+	assert_eq!(min.checked_neg(), None);
+	assert_eq!(max.checked_neg(), Some(Money::from_minor(-i64::MAX)));
+
+	assert_eq!(Money::<3>::FACTOR, 1000);
+	assert_eq!(Money::<3>::from_minor(-12_345).major(), -12);
+	assert_eq!(Money::<3>::from_minor(-12_345).to_string(), "-12.345");
+	assert_eq!(Money::<0>::from_minor(7).to_string(), "7");
+	assert_eq!(Money::<18>::from_minor(1).to_string(), "0.000000000000000001");
+}
+
+#[test]
+#[should_panic = "overflow"]
+fn money_overflow() {
+	let _ = Money::<2>::from_minor(i64::MAX) + Money::from_minor(1);
+}
+
+#[cfg(feature = "rust_decimal")]
+mod decimal {
+	use liter::types::money::DecimalText;
+
+	use super::*;
+
+	#[database]
+	struct Db (Rate);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Rate {
+		#[key]
+		id: Id,
+		value: DecimalText
+	}
+
+	fn rate(id: i64, value: &str) -> Rate {
+		Rate { id: Id::from_i64(id), value: DecimalText(value.parse().unwrap()) }
+	}
+
+	#[test]
+	fn round_trip() -> SqlResult<()> {
+		let db = Db::create_in_memory()?;
+		let rates = [rate(1, "12.340"), rate(2, "-0.5"), rate(3, "79228162514264337593543950335")];
+		for rate in &rates {
+			db.insert(rate)?;
+		}
+		assert_eq!(db.get_all::<Rate>()?, rates);
+
+		// stored as written, including the trailing zero
+		let text: String = db.query_one("SELECT value FROM rate WHERE id = 1")?;
+		assert_eq!(text, "12.340");
+		Ok(())
+	}
+
+	#[test]
+	fn check() -> SqlResult<()> {
+		let db = Db::create_in_memory()?;
+		for (id, valid) in ["0", "-1", "1.5", "-0.25", ".5"].into_iter().enumerate() {
+			db.execute("INSERT INTO rate VALUES (?, ?)", &(id as i64, valid))?;
+		}
+		for invalid in ["", "abc", "1e5", "1.2.3", "1-2", "--1", "-", ".", "1,5", " 1"] {
+			assert!(
+				db.execute("INSERT INTO rate VALUES (100, ?)", &invalid.to_string()).is_err(),
+				"{invalid:?} was accepted"
+			);
+		}
+		Ok(())
+	}
+}
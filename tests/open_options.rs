@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::Path;
+use std::time::{
+	Duration,
+	Instant
+};
+
+use liter::{
+	Database,
+	Id,
+	Ref,
+	Table,
+	database
+};
+use liter::open::Synchronous;
+use rusqlite::{
+	OpenFlags,
+	Result as SqlResult
+};
+
+
+fn remove(path: &Path) {
+	for suffix in ["", "-wal", "-shm", "-journal"] {
+		let _ = fs::remove_file(format!("{}{suffix}", path.display()));
+	}
+}
+
+#[test]
+fn pragmas() -> SqlResult<()> {
+	#[database]
+	struct Db (Artist, Song);
+
+	#[derive(Table)]
+	struct Artist {
+		#[key]
+		id: Id
+	}
+	#[derive(Table)]
+	struct Song {
+		#[key]
+		id: Id,
+		artist: Ref<Artist>
+	}
+
+	let path = std::env::temp_dir().join("liter_open_options_pragmas.db");
+	remove(&path);
+	// the default pragma is replaced regardless of case
+	let db = Database::<Db>::options()
+		.pragma("FOREIGN_KEYS", "off")
+		.init(&path)?;
+	db.execute("INSERT INTO song VALUES (1, 7)", &())?;
+	drop(db);
+	let db = Db::open(&path)?;
+	assert!(db.execute("INSERT INTO song VALUES (2, 7)", &()).is_err());
+	drop(db);
+
+	let synchronous = |db: &Database<Db>| {
+		db.pragma_query_value(None, "synchronous", |row| row.get::<_, i64>(0))
+	};
+	let db = Database::<Db>::options().wal().open(&path)?;
+	assert_eq!(synchronous(&db)?, 1);
+	drop(db);
+	// an explicit synchronous level is kept, before or after
+	let db = Database::<Db>::options().synchronous(Synchronous::Extra).wal().open(&path)?;
+	assert_eq!(synchronous(&db)?, 3);
+	drop(db);
+	let db = Database::<Db>::options().wal().synchronous(Synchronous::Off).open(&path)?;
+	assert_eq!(synchronous(&db)?, 0);
+	drop(db);
+
+	remove(&path);
+	Ok(())
+}
+
+#[test]
+fn flags() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Item {
+		name: String
+	}
+
+	let path = std::env::temp_dir().join("liter_open_options_flags.db");
+	remove(&path);
+	// opening doesn't create the file
+	assert!(Database::<Db>::options().open(&path).is_err());
+	assert!(!path.exists());
+
+	let db = Database::<Db>::options().no_mutex(false).init(&path)?;
+	db.insert(&Item { name: "lamp".to_string() })?;
+	drop(db);
+	// the tables already exist
+	assert!(Database::<Db>::options().init(&path).is_err());
+
+	let reader = Database::<Db>::options().read_only().open(&path)?;
+	assert_eq!(reader.count::<Item>()?, 1);
+	assert!(reader.insert(&Item { name: "chair".to_string() }).is_err());
+	drop(reader);
+
+	// URIs are still understood when the flags don't ask for them
+	let db = Database::<Db>::options()
+		.flags(OpenFlags::SQLITE_OPEN_READ_WRITE)
+		.open_uri(format!("file:{}?mode=ro", path.display()))?;
+	assert!(db.insert(&Item { name: "chair".to_string() }).is_err());
+	drop(db);
+	remove(&path);
+	Ok(())
+}
+
+#[test]
+fn busy_timeout() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table)]
+	struct Item {
+		name: String
+	}
+
+	let path = std::env::temp_dir().join("liter_open_options_busy.db");
+	remove(&path);
+	let holder = Db::init(&path)?;
+	holder.execute_batch("BEGIN IMMEDIATE;")?;
+	let item = Item { name: "waiting".to_string() };
+
+	let impatient = Database::<Db>::options().busy_timeout(Duration::ZERO).open(&path)?;
+	let start = Instant::now();
+	assert!(impatient.insert(&item).is_err());
+	assert!(start.elapsed() < Duration::from_millis(200));
+
+	let patient = Database::<Db>::options().busy_timeout(Duration::from_millis(300)).open(&path)?;
+	let start = Instant::now();
+	assert!(patient.insert(&item).is_err());
+	assert!(start.elapsed() >= Duration::from_millis(300));
+
+	holder.execute_batch("COMMIT;")?;
+	patient.insert(&item)?;
+	drop((holder, impatient, patient));
+	remove(&path);
+	Ok(())
+}
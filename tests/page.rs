@@ -0,0 +1,114 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::page::Page;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn boundaries() -> SqlResult<()> {
+	#[database]
+	struct Db (Comment);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Comment {
+		#[key]
+		id: Id
+	}
+
+	let db = Db::create_in_memory()?;
+	let empty = db.page::<Comment>(0, 10)?;
+	assert_eq!(empty, Page { items: Vec::new(), total: 0, page: 0, page_size: 10 });
+	assert_eq!(empty.page_count(), 0);
+	assert!(!empty.has_previous());
+	assert!(!empty.has_next());
+
+	for _ in 0..20 {
+		db.insert(&Comment { id: Id::NULL })?;
+	}
+	let ids = |page: &Page<Comment>| -> Vec<i64> {
+		page.items.iter().map(|comment| comment.id.get().unwrap()).collect()
+	};
+	let first = db.page::<Comment>(0, 10)?;
+	assert_eq!(ids(&first), (1..=10).collect::<Vec<_>>());
+	assert!(!first.has_previous());
+	assert!(first.has_next());
+	// the last page is full
+	let last = db.page::<Comment>(1, 10)?;
+	assert_eq!(ids(&last), (11..=20).collect::<Vec<_>>());
+	assert_eq!(last.page_count(), 2);
+	assert!(last.has_previous());
+	assert!(!last.has_next());
+
+	let beyond = db.page::<Comment>(2, 10)?;
+	assert!(beyond.items.is_empty());
+	assert_eq!(beyond.total, 20);
+	assert!(!beyond.has_next());
+	// doesn't overflow into the first page
+	let far = db.page::<Comment>(u64::MAX, 10)?;
+	assert!(far.items.is_empty());
+	assert!(!far.has_next());
+
+	assert_eq!(db.page::<Comment>(0, u64::MAX)?.items.len(), 20);
+	assert!(db.page::<Comment>(0, 0)?.items.is_empty());
+	assert_eq!(db.page::<Comment>(6, 3)?.items.len(), 2);
+	Ok(())
+}
+
+#[test]
+fn keyset() -> SqlResult<()> {
+	#[database]
+	struct Db (Tag);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Tag {
+		#[key]
+		name: String
+	}
+
+	let db = Db::create_in_memory()?;
+	assert!(db.page_after::<Tag>(None, 10)?.is_empty());
+	// ordered by key, not by insertion
+	for name in ["pear", "apple", "fig", "kiwi"] {
+		db.insert(&Tag { name: name.to_string() })?;
+	}
+	let names = |tags: Vec<Tag>| -> Vec<String> {
+		tags.into_iter().map(|tag| tag.name).collect()
+	};
+	assert_eq!(names(db.page_after::<Tag>(None, 2)?), ["apple", "fig"]);
+	assert_eq!(names(db.page_after::<Tag>(Some(&"fig".to_string()), 2)?), ["kiwi", "pear"]);
+	// the last key doesn't have to exist (anymore)
+	assert_eq!(names(db.page_after::<Tag>(Some(&"banana".to_string()), 1)?), ["fig"]);
+	assert!(db.page_after::<Tag>(Some(&"pear".to_string()), 10)?.is_empty());
+	assert!(db.page_after::<Tag>(None, 0)?.is_empty());
+	assert_eq!(db.page_after::<Tag>(None, u64::MAX)?.len(), 4);
+	Ok(())
+}
+
+#[test]
+fn keyset_composite() -> SqlResult<()> {
+	#[database]
+	struct Db (Event);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Event {
+		#[key]
+		day: u32,
+		#[key]
+		seq: u32
+	}
+
+	let db = Db::create_in_memory()?;
+	for (day, seq) in [(3, 1), (1, 10), (2, 1), (1, 2)] {
+		db.insert(&Event { day, seq })?;
+	}
+	let keys = |events: Vec<Event>| -> Vec<(u32, u32)> {
+		events.into_iter().map(|event| (event.day, event.seq)).collect()
+	};
+	// compared as a whole: (1, 10) comes before (2, 1)
+	assert_eq!(keys(db.page_after::<Event>(Some(&(1, 2)), 2)?), [(1, 10), (2, 1)]);
+	assert_eq!(keys(db.page_after::<Event>(Some(&(1, 99)), 5)?), [(2, 1), (3, 1)]);
+	Ok(())
+}
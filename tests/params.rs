@@ -0,0 +1,112 @@
+use liter::{
+	Bind,
+	Binder,
+	Id,
+	Table,
+	database,
+	params
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[test]
+fn reused_and_reordered() -> SqlResult<()> {
+	#[database]
+	struct Db (Reading);
+
+	#[derive(Table)]
+	struct Reading {
+		#[key]
+		id: Id,
+		sensor: String,
+		celsius: f64
+	}
+
+	params! {
+		struct Around {
+			sensor: String,
+			celsius: f64,
+			/// Bound as `NULL` when missing
+			margin: Option<f64>
+		}
+	}
+
+	let db = Db::create_in_memory()?;
+	for celsius in [18.5, 21.0, 25.5] {
+		db.insert(&Reading { id: Id::NULL, sensor: "attic".to_string(), celsius })?;
+	}
+	// :celsius appears twice and the parameters aren't in the order of the fields
+	let sql = "SELECT count(*) FROM reading \
+		WHERE celsius BETWEEN :celsius - coalesce(:margin, 0) AND :celsius + coalesce(:margin, 0) \
+		AND sensor = :sensor";
+	let around = |celsius, margin| Around { sensor: "attic".to_string(), celsius, margin };
+	assert_eq!(db.query_one_with::<u32, _>(sql, &around(21.0, Some(3.0)))?, 2);
+	assert_eq!(db.query_one_with::<u32, _>(sql, &around(21.0, None))?, 1);
+	assert_eq!(db.query_one_with::<u32, _>(sql, &around(30.0, Some(1.0)))?, 0);
+	Ok(())
+}
+
+#[test]
+fn errors() -> SqlResult<()> {
+	#[database]
+	struct Db (Reading);
+
+	#[derive(Table)]
+	struct Reading {
+		sensor: String
+	}
+
+	params! {
+		struct Sensor {
+			sensor: String
+		}
+	}
+	params! {
+		struct Pair {
+			pair: (i64, i64)
+		}
+	}
+
+	let db = Db::create_in_memory()?;
+	let sensor = Sensor { sensor: "attic".to_string() };
+	assert!(matches!(
+		db.query_one_with::<u32, _>("SELECT count(*) FROM reading WHERE sensor = :name", &sensor),
+		Err(Error::InvalidParameterName(name)) if name == ":sensor"
+	));
+	// a named parameter can only hold one column
+	assert!(matches!(
+		db.query_one_with::<i64, _>("SELECT :pair", &Pair { pair: (1, 2) }),
+		Err(Error::InvalidParameterCount(2, 1))
+	));
+	Ok(())
+}
+
+#[test]
+fn other_prefixes() -> SqlResult<()> {
+	#[database]
+	struct Db (Reading);
+
+	#[derive(Table)]
+	struct Reading {
+		sensor: String
+	}
+
+	struct Mixed;
+	impl Bind for Mixed {
+		const COLUMNS: usize = 3;
+		fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+			binder.bind_named("$second", &2)?;
+			// continues after the named parameter
+			binder.bind(&3)?;
+			binder.bind_named("@first", &1)
+		}
+	}
+
+	let db = Db::create_in_memory()?;
+	let row: (i64, i64, i64) = db.query_one_with("SELECT @first, $second, ?", &Mixed)?;
+	assert_eq!(row, (1, 2, 3));
+	Ok(())
+}
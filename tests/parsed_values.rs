@@ -0,0 +1,45 @@
+use std::net::{
+	IpAddr,
+	SocketAddr
+};
+use std::path::PathBuf;
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn round_trip() -> SqlResult<()> {
+	#[database]
+	struct Db (Server);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Server {
+		#[key]
+		id: Id,
+		ip: IpAddr,
+		listen: SocketAddr,
+		root: Option<PathBuf>
+	}
+	let db = Db::create_in_memory()?;
+
+	let server = Server {
+		id: Id::from_i64(1),
+		ip: "::1".parse().unwrap(),
+		listen: "127.0.0.1:8080".parse().unwrap(),
+		root: Some(PathBuf::from("/srv/www"))
+	};
+	db.insert(&server)?;
+	assert_eq!(db.get_all::<Server>()?, [server]);
+
+	let listen: String = db.query_one("SELECT listen FROM server")?;
+	assert_eq!(listen, "127.0.0.1:8080");
+
+	db.execute("UPDATE server SET ip = 'localhost'", &())?;
+	assert!(db.get_all::<Server>().is_err());
+	Ok(())
+}
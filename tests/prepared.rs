@@ -0,0 +1,74 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::prepared::PreparedQuery;
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[test]
+fn parameter_count() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table)]
+	struct Item {
+		#[key]
+		id: Id,
+		data: String
+	}
+
+	let db = Db::create_in_memory()?;
+	assert!(matches!(
+		db.prepare_typed::<(i64, i64), String>("SELECT data FROM item WHERE id = ?"),
+		Err(Error::InvalidParameterCount(2, 1))
+	));
+	assert!(matches!(
+		db.prepare_typed::<(), String>("SELECT data FROM item WHERE id = ?"),
+		Err(Error::InvalidParameterCount(0, 1))
+	));
+	assert!(db.prepare_typed::<(), String>("SELECT data FROM nothing").is_err());
+	Ok(())
+}
+
+#[test]
+fn reruns() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table)]
+	struct Item {
+		#[key]
+		id: Id,
+		data: Option<String>
+	}
+
+	let db = Db::create_in_memory()?;
+	let mut insert: PreparedQuery<Option<&str>, i64> =
+		db.prepare_typed("INSERT INTO item (data) VALUES (?)")?;
+	assert_eq!(insert.execute(&Some("a"))?, 1);
+	// isn't left bound to the previous "a"
+	assert_eq!(insert.execute(&None)?, 1);
+	assert_eq!(insert.execute(&Some("b"))?, 1);
+	drop(insert);
+
+	let mut ids: PreparedQuery<Option<&str>, i64> =
+		db.prepare_typed("SELECT id FROM item WHERE data IS ? ORDER BY id")?;
+	assert_eq!(ids.all(&Some("a"))?, [1]);
+	assert_eq!(ids.all(&None)?, [2]);
+	assert!(ids.all(&Some("c"))?.is_empty());
+	assert!(matches!(ids.one(&Some("c")), Err(Error::QueryReturnedNoRows)));
+
+	let mut all: PreparedQuery<(), i64> = db.prepare_typed("SELECT id FROM item ORDER BY id")?;
+	// stopping early doesn't keep the next run from starting over
+	let first_two = all.iter(&(), |rows| rows.take(2).collect::<SqlResult<Vec<_>>>())??;
+	assert_eq!(first_two, [1, 2]);
+	assert_eq!(all.all(&())?, [1, 2, 3]);
+	assert_eq!(all.first(&())?, Some(1));
+	assert_eq!(all.statement().expanded_sql().unwrap(), "SELECT id FROM item ORDER BY id");
+	Ok(())
+}
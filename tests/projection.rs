@@ -0,0 +1,93 @@
+use liter::{
+	Id,
+	Ref,
+	Table,
+	database,
+	projection
+};
+use liter::projection::Projection;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn field_order() -> SqlResult<()> {
+	#[database]
+	struct Db (Artist, Song);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Artist {
+		#[key]
+		id: Id,
+		name: String
+	}
+	#[derive(Table)]
+	struct Song {
+		#[key]
+		id: Id,
+		artist: Ref<Artist>,
+		title: String,
+		audio: Vec<u8>
+	}
+
+	projection! {
+		#[select(from = Song)]
+		#[derive(Debug, PartialEq)]
+		struct Listing {
+			/// Fields don't have to be in the order of the table
+			title: String,
+			artist: Ref<Artist>
+		}
+	}
+
+	assert_eq!(Listing::SELECT, "SELECT title, artist FROM \"song\"");
+	let db = Db::create_in_memory()?;
+	db.insert(&Artist { id: Id::new(1), name: "Nina".to_string() })?;
+	db.insert(&Song {
+		id: Id::new(1),
+		artist: Ref(Id::new(1)),
+		title: "Feeling Good".to_string(),
+		audio: vec![0; 1000]
+	})?;
+	assert_eq!(db.select_all::<Listing>()?, [
+		Listing { title: "Feeling Good".to_string(), artist: Ref(Id::new(1)) }
+	]);
+	Ok(())
+}
+
+#[test]
+fn select_where() -> SqlResult<()> {
+	#[database]
+	struct Db (Photo);
+
+	#[derive(Table)]
+	struct Photo {
+		#[key]
+		id: Id,
+		title: String,
+		year: u16,
+		jpeg: Vec<u8>
+	}
+
+	projection! {
+		#[select(from = Photo)]
+		#[derive(Debug, PartialEq)]
+		struct Dated {
+			id: Id,
+			year: u16
+		}
+	}
+
+	let db = Db::create_in_memory()?;
+	for (id, year) in [(1, 1999), (2, 2005), (3, 2011)] {
+		db.insert(&Photo { id: Id::new(id), title: String::new(), year, jpeg: Vec::new() })?;
+	}
+	let dated = |id, year| Dated { id: Id::new(id), year };
+	assert_eq!(
+		db.select_where::<Dated, _>("year > ? ORDER BY year DESC", &2000)?,
+		[dated(3, 2011), dated(2, 2005)]
+	);
+	assert!(db.select_where::<Dated, _>("year > ?", &2020)?.is_empty());
+	// the condition is plain SQL, so errors in it are only found when running it
+	assert!(db.select_where::<Dated, _>("no_such_column = 1", &()).is_err());
+	Ok(())
+}
@@ -0,0 +1,74 @@
+use liter::database;
+use liter::ratelimit::{
+	Policy,
+	RateLimitBucket
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn refill() -> SqlResult<()> {
+	#[database]
+	struct Db (RateLimitBucket);
+
+	let db = Db::create_in_memory()?;
+	let limiter = db.limiter();
+	let policy = Policy::new(3.0, 1.0 / 60.0);
+	for _ in 0..3 {
+		assert!(limiter.check_and_consume("a", &policy)?);
+	}
+	assert!(!limiter.check_and_consume("a", &policy)?);
+
+	// two minutes ago, two tokens have been refilled since
+	db.execute("UPDATE ratelimitbucket SET updated = updated - 120", &())?;
+	assert!(limiter.check_and_consume("a", &policy)?);
+	assert!(limiter.check_and_consume("a", &policy)?);
+	assert!(!limiter.check_and_consume("a", &policy)?);
+
+	// a bucket is never filled beyond its capacity
+	db.execute("UPDATE ratelimitbucket SET updated = updated - 3600", &())?;
+	for _ in 0..3 {
+		assert!(limiter.check_and_consume("a", &policy)?);
+	}
+	assert!(!limiter.check_and_consume("a", &policy)?);
+	Ok(())
+}
+
+#[test]
+fn costs() -> SqlResult<()> {
+	#[database]
+	struct Db (RateLimitBucket);
+
+	let db = Db::create_in_memory()?;
+	let limiter = db.limiter();
+	let policy = Policy::new(5.0, 0.0);
+	// more than fits into the bucket is never allowed, and doesn't create the bucket
+	assert!(!limiter.check_and_consume("b", &policy.with_cost(6.0))?);
+	assert_eq!(db.count::<RateLimitBucket>()?, 0);
+
+	assert!(limiter.check_and_consume("b", &policy.with_cost(4.0))?);
+	// a denied check doesn't take any tokens
+	assert!(!limiter.check_and_consume("b", &policy.with_cost(2.0))?);
+	assert!(limiter.check_and_consume("b", &policy)?);
+	assert!(!limiter.check_and_consume("b", &policy)?);
+
+	// other buckets aren't affected
+	assert!(limiter.check_and_consume("c", &policy.with_cost(5.0))?);
+	Ok(())
+}
+
+#[test]
+fn reset() -> SqlResult<()> {
+	#[database]
+	struct Db (RateLimitBucket);
+
+	let db = Db::create_in_memory()?;
+	let limiter = db.limiter();
+	let policy = Policy::new(1.0, 0.0);
+	assert!(!limiter.reset("d")?);
+	assert!(limiter.check_and_consume("d", &policy)?);
+	assert!(!limiter.check_and_consume("d", &policy)?);
+	assert!(limiter.reset("d")?);
+	assert!(limiter.check_and_consume("d", &policy)?);
+	Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::path::{
+	Path,
+	PathBuf
+};
+
+use liter::{
+	Database,
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[database]
+struct Db (Sale);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Sale {
+	#[key]
+	id: Id,
+	cents: u64
+}
+
+fn sale(id: i64, cents: u64) -> Sale {
+	Sale { id: Id::new(id), cents }
+}
+
+/// Path of a new database with two sales
+fn sales(name: &str) -> SqlResult<PathBuf> {
+	let path = std::env::temp_dir().join(name);
+	let _ = fs::remove_file(&path);
+	let db = Db::init(&path)?;
+	db.insert(&sale(1, 1250))?;
+	db.insert(&sale(2, 300))?;
+	Ok(path)
+}
+
+fn remove(path: &Path) {
+	fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn missing_file() {
+	let path = std::env::temp_dir().join("liter_read_only_missing.db");
+	let _ = fs::remove_file(&path);
+	// isn't created
+	assert!(Db::open_read_only(&path).is_err());
+	assert!(!path.exists());
+}
+
+#[test]
+fn reads() -> SqlResult<()> {
+	let path = sales("liter_read_only_reads.db")?;
+	let report = Db::open_read_only(&path)?;
+	assert_eq!(report.get::<Sale>(Id::new(3))?, None);
+	assert!(report.get_many::<Sale>(&[])?.is_empty());
+	assert_eq!(report.get_many::<Sale>(&[Id::new(2), Id::new(3)])?, [sale(2, 300)]);
+	assert!(!report.exists::<Sale>(&Id::new(3))?);
+	assert_eq!(report.count_where::<Sale, _>("cents > ?", &1000)?, 1);
+	assert_eq!(report.query_first::<u64, _>("SELECT cents FROM sale WHERE id = ?", &3)?, None);
+	drop(report);
+	remove(&path);
+	Ok(())
+}
+
+#[test]
+fn writes_fail() -> SqlResult<()> {
+	let path = sales("liter_read_only_writes.db")?;
+	let report = Db::open_read_only(&path)?;
+	for sql in [
+		"INSERT INTO sale VALUES (3, 1) RETURNING id",
+		"UPDATE sale SET cents = 0 RETURNING id",
+		"PRAGMA user_version = 5"
+	] {
+		assert!(report.query::<i64, _>(sql, &()).is_err(), "{sql}");
+	}
+	assert_eq!(report.get_all::<Sale>()?, [sale(1, 1250), sale(2, 300)]);
+	drop(report);
+	remove(&path);
+	Ok(())
+}
+
+#[test]
+fn sees_later_writes() -> SqlResult<()> {
+	let path = sales("liter_read_only_later.db")?;
+	let report = Db::open_read_only(&path)?;
+	let db: Database<Db> = Db::open(&path)?;
+	db.insert(&sale(3, 5))?;
+	assert_eq!(report.count::<Sale>()?, 3);
+	drop((db, report));
+	remove(&path);
+	Ok(())
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "regex")]
+
+use liter::{
+	Column,
+	Table,
+	database
+};
+use liter::column::Affinity;
+use liter::types::impl_from_to_sql_2;
+use liter::value::Check;
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+
+
+#[test]
+fn operator() -> SqlResult<()> {
+	#[database]
+	struct Db (Word);
+
+	#[derive(Table)]
+	struct Word {
+		text: Option<String>
+	}
+
+	let db = Db::create_in_memory()?;
+	for text in [Some("αλφα"), Some("alpha"), Some("Alpha"), None] {
+		db.insert(&Word { text: text.map(str::to_string) })?;
+	}
+	let count = |pattern: &str| -> SqlResult<u32> {
+		db.query_one_with("SELECT count(*) FROM word WHERE text REGEXP ?", &pattern)
+	};
+	// NULL doesn't match anything, not even everything
+	assert_eq!(count("")?, 3);
+	assert_eq!(count("^[a-z]+$")?, 1);
+	assert_eq!(count("(?i)^alpha$")?, 2);
+	assert_eq!(count(r"^\p{Greek}+$")?, 1);
+	assert_eq!(db.query_one::<Option<bool>>("SELECT NULL REGEXP 'a'")?, None);
+	// invalid patterns are errors, not non-matches
+	assert!(count("[a-").is_err());
+	Ok(())
+}
+
+#[test]
+fn check() -> SqlResult<()> {
+	#[database]
+	struct Db (Saying);
+
+	/// A quote in the pattern has to be escaped in the CHECK
+	#[derive(Debug, PartialEq)]
+	struct Contraction(String);
+	impl FromSql for Contraction {
+		fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+			String::column_result(value).map(Contraction)
+		}
+	}
+	impl ToSql for Contraction {
+		fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+			self.0.to_sql()
+		}
+	}
+	impl_from_to_sql_2!(Contraction);
+	impl Column for Contraction {
+		const AFFINITY: Affinity = Affinity::Text;
+		const CHECKS: &'static [Check] = &[Check::Regex("^[a-z]+'[a-z]+$")];
+	}
+
+	#[derive(Table)]
+	struct Saying {
+		required: Contraction,
+		optional: Option<Contraction>
+	}
+
+	assert!(Saying::CREATE_TABLE.contains("REGEXP '^[a-z]+''[a-z]+$'"), "{}", Saying::CREATE_TABLE);
+	let db = Db::create_in_memory()?;
+	let saying = |required: &str, optional: Option<&str>| Saying {
+		required: Contraction(required.to_string()),
+		optional: optional.map(|text| Contraction(text.to_string()))
+	};
+	db.insert(&saying("it's", None))?;
+	db.insert(&saying("don't", Some("can't")))?;
+	assert!(db.insert(&saying("its", None)).is_err());
+	assert!(db.insert(&saying("it's", Some("cant"))).is_err());
+	assert_eq!(db.count::<Saying>()?, 2);
+	Ok(())
+}
@@ -0,0 +1,175 @@
+use liter::{
+	Id,
+	Ref,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn resolve_refs() -> SqlResult<()> {
+	#[database]
+	struct Db (Author, Book);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Author {
+		#[key]
+		id: Id,
+		name: String
+	}
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Book {
+		#[key]
+		id: Id,
+		author: Ref<Author>
+	}
+	let db = Db::create_in_memory()?;
+
+	let mut authors = Vec::new();
+	for name in ["Le Guin", "Pratchett", "Jemisin"] {
+		let mut author = Author { id: Id::NULL, name: name.to_string() };
+		db.create(&mut author)?;
+		authors.push(author);
+	}
+	let mut book = Book { id: Id::NULL, author: Ref::make_ref(&authors[1]) };
+	db.create(&mut book)?;
+
+	assert_eq!(db.resolve(&book.author)?, Some(authors[1].clone()));
+	assert_eq!(db.resolve(&Ref::<Author>(Id::new(99)))?, None);
+
+	let refs = [
+		Ref::make_ref(&authors[2]),
+		Ref(Id::new(99)),
+		Ref::make_ref(&authors[0]),
+		Ref::make_ref(&authors[2])
+	];
+	assert_eq!(db.resolve_all(&refs)?, [authors[2].clone(), authors[0].clone()]);
+	assert_eq!(db.resolve_all::<Author>(&[])?, []);
+	Ok(())
+}
+
+#[test]
+fn children() -> SqlResult<()> {
+	#[database]
+	struct Db (Playlist, Song, Mix);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Playlist {
+		#[key]
+		id: Id,
+		name: String
+	}
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Song {
+		#[key]
+		id: Id,
+		playlist: Ref<Playlist>,
+		title: String
+	}
+	/// References `Playlist` twice
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Mix {
+		#[key]
+		id: Id,
+		a: Ref<Playlist>,
+		b: Ref<Playlist>
+	}
+	let db = Db::create_in_memory()?;
+
+	let mut playlists = Vec::new();
+	for name in ["morning", "evening"] {
+		let mut playlist = Playlist { id: Id::NULL, name: name.to_string() };
+		db.create(&mut playlist)?;
+		playlists.push(playlist);
+	}
+	let mut songs = Vec::new();
+	for (playlist, title) in [(0, "Sunrise"), (1, "Nocturne"), (0, "Coffee")] {
+		let mut song = Song {
+			id: Id::NULL,
+			playlist: Ref::make_ref(&playlists[playlist]),
+			title: title.to_string()
+		};
+		db.create(&mut song)?;
+		songs.push(song);
+	}
+
+	assert_eq!(
+		db.children_of::<Song, Playlist>(&playlists[0].id)?,
+		[songs[0].clone(), songs[2].clone()]
+	);
+	assert_eq!(db.children_of::<Song, Playlist>(&Id::new(99))?, []);
+	assert!(matches!(
+		db.children_of::<Mix, Playlist>(&playlists[0].id),
+		Err(rusqlite::Error::InvalidQuery)
+	));
+	Ok(())
+}
+
+#[test]
+fn get_many_in_order() -> SqlResult<()> {
+	#[database]
+	struct Db (Word);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Word {
+		#[key]
+		language: String,
+		#[key]
+		text: String
+	}
+	let db = Db::create_in_memory()?;
+	let word = |language: &str, text: &str| Word {
+		language: language.to_string(),
+		text: text.to_string()
+	};
+	let key = |language: &str, text: &str| (language.to_string(), text.to_string());
+	for (language, text) in [("en", "cat"), ("de", "Katze"), ("fr", "chat")] {
+		db.insert(&word(language, text))?;
+	}
+
+	assert_eq!(
+		db.get_many::<Word>(&[key("fr", "chat"), key("en", "dog"), key("en", "cat"), key("fr", "chat")])?,
+		[word("fr", "chat"), word("en", "cat")]
+	);
+	assert_eq!(db.get_many::<Word>(&[])?, []);
+
+	// more keys than fit into a single statement
+	let keys: Vec<_> = (0..2000).map(|n| key("de", &n.to_string()))
+		.chain([key("de", "Katze")])
+		.collect();
+	assert_eq!(db.get_many::<Word>(&keys)?, [word("de", "Katze")]);
+	Ok(())
+}
+
+#[test]
+fn get_many_across_batches() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Item {
+		#[key]
+		id: Id,
+		value: i64
+	}
+	let db = Db::create_in_memory()?;
+	let item = |n: i64| Item { id: Id::new(n), value: n * 10 };
+	for n in 1..=1200 {
+		db.insert(&item(n))?;
+	}
+
+	// found in different batches than they are asked for in, with a missing key & a duplicate
+	let keys: Vec<Id> = (1..=1200).rev()
+		.chain([0, 1200, 600])
+		.map(Id::new)
+		.collect();
+	let expected: Vec<Item> = (1..=1200).rev().map(item).collect();
+	assert_eq!(db.get_many::<Item>(&keys)?, expected);
+
+	let refs: Vec<Ref<Item>> = [700, 3, 1201, 501, 500].into_iter()
+		.map(|n| Ref(Id::new(n)))
+		.collect();
+	assert_eq!(db.resolve_all(&refs)?, [item(700), item(3), item(501), item(500)]);
+	Ok(())
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "sealed")]
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::sealed::{
+	RowSeal,
+	Tampered
+};
+use rusqlite::Result as SqlResult;
+
+
+const KEY: &[u8] = b"test key";
+
+#[test]
+fn verify() -> SqlResult<()> {
+	#[database]
+	struct Db (Entitlement, RowSeal);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Entitlement {
+		#[key]
+		id: Id,
+		seats: u32
+	}
+
+	let db = Db::create_in_memory()?;
+	for (id, seats) in [(1, 5), (2, 10), (3, 1)] {
+		db.insert(&Entitlement { id: Id::new(id), seats })?;
+	}
+	assert_eq!(db.seal::<Entitlement>(KEY)?, 3);
+	assert!(db.verify_sealed::<Entitlement>(KEY)?.is_empty());
+	// another key doesn't match any of the seals
+	assert_eq!(db.verify_sealed::<Entitlement>(b"other key")?.len(), 3);
+
+	db.execute("UPDATE entitlement SET seats = 50 WHERE id = 2", &())?;
+	db.execute("DELETE FROM entitlement WHERE id = 3", &())?;
+	db.execute("INSERT INTO entitlement VALUES (4, 1)", &())?;
+	assert_eq!(db.verify_sealed::<Entitlement>(KEY)?, [
+		Tampered::Modified("2".to_string()),
+		Tampered::Unsealed("4".to_string()),
+		Tampered::Deleted("3".to_string())
+	]);
+
+	assert_eq!(db.get_sealed::<Entitlement>(&Id::new(1), KEY)?, Some(Entitlement { id: Id::new(1), seats: 5 }));
+	assert!(db.get_sealed::<Entitlement>(&Id::new(2), KEY).is_err());
+	assert!(db.get_sealed::<Entitlement>(&Id::new(4), KEY).is_err());
+	assert_eq!(db.get_sealed::<Entitlement>(&Id::new(3), KEY)?, None);
+
+	// resealing accepts the changes and forgets the deleted row
+	assert_eq!(db.seal::<Entitlement>(KEY)?, 3);
+	assert!(db.verify_sealed::<Entitlement>(KEY)?.is_empty());
+	assert_eq!(db.count::<RowSeal>()?, 3);
+	Ok(())
+}
+
+#[test]
+fn exact_reals() -> SqlResult<()> {
+	#[database]
+	struct Db (Price, RowSeal);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Price {
+		#[key]
+		item: String,
+		amount: f64
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Price { item: "gold".to_string(), amount: 0.1 + 0.2 })?;
+	db.seal::<Price>(KEY)?;
+	// quote() prints both as 0.3
+	db.execute("UPDATE price SET amount = 0.3", &())?;
+	assert_eq!(
+		db.verify_sealed::<Price>(KEY)?,
+		[Tampered::Modified("'gold'".to_string())]
+	);
+	Ok(())
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "zeroize")]
+
+use liter::{
+	Column,
+	Entry,
+	Table,
+	column::Affinity,
+	database,
+	types::impl_from_to_sql_2,
+	value::Check
+};
+use liter::types::secret::Secret;
+use rusqlite::Result as SqlResult;
+use rusqlite::types::{
+	FromSql,
+	FromSqlResult,
+	ToSql,
+	ToSqlOutput,
+	ValueRef
+};
+use zeroize::Zeroize;
+
+
+/// Six letter recovery code, compared case-insensitively
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Code(String);
+impl Column for Code {
+	const AFFINITY: Affinity = Affinity::Text;
+	const CHECKS: &'static [Check] = &[
+		Check::Length(6),
+		Check::Sql("NOT GLOB '*[^A-Za-z]*'")
+	];
+	const COLLATION: Option<&'static str> = Some("NOCASE");
+}
+impl ToSql for Code {
+	fn to_sql(&self) -> SqlResult<ToSqlOutput<'_>> {
+		self.0.to_sql()
+	}
+}
+impl FromSql for Code {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		String::column_result(value).map(Self)
+	}
+}
+impl_from_to_sql_2!(Code);
+impl Zeroize for Code {
+	fn zeroize(&mut self) {
+		self.0.zeroize();
+	}
+}
+
+#[database]
+struct Db (Account);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Account {
+	#[key]
+	name: String,
+	token: Secret<String>,
+	recovery: Secret<Code>,
+	key: Option<Secret<Vec<u8>>>
+}
+
+fn account(name: &str, token: &str, recovery: &str) -> Account {
+	Account {
+		name: name.to_string(),
+		token: Secret::new(token.to_string()),
+		recovery: Secret::new(Code(recovery.to_string())),
+		key: None
+	}
+}
+
+#[test]
+fn round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let accounts = [
+		account("alice", "hunter2", "abcDEF"),
+		Account { key: Some(Secret::new(vec![0, 1, 2, 255])), ..account("bob", "", "zzzzzz") }
+	];
+	for account in &accounts {
+		db.insert(account)?;
+	}
+	assert_eq!(db.get_all::<Account>()?, accounts);
+
+	// the value itself is stored as is
+	let stored: (String, String, Option<Vec<u8>>) = db.query_one(
+		"SELECT token, recovery, key FROM account WHERE name = 'bob'"
+	)?;
+	assert_eq!(stored, (String::new(), "zzzzzz".to_string(), Some(vec![0, 1, 2, 255])));
+	Ok(())
+}
+
+#[test]
+fn redacted() {
+	let account = Account {
+		key: Some(Secret::new(b"private".to_vec())),
+		..account("alice", "hunter2", "abcdef")
+	};
+	let debug = format!("{account:?} {account:#?}");
+	assert!(debug.contains("alice"));
+	for secret in ["hunter2", "abcdef", "112, 114, 105"] {
+		assert!(!debug.contains(secret), "{secret} in {debug}");
+	}
+	assert!(debug.contains("Secret([REDACTED])"));
+}
+
+#[test]
+fn checks_of_the_inner_type() -> SqlResult<()> {
+	assert!(Account::CREATE_TABLE.contains("COLLATE NOCASE"));
+
+	let db = Db::create_in_memory()?;
+	let valid = account("alice", "hunter2", "abcdef");
+	db.insert(&valid)?;
+	for invalid in ["abcde", "abcdefg", "abc123", "", "abc de"] {
+		assert!(db.insert(&account("bob", "token", invalid)).is_err(), "{invalid:?}");
+		assert!(
+			db.execute("UPDATE account SET recovery = ?", &invalid.to_string()).is_err(),
+			"{invalid:?}"
+		);
+	}
+	assert_eq!(db.get_all::<Account>()?, [valid]);
+	Ok(())
+}
+
+#[test]
+fn ordering() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let accounts = [
+		account("a", "token", "aaaaaa"),
+		account("b", "token", "BBBBBB"),
+		account("c", "token", "cccccc"),
+		account("d", "token", "DDDDDD")
+	];
+	for account in accounts.iter().rev() {
+		db.insert(account)?;
+	}
+
+	// with the collation of Code
+	let by_recovery: Vec<String> = db.query_all("SELECT name FROM account ORDER BY recovery")?;
+	assert_eq!(by_recovery, ["a", "b", "c", "d"]);
+	let equal: String = db.query_one("SELECT name FROM account WHERE recovery = 'bbbbbb'")?;
+	assert_eq!(equal, "b");
+	Ok(())
+}
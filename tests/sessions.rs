@@ -0,0 +1,47 @@
+use std::time::{
+	Duration,
+	SystemTime
+};
+
+use liter::{
+	SyncDatabase,
+	database
+};
+use liter::sessions::{
+	Session,
+	SessionStore
+};
+use rusqlite::Result as SqlResult;
+
+#[database]
+struct Db (Session);
+
+#[test]
+fn expiry() -> SqlResult<()> {
+	let db = SyncDatabase::new(Db::create_in_memory()?);
+	let hour = Duration::from_secs(3600);
+
+	db.save_session("old", "{}", SystemTime::now() - hour)?;
+	db.save_session("new", "[1, 2]", SystemTime::now() + hour)?;
+
+	assert_eq!(db.load_session("old")?, None);
+	assert_eq!(db.load_session("new")?.as_deref(), Some("[1, 2]"));
+	assert_eq!(db.get_all::<Session>()?.len(), 2);
+
+	assert_eq!(db.delete_expired_sessions()?, 1);
+	assert_eq!(db.get_all::<Session>()?.len(), 1);
+
+	db.save_session("new", "{\"replaced\": true}", SystemTime::now() + hour)?;
+	assert_eq!(
+		db.load_session("new")?.as_deref(),
+		Some("{\"replaced\": true}")
+	);
+
+	assert!(db.delete_session("new")?);
+	assert!(!db.delete_session("new")?);
+
+	db.save_session("invalid", "not json", SystemTime::now() + hour)
+		.expect_err("session data must be valid JSON");
+
+	Ok(())
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::thread;
+
+use liter::{
+	Id,
+	SyncDatabase,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+#[database]
+struct Db (Item);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Item {
+	#[key]
+	id: Id,
+	data: u64
+}
+
+#[test]
+fn share_between_threads() -> SqlResult<()> {
+	fn assert_send_sync<T: Send + Sync>() {}
+	assert_send_sync::<SyncDatabase<Db>>();
+
+	let db = Arc::new(SyncDatabase::new(Db::create_in_memory()?));
+
+	let handles: Vec<_> = (0..4u64).map(|n| {
+		let db = db.clone();
+		thread::spawn(move || {
+			for data in 0..10 {
+				let mut item = Item { id: Id::NULL, data: n * 10 + data };
+				db.create(&mut item).unwrap();
+			}
+		})
+	}).collect();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	assert_eq!(db.get_all::<Item>()?.len(), 40);
+
+	let mut guard = db.lock();
+	let tx = guard.transaction()?;
+	tx.insert(&Item { id: Id::from_i64(1000), data: 0 })?;
+	tx.rollback()?;
+	drop(guard);
+	assert!(db.get::<Item>(Id::from_i64(1000))?.is_none());
+
+	Ok(())
+}
+
+#[test]
+fn writes_are_attributed_to_the_caller() -> SqlResult<()> {
+	let db = SyncDatabase::new(Db::create_in_memory()?);
+	let mut item = Item { id: Id::NULL, data: 1 };
+	db.create(&mut item)?;
+	item.data = 2;
+	db.update(&item)?;
+	db.delete::<Item>(&item.id)?;
+
+	let diagnostics = db.lock().lock_diagnostics()?;
+	assert_eq!(diagnostics.recent_writes.len(), 3);
+	for write in &diagnostics.recent_writes {
+		assert_eq!(write.caller.file(), file!(), "{}", write.operation);
+	}
+	Ok(())
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "shell")]
+
+use liter::{
+	Database,
+	Id,
+	Schema,
+	Table,
+	database
+};
+use liter::shell::run_with;
+use rusqlite::Result as SqlResult;
+
+
+/// Output of the shell for the input lines, without the prompts
+fn run<S: Schema>(db: &Database<S>, lines: &[&str]) -> Vec<String> {
+	let mut output = Vec::new();
+	run_with(db, lines.join("\n").as_bytes(), &mut output).unwrap();
+	String::from_utf8(output).unwrap()
+		.split("liter> ")
+		.flat_map(|chunk| chunk.lines().map(str::to_string).collect::<Vec<_>>())
+		.filter(|line| !line.is_empty())
+		.collect()
+}
+
+#[test]
+fn params() -> SqlResult<()> {
+	#[database]
+	struct Db (Planet);
+
+	#[derive(Table)]
+	struct Planet {
+		#[key]
+		id: Id
+	}
+
+	let db = Db::create_in_memory()?;
+	let output = run(&db, &[
+		".param NULL",
+		".param null",
+		".param -3",
+		".param 2.5",
+		".param '42'",
+		".param it's",
+		"SELECT typeof(?), typeof(?), ?, ?, typeof(?), ?",
+		// the parameters were used up by the previous statement
+		"SELECT ?"
+	]);
+	assert_eq!(output[0], "typeof(?)\ttypeof(?)\t?\t?\ttypeof(?)\t?");
+	assert_eq!(output[1], "null\tnull\t-3\t2.5\ttext\tit's");
+	assert!(output[2].starts_with("error: "), "{output:?}");
+	Ok(())
+}
+
+#[test]
+fn browse() -> SqlResult<()> {
+	#[database]
+	struct Db (Planet);
+
+	#[derive(Table)]
+	struct Planet {
+		#[key]
+		id: Id,
+		name: Option<String>,
+		image: Vec<u8>
+	}
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Planet { id: Id::new(1), name: None, image: vec![0xca, 0xfe] })?;
+	db.insert(&Planet { id: Id::new(2), name: Some("Venus".to_string()), image: vec![0; 33] })?;
+	let output = run(&db, &[
+		".columns planet",
+		".browse planet",
+		".browse planet 1 1",
+		".browse planet many",
+		".browse moon",
+		".columns"
+	]);
+	assert_eq!(output, [
+		"id: INTEGER NOT NULL (key)",
+		"name: TEXT",
+		"image: BLOB NOT NULL",
+		"id\tname\timage",
+		"1\tNULL\tx'cafe'",
+		"2\tVenus\t<33 bytes>",
+		"id\tname\timage",
+		"2\tVenus\t<33 bytes>",
+		"error: invalid digit found in string",
+		"error: no table named \"moon\"",
+		"error: no table named \"\""
+	]);
+	Ok(())
+}
+
+#[test]
+fn commands() -> SqlResult<()> {
+	#[database]
+	struct Db (Planet);
+
+	#[derive(Table)]
+	struct Planet {
+		#[key]
+		id: Id
+	}
+
+	let db = Db::create_in_memory()?;
+	let output = run(&db, &[
+		"",
+		"   .tables   ",
+		".drop planet",
+		"DROP TABLE planet",
+		"SELEC 1",
+		".quit",
+		".tables"
+	]);
+	assert_eq!(output.len(), 4, "{output:?}");
+	assert_eq!(output[0], "planet (0 rows)");
+	assert_eq!(output[1], "unknown command .drop, see .help");
+	assert_eq!(output[2], "only read-only statements are run");
+	assert!(output[3].starts_with("error: "));
+	assert_eq!(db.count::<Planet>()?, 0);
+	Ok(())
+}
@@ -0,0 +1,79 @@
+use std::fs;
+
+use liter::{
+	Database,
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn capacities() -> SqlResult<()> {
+	#[database]
+	struct Db (Song, Album);
+
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Song {
+		#[key]
+		id: Id,
+		title: String
+	}
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Album {
+		#[key]
+		id: Id,
+		name: String
+	}
+
+	// the statements of the two tables alternate, so a small cache keeps evicting them
+	for capacity in [0, 1, 2, 100] {
+		let path = std::env::temp_dir().join(format!("liter_statement_cache_{capacity}.db"));
+		let _ = fs::remove_file(&path);
+		let db = Database::<Db>::options().statement_cache(capacity).init(&path)?;
+		for n in 1..=3 {
+			let mut song = Song { id: Id::NULL, title: format!("song {n}") };
+			db.create(&mut song)?;
+			let mut album = Album { id: Id::NULL, name: format!("album {n}") };
+			db.create(&mut album)?;
+			assert_eq!(db.get::<Song>(song.id.clone())?, Some(song.clone()));
+			assert_eq!(db.get::<Album>(album.id.clone())?, Some(album.clone()));
+			song.title.push('!');
+			db.update(&song)?;
+			db.upsert(&album)?;
+		}
+		assert_eq!(db.count::<Song>()?, 3, "{capacity}");
+		assert_eq!(db.get_all::<Album>()?.len(), 3, "{capacity}");
+		assert!(db.get_all::<Song>()?.iter().all(|song| song.title.ends_with('!')));
+		drop(db);
+		fs::remove_file(&path).unwrap();
+	}
+	Ok(())
+}
+
+#[test]
+fn schema_changes() -> SqlResult<()> {
+	#[database]
+	struct Db (Song);
+
+	#[derive(Table, Clone, Debug, PartialEq)]
+	struct Song {
+		#[key]
+		id: Id,
+		title: String
+	}
+
+	let db = Db::create_in_memory()?;
+	let mut song = Song { id: Id::NULL, title: "before".to_string() };
+	db.create(&mut song)?;
+	assert_eq!(db.get::<Song>(song.id.clone())?, Some(song.clone()));
+
+	// cached statements are prepared again by SQLite when the schema changed
+	db.execute_batch("CREATE INDEX song_title ON song (title);")?;
+	assert_eq!(db.get::<Song>(song.id.clone())?, Some(song.clone()));
+	let mut after = Song { id: Id::NULL, title: "after".to_string() };
+	db.create(&mut after)?;
+	assert_eq!(db.get_all::<Song>()?, [song, after]);
+	Ok(())
+}
@@ -0,0 +1,61 @@
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::storage::ColumnStorage;
+use rusqlite::Result as SqlResult;
+
+
+#[database]
+struct Db (Tag);
+
+#[derive(Table, Debug)]
+struct Tag {
+	#[key]
+	id: Id,
+	name: String,
+	note: Option<String>
+}
+
+fn column(name: &'static str, total_bytes: u64, distinct: u64, distinct_bytes: u64, dictionary_savings: u64)
+	-> ColumnStorage
+{
+	ColumnStorage { name, total_bytes, distinct, distinct_bytes, dictionary_savings }
+}
+
+#[test]
+fn empty_table() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let report = db.storage_report::<Tag>()?;
+	assert_eq!(report.table, "tag");
+	assert_eq!(report.rows, 0);
+	assert_eq!(report.columns, [
+		column("id", 0, 0, 0, 0),
+		column("name", 0, 0, 0, 0),
+		column("note", 0, 0, 0, 0)
+	]);
+	assert_eq!(report.columns[1].average_bytes(report.rows), 0);
+	assert!(report.to_string().starts_with("tag (0 rows)\n"));
+	Ok(())
+}
+
+#[test]
+fn sizes() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	for id in 1..=3 {
+		// 6 bytes of UTF-8, but 3 characters
+		db.insert(&Tag { id: Id::new(id), name: "äöü".to_string(), note: None })?;
+	}
+	let report = db.storage_report::<Tag>()?;
+	assert_eq!(report.rows, 3);
+	assert_eq!(report.columns, [
+		// unique values don't get any smaller with an id per row
+		column("id", 24, 3, 24, 0),
+		column("name", 18, 1, 6, 12),
+		// only NULLs
+		column("note", 0, 0, 0, 0)
+	]);
+	assert_eq!(report.columns[1].average_bytes(report.rows), 6);
+	Ok(())
+}
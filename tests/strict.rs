@@ -0,0 +1,110 @@
+use liter::{
+	Bind,
+	Database,
+	Entry,
+	Fetch,
+	Schema,
+	Table,
+	database
+};
+use liter::table::TableDef;
+use liter::types::{
+	Binder,
+	Fetcher
+};
+use liter::util::construe;
+use rusqlite::Result as SqlResult;
+
+
+fn is_strict<S: Schema>(db: &Database<S>) -> SqlResult<bool> {
+	db.query_one("SELECT strict FROM pragma_table_list WHERE name = 'reading'")
+}
+
+#[test]
+fn strict_by_default() -> SqlResult<()> {
+	#[database]
+	struct Db (Reading);
+
+	#[derive(Table)]
+	struct Reading {
+		sensor: String,
+		value: i64
+	}
+
+	assert!(Reading::STRICT);
+	assert!(Reading::CREATE_TABLE.ends_with("\n) STRICT;"), "{}", Reading::CREATE_TABLE);
+
+	let db = Db::create_in_memory()?;
+	assert!(is_strict(&db)?);
+	assert!(db.execute("INSERT INTO reading VALUES ('a', 'not a number')", &()).is_err());
+	Ok(())
+}
+
+#[test]
+fn non_strict_table() -> SqlResult<()> {
+	#[derive(Table, Debug, PartialEq)]
+	struct Reading {
+		sensor: String,
+		value: i64
+	}
+
+	/// Same table, but not `STRICT`
+	#[derive(Debug, PartialEq)]
+	struct Loose(Reading);
+
+	impl Table for Loose {
+		const NAME: &'static str = Reading::NAME;
+		const DEFINITION: TableDef = Reading::DEFINITION;
+		const STRICT: bool = false;
+		const CREATE_TABLE: &'static str = construe!(
+			&str => TableDef::define_with(&Loose::DEFINITION, Loose::STRICT)
+		);
+		const ALL_COLUMNS: &'static [&'static str] = Reading::ALL_COLUMNS;
+		const KEY_COLUMNS: &'static [&'static str] = Reading::KEY_COLUMNS;
+		const OTHER_COLUMNS: &'static [&'static str] = Reading::OTHER_COLUMNS;
+		type References = ();
+	}
+	impl Entry for Loose {
+		const GET_ALL: &'static str = Reading::GET_ALL;
+		const INSERT: &'static str = Reading::INSERT;
+	}
+	impl Bind for Loose {
+		const COLUMNS: usize = Reading::COLUMNS;
+		fn bind(&self, binder: &mut Binder<'_, '_>) -> SqlResult<()> {
+			self.0.bind(binder)
+		}
+	}
+	impl Fetch for Loose {
+		fn fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Self> {
+			Reading::fetch(fetcher).map(Self)
+		}
+		fn try_fetch(fetcher: &mut Fetcher<'_>) -> SqlResult<Option<Self>> {
+			Reading::try_fetch(fetcher).map(|reading| reading.map(Self))
+		}
+	}
+
+	struct Db;
+	impl Schema for Db {
+		type Tables = (Loose, );
+		const DEFINITIONS: &'static [TableDef] = &[Loose::DEFINITION];
+		const CREATE: &'static str = construe!(
+			&str => liter::schema::define(&[Loose::CREATE_TABLE])
+		);
+	}
+
+	assert!(Loose::CREATE_TABLE.ends_with("\n);"), "{}", Loose::CREATE_TABLE);
+	let db = Database::<Db>::create_in_memory()?;
+	assert!(!is_strict(&db)?);
+
+	// the affinity is only a preference: numeric text is converted, other text is kept
+	db.execute("INSERT INTO reading VALUES ('a', '12')", &())?;
+	db.execute("INSERT INTO reading VALUES ('b', 'not a number')", &())?;
+	let types: Vec<String> = db.query_all("SELECT typeof(value) FROM reading ORDER BY sensor")?;
+	assert_eq!(types, ["integer", "text"]);
+
+	let converted: Vec<Loose> = db.query("SELECT * FROM reading WHERE sensor = 'a'", &())?;
+	assert_eq!(converted, [Loose(Reading { sensor: "a".to_string(), value: 12 })]);
+	// the text can't be fetched as an i64
+	assert!(db.get_all::<Loose>().is_err());
+	Ok(())
+}
@@ -0,0 +1,103 @@
+use liter::{
+	Id,
+	Ref,
+	Table,
+	database
+};
+use liter::table_fn::ColumnInfo;
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[test]
+fn pragma_arguments() -> SqlResult<()> {
+	#[database]
+	struct Db (Shelf);
+
+	#[derive(Table)]
+	struct Shelf {
+		#[key]
+		id: Id
+	}
+
+	let db = Db::create_in_memory()?;
+	for name in ["", "table_info; DROP TABLE shelf", "table-info", "table_info('shelf')"] {
+		assert!(matches!(
+			db.pragma_rows::<ColumnInfo>(name, None),
+			Err(Error::InvalidParameterName(invalid)) if invalid == name
+		));
+	}
+	assert!(db.pragma_rows::<String>("no_such_pragma", None).is_err());
+	// the argument is bound, not pasted into the SQL
+	assert!(db.pragma_rows::<ColumnInfo>("table_info", Some("shelf') --"))?.is_empty());
+	assert_eq!(db.pragma_rows::<ColumnInfo>("table_info", Some("shelf"))?.len(), 1);
+	Ok(())
+}
+
+#[test]
+fn keys_and_indexes() -> SqlResult<()> {
+	#[database]
+	struct Db (Shelf, Book);
+
+	#[derive(Table)]
+	struct Shelf {
+		#[key]
+		room: String,
+		#[key]
+		number: u8
+	}
+	#[derive(Table)]
+	struct Book {
+		#[key]
+		id: Id,
+		shelf: Ref<Shelf>,
+		#[unique]
+		isbn: String
+	}
+
+	let db = Db::create_in_memory()?;
+	let shelf = db.table_info::<Shelf>()?;
+	let key: Vec<u32> = shelf.iter().map(|column| column.primary_key).collect();
+	assert_eq!(key, [1, 2]);
+	assert!(shelf.iter().all(|column| column.default.is_none()));
+
+	let origins: Vec<String> = db.index_list::<Shelf>()?.into_iter()
+		.map(|index| index.origin)
+		.collect();
+	assert_eq!(origins, ["pk"]);
+	let indexes = db.index_list::<Book>()?;
+	assert_eq!(indexes.len(), 1);
+	assert_eq!(indexes[0].origin, "u");
+	assert!(indexes[0].unique);
+	assert!(!indexes[0].partial);
+
+	// one row for each column of the foreign key
+	let keys = db.foreign_key_list::<Book>()?;
+	assert_eq!(keys.len(), 2);
+	assert!(keys.iter().all(|key| key.id == keys[0].id && key.table == "shelf"));
+	let seqs: Vec<i64> = keys.iter().map(|key| key.seq).collect();
+	assert_eq!(seqs, [0, 1]);
+	assert!(db.foreign_key_list::<Shelf>()?.is_empty());
+	Ok(())
+}
+
+#[test]
+#[cfg(feature = "series")]
+fn series() -> SqlResult<()> {
+	#[database]
+	struct Db (Shelf);
+
+	#[derive(Table)]
+	struct Shelf {
+		#[key]
+		id: Id
+	}
+
+	let db = Db::create_in_memory()?;
+	assert_eq!(db.series(1, 10, 3)?, [1, 4, 7, 10]);
+	assert_eq!(db.series(3, 3, 1)?, [3]);
+	assert!(db.series(5, 1, 1)?.is_empty());
+	Ok(())
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "time")]
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+use time::{
+	Date,
+	Month,
+	OffsetDateTime,
+	PrimitiveDateTime,
+	Time,
+	UtcOffset
+};
+
+mod common;
+
+
+#[database]
+struct Db (Event);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Event {
+	#[key]
+	id: Id,
+	at: OffsetDateTime,
+	local: PrimitiveDateTime,
+	day: Date,
+	starts: Time,
+	ended: Option<OffsetDateTime>
+}
+
+fn local(year: i32, month: Month, day: u8, hms: (u8, u8, u8), milli: u16) -> PrimitiveDateTime {
+	Date::from_calendar_date(year, month, day).unwrap()
+		.with_hms_milli(hms.0, hms.1, hms.2, milli).unwrap()
+}
+
+fn event(id: i64, local: PrimitiveDateTime) -> Event {
+	Event {
+		id: Id::from_i64(id),
+		at: local.assume_utc(),
+		local,
+		day: local.date(),
+		starts: local.time(),
+		ended: None
+	}
+}
+
+#[test]
+fn round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let leap = event(1, local(2024, Month::February, 29, (23, 59, 58), 250));
+	let offset = Event {
+		// stored with its offset, but still the same instant
+		at: local(1969, Month::July, 20, (22, 17, 40), 0)
+			.assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap()),
+		ended: Some(local(1969, Month::July, 21, (2, 56, 15), 0).assume_utc()),
+		..event(2, local(1969, Month::July, 20, (20, 17, 40), 0))
+	};
+	db.insert(&leap)?;
+	db.insert(&offset)?;
+	assert_eq!(db.get_all::<Event>()?, [leap, offset.clone()]);
+
+	let at: String = db.query_one("SELECT at FROM event WHERE id = 2")?;
+	assert!(at.starts_with("1969-07-20 22:17:40") && at.ends_with("+02:00"), "{at}");
+	let (day, starts): (String, String) = db.query_one("SELECT day, starts FROM event WHERE id = 1")?;
+	assert_eq!(day, "2024-02-29");
+	assert!(starts.starts_with("23:59:58.25"), "{starts}");
+	Ok(())
+}
+
+#[test]
+fn check() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let valid = event(1, local(2024, Month::January, 1, (12, 0, 0), 0));
+	db.insert(&valid)?;
+	common::assert_checked(&db, "event", "ended", &[
+		"at = 'tomorrow'",
+		"at = '12:00:00+00:00'",
+		"local = '1/1/2024 12:00'",
+		"day = ''",
+		"starts = '2024-01-01'",
+		"starts = 'noon'",
+		"ended = 'never'"
+	])?;
+	assert_eq!(db.get_all::<Event>()?, [valid]);
+	Ok(())
+}
+
+#[test]
+fn ordering() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let times = [
+		local(1969, Month::December, 31, (23, 59, 59), 0),
+		local(2024, Month::January, 1, (9, 0, 0), 0),
+		local(2024, Month::January, 1, (10, 0, 0), 0),
+		local(2024, Month::January, 1, (10, 0, 0), 123),
+		local(2024, Month::January, 1, (10, 0, 0), 500),
+		local(2024, Month::January, 2, (0, 0, 0), 0),
+		local(2038, Month::January, 19, (3, 14, 8), 0)
+	];
+	// inserted in another order than they sort
+	for (id, local) in times.iter().enumerate().rev() {
+		db.insert(&event(id as i64, *local))?;
+	}
+
+	// all in UTC, so the OffsetDateTimes sort chronologically as well
+	common::assert_sorted(&db, "event", &["at", "local"], times.len())?;
+	let days: Vec<Date> = db.query_all("SELECT DISTINCT day FROM event ORDER BY day")?;
+	let mut expected_days: Vec<Date> = times.iter().map(|local| local.date()).collect();
+	expected_days.dedup();
+	assert_eq!(days, expected_days);
+	let starts: Vec<Time> = db.query_all("SELECT starts FROM event ORDER BY starts")?;
+	let mut expected_starts: Vec<Time> = times.iter().map(|local| local.time()).collect();
+	expected_starts.sort();
+	assert_eq!(starts, expected_starts);
+	Ok(())
+}
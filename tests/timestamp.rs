@@ -0,0 +1,104 @@
+use std::time::{
+	Duration,
+	SystemTime,
+	UNIX_EPOCH
+};
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+mod common;
+
+
+#[database]
+struct Db (Run);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Run {
+	#[key]
+	id: Id,
+	started: SystemTime,
+	took: Duration,
+	finished: Option<SystemTime>
+}
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn run(id: i64, started: SystemTime, took: Duration) -> Run {
+	Run { id: Id::from_i64(id), started, took, finished: None }
+}
+
+#[test]
+fn round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let after = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+	let runs = [
+		Run { finished: Some(after + Duration::from_secs(2)), ..run(1, after, Duration::new(2, 1)) },
+		// a whole number of seconds and some nanoseconds before the epoch
+		run(2, UNIX_EPOCH - Duration::new(10 * YEAR, 1), Duration::ZERO),
+		run(3, UNIX_EPOCH - Duration::from_secs(1), Duration::from_nanos(1)),
+		run(4, UNIX_EPOCH, Duration::from_nanos(i64::MAX as u64))
+	];
+	for run in &runs {
+		db.insert(run)?;
+	}
+	assert_eq!(db.get_all::<Run>()?, runs);
+
+	let stored: (i64, i64) = db.query_one("SELECT started, took FROM run WHERE id = 1")?;
+	assert_eq!(stored, (1_700_000_000_123_456_789, 2_000_000_001));
+	let stored: i64 = db.query_one("SELECT started FROM run WHERE id = 2")?;
+	assert_eq!(stored, -(10 * YEAR as i64) * 1_000_000_000 - 1);
+	Ok(())
+}
+
+#[test]
+fn check() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let valid = run(1, UNIX_EPOCH, Duration::from_secs(1));
+	db.insert(&valid)?;
+	common::assert_checked(&db, "run", "finished", &["took = -1"])?;
+	assert!(db.execute("INSERT INTO run VALUES (2, 0, -1000000000, NULL)", &()).is_err());
+	// the epoch itself and an empty duration are fine
+	db.execute("UPDATE run SET took = 0", &())?;
+	assert_eq!(db.get_all::<Run>()?, [Run { took: Duration::ZERO, ..valid }]);
+	Ok(())
+}
+
+#[test]
+fn out_of_range() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let too_long = Duration::from_secs(300 * YEAR);
+	assert!(db.insert(&run(1, UNIX_EPOCH, too_long)).is_err());
+	assert!(db.insert(&run(2, UNIX_EPOCH + too_long, Duration::ZERO)).is_err());
+	assert!(db.insert(&run(3, UNIX_EPOCH - too_long, Duration::ZERO)).is_err());
+	assert!(db.get_all::<Run>()?.is_empty());
+	Ok(())
+}
+
+#[test]
+fn ordering() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let times = [
+		UNIX_EPOCH - Duration::from_secs(50 * YEAR),
+		UNIX_EPOCH - Duration::from_secs(1),
+		UNIX_EPOCH - Duration::from_nanos(1),
+		UNIX_EPOCH,
+		UNIX_EPOCH + Duration::from_nanos(1),
+		UNIX_EPOCH + Duration::from_secs(1),
+		UNIX_EPOCH + Duration::from_secs(100 * YEAR)
+	];
+	// inserted in another order than they sort
+	for (id, started) in times.iter().enumerate().rev() {
+		let took = started.duration_since(times[0]).unwrap();
+		db.insert(&run(id as i64, *started, took))?;
+	}
+
+	let sorted: Vec<SystemTime> = db.query_all("SELECT started FROM run ORDER BY started")?;
+	assert_eq!(sorted, times);
+	common::assert_sorted(&db, "run", &["started", "took"], times.len())?;
+	Ok(())
+}
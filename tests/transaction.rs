@@ -0,0 +1,105 @@
+use liter::{
+	Id,
+	Ref,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+#[database]
+struct Db (Item, Note);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Item {
+	#[key]
+	id: Id,
+	data: u64
+}
+
+#[derive(Table)]
+struct Note {
+	#[key]
+	id: Id,
+	item: Ref<Item>
+}
+
+fn item(id: i64) -> Item {
+	Item { id: Id::from_i64(id), data: id as u64 }
+}
+
+#[test]
+fn commit_and_rollback() -> SqlResult<()> {
+	let mut db = Db::create_in_memory()?;
+
+	let tx = db.transaction()?;
+	tx.insert(&item(1))?;
+	tx.commit()?;
+	assert_eq!(db.get_all::<Item>()?, vec![item(1)]);
+
+	let tx = db.transaction()?;
+	tx.insert(&item(2))?;
+	tx.rollback()?;
+	assert_eq!(db.get_all::<Item>()?, vec![item(1)]);
+
+	{
+		let tx = db.transaction()?;
+		tx.insert(&item(3))?;
+		// dropped without commit
+	}
+	assert_eq!(db.get_all::<Item>()?, vec![item(1)]);
+
+	Ok(())
+}
+
+#[test]
+fn savepoint() -> SqlResult<()> {
+	let mut db = Db::create_in_memory()?;
+
+	let mut tx = db.transaction()?;
+	tx.insert(&item(1))?;
+
+	let sp = tx.savepoint()?;
+	sp.insert(&item(2))?;
+	assert!(sp.get::<Item>(Id::from_i64(2))?.is_some());
+	sp.rollback()?;
+	assert!(tx.get::<Item>(Id::from_i64(2))?.is_none());
+
+	let mut sp = tx.savepoint()?;
+	sp.insert(&item(3))?;
+	{
+		let inner = sp.savepoint()?;
+		inner.insert(&item(4))?;
+		// dropped without commit
+	}
+	sp.commit()?;
+	tx.commit()?;
+
+	assert_eq!(db.get_all::<Item>()?, vec![item(1), item(3)]);
+
+	Ok(())
+}
+
+#[test]
+fn failed_commit() -> SqlResult<()> {
+	let mut db = Db::create_in_memory()?;
+
+	let tx = db.transaction()?;
+	// only check the foreign keys when committing
+	tx.pragma_update(None, "defer_foreign_keys", true)?;
+	tx.insert(&Note { id: Id::NULL, item: Ref(Id::from_i64(1)) })?;
+	assert!(tx.commit().is_err());
+
+	// the transaction was rolled back instead of being left open
+	assert!(db.is_autocommit());
+	assert!(db.get_all::<Note>()?.is_empty());
+	let diagnostics = db.lock_diagnostics()?;
+	let last = diagnostics.recent_writes.last().unwrap();
+	assert_eq!(last.operation, "transaction");
+	assert!(last.failed);
+
+	let tx = db.transaction()?;
+	tx.insert(&item(1))?;
+	tx.commit()?;
+	assert_eq!(db.get_all::<Item>()?, vec![item(1)]);
+	Ok(())
+}
@@ -0,0 +1,138 @@
+use liter::{
+	Id,
+	Ref,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn self_reference() -> SqlResult<()> {
+	#[database]
+	struct Db (Category);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Category {
+		#[key]
+		id: Id,
+		parent: Option<Ref<Self>>,
+		name: String
+	}
+
+	let db = Db::create_in_memory()?;
+	let category = |parent: Option<&Category>, name: &str| -> SqlResult<Category> {
+		let mut category = Category {
+			id: Id::NULL,
+			parent: parent.map(Ref::make_ref),
+			name: name.to_string()
+		};
+		db.create(&mut category)?;
+		Ok(category)
+	};
+	let root = category(None, "root")?;
+	let books = category(Some(&root), "books")?;
+	let fantasy = category(Some(&books), "fantasy")?;
+
+	assert_eq!(db.resolve(fantasy.parent.as_ref().unwrap())?, Some(books.clone()));
+
+	// a parent has to exist
+	let orphan = Category {
+		id: Id::NULL,
+		parent: Some(Ref(Id::new(99))),
+		name: "orphan".to_string()
+	};
+	assert!(db.insert(&orphan).is_err());
+
+	// a parent can't be deleted before its children
+	assert!(db.execute("DELETE FROM category WHERE id = ?", &books.id).is_err());
+	db.execute("DELETE FROM category WHERE id = ?", &fantasy.id)?;
+	db.execute("DELETE FROM category WHERE id = ?", &books.id)?;
+	assert_eq!(db.get_all::<Category>()?, [root]);
+	Ok(())
+}
+
+#[test]
+fn load_ancestors() -> SqlResult<()> {
+	#[database]
+	struct Db (Category);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Category {
+		#[key]
+		id: Id,
+		parent: Option<Ref<Self>>,
+		name: String
+	}
+
+	let db = Db::create_in_memory()?;
+	let category = |parent: Option<&Category>, name: &str| -> SqlResult<Category> {
+		let mut category = Category {
+			id: Id::NULL,
+			parent: parent.map(Ref::make_ref),
+			name: name.to_string()
+		};
+		db.create(&mut category)?;
+		Ok(category)
+	};
+	let root = category(None, "root")?;
+	let books = category(Some(&root), "books")?;
+	let fantasy = category(Some(&books), "fantasy")?;
+
+	let graph = db.load_graph::<Category>(&[fantasy.id.clone()], 1)?;
+	assert_eq!(graph.all::<Category>(), [fantasy.clone(), books.clone()]);
+
+	let graph = db.load_graph::<Category>(&[fantasy.id.clone()], 10)?;
+	let mut path = Vec::new();
+	let mut current = graph.roots().next();
+	while let Some(category) = current {
+		path.push(category.name.as_str());
+		current = category.parent.as_ref().and_then(|parent| graph.resolve(parent));
+	}
+	assert_eq!(path, ["fantasy", "books", "root"]);
+	Ok(())
+}
+
+#[test]
+fn recursive_queries() -> SqlResult<()> {
+	#[database]
+	struct Db (Category);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Category {
+		#[key]
+		id: Id,
+		parent: Option<Ref<Self>>,
+		name: String
+	}
+
+	let db = Db::create_in_memory()?;
+	let category = |parent: Option<&Category>, name: &str| -> SqlResult<Category> {
+		let mut category = Category {
+			id: Id::NULL,
+			parent: parent.map(Ref::make_ref),
+			name: name.to_string()
+		};
+		db.create(&mut category)?;
+		Ok(category)
+	};
+	let root = category(None, "root")?;
+	let books = category(Some(&root), "books")?;
+	let music = category(Some(&root), "music")?;
+	let fantasy = category(Some(&books), "fantasy")?;
+
+	let mut descendants = db.descendants::<Category>(&root.id)?;
+	// same depth, in no particular order
+	descendants[..2].sort_by(|a, b| a.name.cmp(&b.name));
+	assert_eq!(descendants, [books.clone(), music.clone(), fantasy.clone()]);
+	assert_eq!(db.descendants::<Category>(&music.id)?, []);
+
+	assert_eq!(db.ancestors::<Category>(&fantasy.id)?, [books.clone(), root.clone()]);
+	assert_eq!(db.ancestors::<Category>(&root.id)?, []);
+
+	// a cycle doesn't make the queries run forever
+	db.execute("UPDATE category SET parent = ? WHERE id = ?", &(&fantasy.id, &root.id))?;
+	assert_eq!(db.ancestors::<Category>(&root.id)?, [fantasy, books]);
+	assert_eq!(db.descendants::<Category>(&root.id)?.len(), 3);
+	Ok(())
+}
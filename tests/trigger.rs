@@ -0,0 +1,114 @@
+use liter::{
+	Database,
+	Id,
+	Schema,
+	Table,
+	database
+};
+use liter::table::TableDef;
+use liter::trigger::{
+	Event,
+	TriggerDef
+};
+use liter::util::construe;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn created_with_schema() -> SqlResult<()> {
+	#[derive(Table)]
+	struct Account {
+		#[key]
+		id: Id,
+		balance: i64
+	}
+	#[derive(Table)]
+	struct Audit {
+		account: i64,
+		balance: i64
+	}
+
+	const LOG_BALANCE: TriggerDef = TriggerDef::after::<Account>(
+		"log_balance",
+		Event::UpdateOf(&["balance"]),
+		"INSERT INTO audit VALUES (new.id, new.balance);"
+	);
+
+	struct Db;
+	impl Schema for Db {
+		type Tables = (Account, (Audit, ));
+		const DEFINITIONS: &'static [TableDef] = &[Account::DEFINITION, Audit::DEFINITION];
+		const CREATE: &'static str = construe!(
+			&str => liter::schema::define(&[Account::CREATE_TABLE, Audit::CREATE_TABLE])
+		);
+		const TRIGGERS: &'static [TriggerDef] = &[LOG_BALANCE];
+	}
+
+	let db = Database::<Db>::create_in_memory()?;
+	let triggers: Vec<String> = db.query("SELECT name FROM sqlite_schema WHERE type = 'trigger'", &())?;
+	assert_eq!(triggers, ["log_balance"]);
+
+	db.insert(&Account { id: Id::new(1), balance: 10 })?;
+	assert_eq!(db.count::<Audit>()?, 0);
+	db.execute("UPDATE account SET balance = 25", &())?;
+	assert_eq!(db.query::<(i64, i64), _>("SELECT * FROM audit", &())?, [(1, 25)]);
+	// creating it again is a no-op
+	db.create_trigger(&LOG_BALANCE)?;
+	Ok(())
+}
+
+#[test]
+fn condition() -> SqlResult<()> {
+	#[database]
+	struct Db (Account);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Account {
+		#[key]
+		id: Id,
+		balance: i64
+	}
+
+	let no_overdraft = TriggerDef::before::<Account>(
+		"no_overdraft",
+		Event::Update,
+		"SELECT raise(ABORT, 'overdrawn')"
+	).when("new.balance < 0");
+	assert_eq!(no_overdraft.to_string(), "CREATE TRIGGER IF NOT EXISTS \"no_overdraft\" \
+		BEFORE UPDATE ON \"account\" FOR EACH ROW WHEN new.balance < 0 \
+		BEGIN SELECT raise(ABORT, 'overdrawn'); END;");
+
+	let db = Db::create_in_memory()?;
+	db.create_trigger(&no_overdraft)?;
+	db.insert(&Account { id: Id::new(1), balance: 10 })?;
+	assert!(db.execute("UPDATE account SET balance = -1", &()).is_err());
+	db.execute("UPDATE account SET balance = 0", &())?;
+	assert_eq!(db.get::<Account>(Id::new(1))?, Some(Account { id: Id::new(1), balance: 0 }));
+	Ok(())
+}
+
+#[test]
+#[should_panic]
+fn update_of_unknown_column() {
+	#[derive(Table)]
+	struct Account {
+		#[key]
+		id: Id,
+		balance: i64
+	}
+
+	let _ = TriggerDef::after::<Account>("t", Event::UpdateOf(&["balance", "owner"]), "SELECT 1");
+}
+
+#[test]
+#[should_panic]
+fn update_of_no_columns() {
+	#[derive(Table)]
+	struct Account {
+		#[key]
+		id: Id,
+		balance: i64
+	}
+
+	let _ = TriggerDef::after::<Account>("t", Event::UpdateOf(&[]), "SELECT 1");
+}
@@ -0,0 +1,24 @@
+use liter::Id;
+
+#[test]
+fn typed() {
+	struct User;
+	let user: Id<User> = Id::new(7);
+	let plain: Id = user.clone().cast();
+	assert_eq!(plain, Id::new(7));
+	assert_eq!(plain.cast::<User>(), user);
+	assert_eq!(Id::<User>::NULL.cast::<()>(), Id::NULL);
+}
+
+#[test]
+fn conversions() {
+	let id: Id = Id::new(42);
+	assert_eq!(id.get(), Some(42));
+	assert_eq!(Id::<()>::NULL.get(), None);
+	assert_eq!(id.to_string(), "42");
+	assert_eq!("42".parse::<Id>(), Ok(id.clone()));
+	assert_eq!(Id::try_from(42), Ok(id.clone()));
+
+	let ids: std::collections::HashSet<Id> = [Id::new(1), Id::new(1)].into();
+	assert_eq!(ids.len(), 1);
+}
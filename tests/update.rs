@@ -0,0 +1,96 @@
+use liter::{
+	Id,
+	Table,
+	columns,
+	database
+};
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[test]
+fn set_columns() -> SqlResult<()> {
+	#[database]
+	struct Db (Task);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Task {
+		#[key]
+		id: Id,
+		title: String,
+		done: bool,
+		note: Option<String>
+	}
+	columns!(struct TaskColumns for Task { title: String, done: bool, note: Option<String> });
+
+	let db = Db::create_in_memory()?;
+	for (id, title) in [(1, "write"), (2, "test"), (3, "ship")] {
+		db.insert(&Task { id: Id::new(id), title: title.to_string(), done: false, note: None })?;
+	}
+
+	let columns = Task::columns();
+	let updated = db.update_where::<Task>()
+		.set(columns.done.eq(true))
+		.set(columns.note.eq(Some("first".to_string())))
+		// the last value of a column wins
+		.set(columns.note.eq(Some("early".to_string())))
+		.filter("id < ?", 3)
+		.execute()?;
+	assert_eq!(updated, 2);
+	assert_eq!(db.get::<Task>(Id::new(2))?, Some(Task {
+		id: Id::new(2),
+		title: "test".to_string(),
+		done: true,
+		note: Some("early".to_string())
+	}));
+
+	// without a filter, all rows are updated
+	let updated = db.update_where::<Task>()
+		.set(columns.note.eq(None))
+		.set(columns.title.eq("done".to_string()))
+		.execute()?;
+	assert_eq!(updated, 3);
+	assert_eq!(db.count_where::<Task, _>("title = 'done' AND note IS NULL", &())?, 3);
+	Ok(())
+}
+
+#[test]
+fn nothing_set() -> SqlResult<()> {
+	#[database]
+	struct Db (Task);
+
+	#[derive(Table)]
+	struct Task {
+		done: bool
+	}
+
+	let db = Db::create_in_memory()?;
+	assert!(matches!(db.update_where::<Task>().execute(), Err(Error::InvalidQuery)));
+	Ok(())
+}
+
+#[test]
+fn filter_without_params() -> SqlResult<()> {
+	#[database]
+	struct Db (Task);
+
+	#[derive(Table)]
+	struct Task {
+		priority: i64,
+		done: bool
+	}
+	columns!(struct TaskColumns for Task { priority: i64 });
+
+	let db = Db::create_in_memory()?;
+	db.insert(&Task { priority: 1, done: false })?;
+	db.insert(&Task { priority: 2, done: true })?;
+	let updated = db.update_where::<Task>()
+		.set(Task::columns().priority.eq(-5))
+		.filter("NOT done", ())
+		.execute()?;
+	assert_eq!(updated, 1);
+	assert_eq!(db.query::<i64, _>("SELECT priority FROM task ORDER BY done", &())?, [-5, 2]);
+	Ok(())
+}
@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::Path;
+
+use liter::{
+	Database,
+	Table,
+	database
+};
+use liter::uri::{
+	DatabaseUri,
+	UriCache,
+	UriMode
+};
+use rusqlite::Result as SqlResult;
+
+
+#[database]
+struct Db (Station);
+
+#[derive(Table, Debug, PartialEq)]
+struct Station {
+	name: String
+}
+
+#[test]
+fn escaping() {
+	let uri = DatabaseUri::file("/data/what?#%.db");
+	assert_eq!(uri.as_str(), "file:/data/what%3F%23%25.db");
+	// `&` and `=` only mean something in the query
+	assert_eq!(DatabaseUri::file("a&b=c.db").as_str(), "file:a&b=c.db");
+	assert_eq!(DatabaseUri::file("//share/x.db").as_str(), "file:////share/x.db");
+	assert_eq!(DatabaseUri::file("").as_str(), "file:");
+
+	let uri = DatabaseUri::file("x.db")
+		.param("na&me", "a&b=c#d%e?f")
+		.cache(UriCache::Private);
+	assert_eq!(uri.as_str(), "file:x.db?na%26me=a%26b%3Dc%23d%25e?f&cache=private");
+	assert_eq!(uri.to_string(), uri.as_str());
+}
+
+#[test]
+#[cfg(unix)]
+fn open_escaped_path() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter uri?#%25.db");
+	let _ = fs::remove_file(&path);
+	let db = Db::init(&path)?;
+	db.insert(&Station { name: "north".to_string() })?;
+	drop(db);
+
+	let uri = DatabaseUri::file(path.to_str().unwrap()).mode(UriMode::ReadOnly);
+	let archive = Database::<Db>::open_uri(&uri)?;
+	assert_eq!(archive.get_all::<Station>()?, [Station { name: "north".to_string() }]);
+	assert!(archive.insert(&Station { name: "south".to_string() }).is_err());
+	drop(archive);
+
+	// the same path unescaped names another file, which doesn't exist
+	let unescaped = format!("file:{}?mode=ro", path.display());
+	assert!(Database::<Db>::open_uri(&unescaped).is_err());
+	fs::remove_file(&path).unwrap();
+	Ok(())
+}
+
+#[test]
+fn shared_memory() -> SqlResult<()> {
+	let uri = DatabaseUri::file("liter_uri_shared")
+		.mode(UriMode::Memory)
+		.cache(UriCache::Shared);
+	// URI filenames are on by default, even for paths
+	let first = Database::<Db>::options().init(Path::new(uri.as_str()))?;
+	first.insert(&Station { name: "shared".to_string() })?;
+	let second = Database::<Db>::open_uri(&uri)?;
+	assert_eq!(second.get_all::<Station>()?.len(), 1);
+	Ok(())
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "uuid")]
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use liter::types::uuid::HyphenatedUuid;
+use rusqlite::Result as SqlResult;
+use uuid::Uuid;
+
+mod common;
+
+
+#[database]
+struct Db (Device);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Device {
+	#[key]
+	id: Id,
+	serial: Uuid,
+	label: HyphenatedUuid,
+	parent: Option<Uuid>
+}
+
+fn device(id: i64, uuid: Uuid) -> Device {
+	Device {
+		id: Id::from_i64(id),
+		serial: uuid,
+		label: HyphenatedUuid(uuid),
+		parent: None
+	}
+}
+
+#[test]
+fn round_trip() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+	let devices = [
+		device(1, uuid),
+		Device { parent: Some(Uuid::max()), ..device(2, Uuid::nil()) }
+	];
+	for device in &devices {
+		db.insert(device)?;
+	}
+	assert_eq!(db.get_all::<Device>()?, devices);
+
+	let (serial, label): (Vec<u8>, String) = db.query_one("SELECT serial, label FROM device WHERE id = 1")?;
+	assert_eq!(serial, uuid.as_bytes());
+	assert_eq!(label, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+	Ok(())
+}
+
+#[test]
+fn check() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let valid = device(1, Uuid::from_u128(1));
+	db.insert(&valid)?;
+	common::assert_checked(&db, "device", "parent", &[
+		"serial = x'00'",
+		"serial = zeroblob(17)",
+		"serial = ''",
+		"parent = x'0102030405060708090a0b0c0d0e0f'",
+		"label = '67e55044-10b1-426f-9247-bb680e5fe0c'",
+		"label = '67E55044-10B1-426F-9247-BB680E5FE0C8'",
+		"label = '67e5504410b1426f9247bb680e5fe0c8'",
+		"label = '{67e55044-10b1-426f-9247-bb680e5fe0c8}'",
+		"label = 'g7e55044-10b1-426f-9247-bb680e5fe0c8'"
+	])?;
+	assert_eq!(db.get_all::<Device>()?, [valid]);
+	Ok(())
+}
+
+#[test]
+fn ordering() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	let uuids = [
+		Uuid::nil(),
+		Uuid::from_u128(1),
+		Uuid::from_u128(0xff),
+		Uuid::from_u128(0x0100),
+		Uuid::from_u128(0x0a00_0000_0000_0000_0000_0000_0000_0000),
+		Uuid::from_u128(0xf000_0000_0000_0000_0000_0000_0000_0000),
+		Uuid::max()
+	];
+	// inserted in another order than they sort
+	for (id, uuid) in uuids.iter().enumerate().rev() {
+		db.insert(&device(id as i64, *uuid))?;
+	}
+
+	// both the bytes and the lowercase hex sort like the Uuids themselves
+	common::assert_sorted(&db, "device", &["serial", "label"], uuids.len())?;
+	let serials: Vec<Uuid> = db.query_all("SELECT serial FROM device ORDER BY serial")?;
+	assert_eq!(serials, uuids);
+	Ok(())
+}
@@ -19,9 +19,15 @@ use liter::{
 	types::impl_from_to_sql_2,
 	database,
 };
+use liter::types::{
+	Binder,
+	Fetcher
+};
 use liter::value::{
 	ValueDef,
 	NestedValueDef,
+	ForeignKey,
+	FkConflictAction,
 };
 
 
@@ -220,3 +226,93 @@ fn foreign_key() -> rusqlite::Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn foreign_key_actions() {
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct File {
+		#[key]
+		id: Id,
+		name: String
+	}
+
+	let def = <Option<Ref<File>> as Value>::DEFINITION
+		.on_delete(FkConflictAction::Cascade)
+		.on_update(FkConflictAction::SetNull)
+		.immediate_reference();
+	assert_eq!(def.reference, Some(ForeignKey {
+		table_name: File::NAME,
+		deferrable: false,
+		on_delete: FkConflictAction::Cascade,
+		on_update: FkConflictAction::SetNull
+	}));
+}
+
+#[test]
+#[should_panic]
+fn set_null_on_required_reference() {
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct File {
+		#[key]
+		id: Id,
+		name: String
+	}
+
+	let _ = <Ref<File> as Value>::DEFINITION
+		.on_delete(FkConflictAction::SetNull);
+}
+
+#[test]
+fn set_null_on_delete() -> rusqlite::Result<()> {
+	#[database]
+	struct Db (Folder, Note);
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Folder {
+		#[key]
+		id: Id,
+		name: String
+	}
+
+	/// Optional reference that is set to `NULL` when the folder is deleted
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	struct InFolder(Option<Ref<Folder>>);
+	impl Value for InFolder {
+		const DEFINITION: ValueDef = <Option<Ref<Folder>> as Value>::DEFINITION
+			.on_delete(FkConflictAction::SetNull);
+		type References = Folder;
+	}
+	impl Bind for InFolder {
+		const COLUMNS: usize = <Option<Ref<Folder>> as Bind>::COLUMNS;
+		fn bind(&self, binder: &mut Binder<'_, '_>) -> rusqlite::Result<()> {
+			self.0.bind(binder)
+		}
+	}
+	impl Fetch for InFolder {
+		fn fetch(fetcher: &mut Fetcher<'_>) -> rusqlite::Result<Self> {
+			fetcher.fetch().map(Self)
+		}
+		fn try_fetch(fetcher: &mut Fetcher<'_>) -> rusqlite::Result<Option<Self>> {
+			Self::fetch(fetcher).map(Some)
+		}
+	}
+
+	#[derive(Table, Clone, Debug, PartialEq, Eq)]
+	struct Note {
+		#[key]
+		id: Id,
+		folder: InFolder
+	}
+	assert!(Note::CREATE_TABLE.contains("ON DELETE SET NULL"), "{}", Note::CREATE_TABLE);
+	let db = Db::create_in_memory()?;
+
+	let mut folder = Folder { id: Id::NULL, name: "inbox".to_string() };
+	db.create(&mut folder)?;
+	let mut note = Note { id: Id::NULL, folder: InFolder(Some(Ref::make_ref(&folder))) };
+	db.create(&mut note)?;
+
+	db.execute("DELETE FROM folder", &())?;
+	assert_eq!(db.get::<Note>(note.id.clone())?, Some(Note { folder: InFolder(None), ..note }));
+
+	Ok(())
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "vector")]
+
+use liter::{
+	Database,
+	Id,
+	Table,
+	database
+};
+use liter::types::vector::Vector;
+use rusqlite::{
+	Error,
+	Result as SqlResult
+};
+
+
+#[database]
+struct Db (Point, Pair);
+
+#[derive(Table, Clone, Debug, PartialEq)]
+struct Point {
+	#[key]
+	id: Id,
+	position: Option<Vector<2>>
+}
+
+/// Two vectors of the same dimensions, so it's unclear which one to search
+#[derive(Table, Clone, Debug, PartialEq)]
+struct Pair {
+	#[key]
+	id: Id,
+	first: Vector<2>,
+	second: Vector<2>
+}
+
+fn point(id: i64, position: Option<[f32; 2]>) -> Point {
+	Point { id: Id::new(id), position: position.map(Vector) }
+}
+
+fn points() -> SqlResult<Database<Db>> {
+	let db = Db::create_in_memory()?;
+	db.insert(&point(1, Some([0.0, 0.0])))?;
+	db.insert(&point(2, Some([3.0, 4.0])))?;
+	db.insert(&point(3, None))?;
+	db.insert(&point(4, Some([-1.0, 0.0])))?;
+	Ok(db)
+}
+
+fn ids(nearest: &[(Point, f64)]) -> Vec<i64> {
+	nearest.iter().map(|(point, _distance)| point.id.get().unwrap()).collect()
+}
+
+#[test]
+fn distances() -> SqlResult<()> {
+	let db = points()?;
+	let nearest = db.nearest::<Point>(&[0.0, 0.0], 10)?;
+	// the NULL one is skipped, even though more were asked for
+	assert_eq!(ids(&nearest), [1, 4, 2]);
+	let distances: Vec<f64> = nearest.iter().map(|(_point, distance)| *distance).collect();
+	assert_eq!(distances, [0.0, 1.0, 5.0]);
+
+	assert!(db.nearest::<Point>(&[0.0, 0.0], 0)?.is_empty());
+	Ok(())
+}
+
+#[test]
+fn empty_table() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	assert!(db.nearest::<Point>(&[1.0, 1.0], 3)?.is_empty());
+	Ok(())
+}
+
+#[test]
+fn dimension_mismatch() -> SqlResult<()> {
+	let db = points()?;
+	let queries: [&[f32]; 3] = [&[], &[1.0], &[1.0, 2.0, 3.0]];
+	for query in queries {
+		assert!(
+			matches!(db.nearest::<Point>(query, 1), Err(Error::InvalidQuery)),
+			"{query:?}"
+		);
+	}
+	assert!(matches!(db.nearest::<Pair>(&[1.0, 2.0], 1), Err(Error::InvalidQuery)));
+
+	// neither can a vector of another size be stored
+	assert!(db.execute("UPDATE point SET position = x'0000803f'", &()).is_err());
+	assert!(db.execute("UPDATE point SET position = zeroblob(12)", &()).is_err());
+	Ok(())
+}
+
+#[test]
+fn distance_function_checks_sizes() -> SqlResult<()> {
+	let db = points()?;
+	// registers the function, unless sqlite-vec is loaded
+	db.nearest::<Point>(&[0.0, 0.0], 1)?;
+	let result = db.query_one::<f64>("SELECT liter_distance_l2(zeroblob(8), zeroblob(12))");
+	assert!(result.is_err());
+	let null: Option<f64> = db.query_one("SELECT liter_distance_l2(NULL, zeroblob(8))")?;
+	assert_eq!(null, None);
+	Ok(())
+}
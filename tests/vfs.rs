@@ -0,0 +1,75 @@
+use std::ffi::CStr;
+use std::fs;
+use std::ptr;
+
+use liter::{
+	Table,
+	database
+};
+use liter::open::{
+	is_vfs_registered,
+	register_vfs,
+	OpenOptions
+};
+use rusqlite::ffi;
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn unknown_vfs() {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table)]
+	struct Item {
+		name: String
+	}
+
+	assert!(!is_vfs_registered("liter-no-such-vfs"));
+	// names with a NUL in them can't be registered
+	assert!(!is_vfs_registered("memdb\0"));
+	let path = std::env::temp_dir().join("liter_vfs_unknown.db");
+	let _ = fs::remove_file(&path);
+	assert!(OpenOptions::<Db>::new().vfs("liter-no-such-vfs").init(&path).is_err());
+	assert!(!path.exists());
+}
+
+#[test]
+fn register() -> SqlResult<()> {
+	#[database]
+	struct Db (Item);
+
+	#[derive(Table, Debug, PartialEq)]
+	struct Item {
+		name: String
+	}
+
+	// SAFETY: a copy of the default VFS under another name, which lives forever
+	let default = unsafe { *ffi::sqlite3_vfs_find(ptr::null()) };
+	let nameless = Box::new(ffi::sqlite3_vfs { zName: ptr::null(), ..default });
+	assert!(unsafe { register_vfs(nameless, false) }.is_err());
+
+	let copy = Box::new(ffi::sqlite3_vfs {
+		zName: b"liter-test-copy\0".as_ptr().cast(),
+		pNext: ptr::null_mut(),
+		..default
+	});
+	let name = unsafe { register_vfs(copy, false) }?;
+	assert_eq!(name, CStr::from_bytes_with_nul(b"liter-test-copy\0").unwrap());
+	assert!(is_vfs_registered("liter-test-copy"));
+	// it didn't become the default
+	let current = unsafe { CStr::from_ptr((*ffi::sqlite3_vfs_find(ptr::null())).zName) };
+	assert_ne!(current, name);
+
+	let path = std::env::temp_dir().join("liter_vfs_copy.db");
+	let _ = fs::remove_file(&path);
+	let db = OpenOptions::<Db>::new().vfs("liter-test-copy").init(&path)?;
+	db.insert(&Item { name: "lamp".to_string() })?;
+	drop(db);
+	// the file is the same to the default VFS
+	let db = Db::open(&path)?;
+	assert_eq!(db.get_all::<Item>()?, [Item { name: "lamp".to_string() }]);
+	drop(db);
+	fs::remove_file(&path).unwrap();
+	Ok(())
+}
@@ -0,0 +1,81 @@
+use liter::{
+	Database,
+	Id,
+	Schema,
+	Table,
+	database,
+	view
+};
+use liter::table::TableDef;
+use liter::util::construe;
+use liter::view::{
+	View,
+	ViewDef
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn created_with_schema() -> SqlResult<()> {
+	#[derive(Table)]
+	struct Task {
+		#[key]
+		id: Id,
+		done: bool
+	}
+
+	view! {
+		#[view(name = "open_task", sql = "SELECT id FROM task WHERE NOT done")]
+		#[derive(Debug, PartialEq)]
+		struct OpenTask {
+			id: i64
+		}
+	}
+
+	struct Db;
+	impl Schema for Db {
+		type Tables = (Task, );
+		const DEFINITIONS: &'static [TableDef] = &[Task::DEFINITION];
+		const CREATE: &'static str = construe!(
+			&str => liter::schema::define(&[Task::CREATE_TABLE])
+		);
+		const VIEWS: &'static [ViewDef] = &[OpenTask::DEFINITION];
+	}
+
+	let db = Database::<Db>::create_in_memory()?;
+	let views: Vec<String> = db.query("SELECT name FROM sqlite_schema WHERE type = 'view'", &())?;
+	assert_eq!(views, ["open_task"]);
+
+	db.insert(&Task { id: Id::new(1), done: false })?;
+	db.insert(&Task { id: Id::new(2), done: true })?;
+	assert_eq!(db.get_all_view::<OpenTask>()?, [OpenTask { id: 1 }]);
+	// creating it again is a no-op
+	db.create_view::<OpenTask>()?;
+	Ok(())
+}
+
+#[test]
+fn not_listed() -> SqlResult<()> {
+	#[database]
+	struct Db (Task);
+
+	#[derive(Table)]
+	struct Task {
+		#[key]
+		id: Id,
+		done: bool
+	}
+
+	view! {
+		#[view(name = "done_task", sql = "SELECT id FROM task WHERE done")]
+		struct DoneTask {
+			id: i64
+		}
+	}
+
+	let db = Db::create_in_memory()?;
+	assert!(db.get_all_view::<DoneTask>().is_err());
+	db.create_view::<DoneTask>()?;
+	assert!(db.get_all_view::<DoneTask>()?.is_empty());
+	Ok(())
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "vtab")]
+
+use liter::{
+	Id,
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+
+#[test]
+fn values() -> SqlResult<()> {
+	#[database]
+	struct Db (Product);
+
+	#[derive(Table)]
+	struct Product {
+		#[key]
+		id: Id
+	}
+	#[derive(Table, Debug, PartialEq)]
+	struct Line {
+		name: String,
+		note: Option<String>,
+		price: f64,
+		data: Vec<u8>
+	}
+
+	let db = Db::create_in_memory()?;
+	let mut lines = vec![
+		Line { name: "pen".to_string(), note: None, price: 1.5, data: vec![0, 1] },
+		Line { name: "ink".to_string(), note: Some("blue".to_string()), price: 8.0, data: Vec::new() }
+	];
+	db.register_collection("lines", &lines)?;
+	// a snapshot of the entries
+	lines.clear();
+	let rows: Vec<Line> = db.query_all("SELECT name, note, price, data FROM lines ORDER BY name DESC")?;
+	assert_eq!(rows[0], Line { name: "pen".to_string(), note: None, price: 1.5, data: vec![0, 1] });
+	assert_eq!(rows[1].note.as_deref(), Some("blue"));
+	assert_eq!(db.query_one::<i64>("SELECT count(*) FROM lines WHERE note IS NULL")?, 1);
+	assert_eq!(db.query_one::<i64>("SELECT max(rowid) FROM lines")?, 2);
+
+	db.register_collection::<Line>("lines", &[])?;
+	assert_eq!(db.query_one::<i64>("SELECT count(*) FROM lines")?, 0);
+	Ok(())
+}
+
+#[test]
+fn misuse() -> SqlResult<()> {
+	#[database]
+	struct Db (Product);
+
+	#[derive(Table)]
+	struct Product {
+		#[key]
+		id: Id
+	}
+	#[derive(Table)]
+	struct Line {
+		name: String
+	}
+
+	let db = Db::create_in_memory()?;
+	db.register_collection("lines", &[Line { name: "pen".to_string() }])?;
+	// it has no hidden columns to take arguments
+	assert!(db.query_all::<String>("SELECT name FROM lines('pen')").is_err());
+	// it's read-only
+	assert!(db.execute("INSERT INTO lines VALUES ('ink')", &()).is_err());
+	assert!(db.execute("DELETE FROM lines", &()).is_err());
+	// and only exists as an eponymous table
+	assert!(db.execute_batch("CREATE VIRTUAL TABLE more USING lines;").is_err());
+	assert!(db.query_all::<String>("SELECT name FROM missing_lines").is_err());
+	assert_eq!(db.query_all::<String>("SELECT name FROM lines")?, ["pen"]);
+	Ok(())
+}
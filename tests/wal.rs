@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use liter::{
+	Database,
+	Table,
+	database
+};
+use liter::wal::{
+	Checkpoint,
+	CheckpointMode
+};
+use rusqlite::Result as SqlResult;
+
+
+#[database]
+struct Db (Event);
+
+#[derive(Table, Debug)]
+struct Event {
+	name: String
+}
+
+const MODES: [CheckpointMode; 4] = [
+	CheckpointMode::Passive,
+	CheckpointMode::Full,
+	CheckpointMode::Restart,
+	CheckpointMode::Truncate
+];
+
+fn event(name: &str) -> Event {
+	Event { name: name.to_string() }
+}
+
+fn remove(path: &Path) {
+	for suffix in ["", "-wal", "-shm", "-journal"] {
+		let _ = fs::remove_file(format!("{}{suffix}", path.display()));
+	}
+}
+
+#[test]
+fn not_wal() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_wal_rollback.db");
+	remove(&path);
+	let on_disk = Db::init(&path)?;
+	let in_memory = Db::create_in_memory()?;
+	for db in [&on_disk, &in_memory] {
+		assert!(!db.is_wal()?);
+		db.insert(&event("not logged"))?;
+		for mode in MODES {
+			assert_eq!(db.checkpoint(mode)?, Checkpoint {
+				busy: false,
+				log_frames: None,
+				checkpointed_frames: None
+			}, "{mode:?}");
+		}
+	}
+	drop(on_disk);
+	remove(&path);
+	Ok(())
+}
+
+#[test]
+fn auto_checkpoint() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_wal_auto.db");
+	remove(&path);
+	let db = Database::<Db>::options()
+		.wal()
+		.auto_checkpoint(0)
+		.init(&path)?;
+	assert_eq!(db.auto_checkpoint()?, 0);
+	for _ in 0..10 {
+		db.insert(&event("logged"))?;
+	}
+	// without a reader in the way, the whole log is moved into the database file
+	let checkpoint = db.checkpoint(CheckpointMode::Passive)?;
+	assert_eq!(checkpoint.log_frames, checkpoint.checkpointed_frames);
+	assert!(checkpoint.log_frames >= Some(10));
+
+	db.set_auto_checkpoint(1)?;
+	assert_eq!(db.auto_checkpoint()?, 1);
+	drop(db);
+	remove(&path);
+	Ok(())
+}
+
+#[test]
+fn reader_blocks_checkpoint() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_wal_reader.db");
+	remove(&path);
+	let db = Database::<Db>::options()
+		.wal()
+		.auto_checkpoint(0)
+		.busy_timeout(Duration::ZERO)
+		.init(&path)?;
+	db.insert(&event("before"))?;
+	db.checkpoint(CheckpointMode::Truncate)?;
+
+	let reader = Database::<Db>::options().read_only().open(&path)?;
+	let mut stmt = reader.prepare("SELECT name FROM event")?;
+	let mut rows = stmt.query([])?;
+	// keeps the read transaction, and with it the reader's snapshot, open
+	assert!(rows.next()?.is_some());
+
+	db.insert(&event("after"))?;
+	let passive = db.checkpoint(CheckpointMode::Passive)?;
+	assert!(!passive.busy);
+	assert!(passive.checkpointed_frames < passive.log_frames, "{passive:?}");
+	for mode in [CheckpointMode::Full, CheckpointMode::Restart, CheckpointMode::Truncate] {
+		assert!(db.checkpoint(mode)?.busy, "{mode:?}");
+	}
+
+	drop(rows);
+	drop(stmt);
+	let truncated = db.checkpoint(CheckpointMode::Truncate)?;
+	assert_eq!(truncated, Checkpoint {
+		busy: false,
+		log_frames: Some(0),
+		checkpointed_frames: Some(0)
+	});
+	drop((reader, db));
+	remove(&path);
+	Ok(())
+}
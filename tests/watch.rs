@@ -0,0 +1,63 @@
+use std::fs;
+use std::time::Duration;
+
+use liter::{
+	Table,
+	database
+};
+use rusqlite::Result as SqlResult;
+
+#[database]
+struct Db (Settings);
+
+#[derive(Table, Clone, Debug, PartialEq, Eq)]
+struct Settings {
+	#[key]
+	id: u8,
+	log_level: String
+}
+
+#[test]
+fn notify_on_change() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_watch_settings.db");
+	let _ = fs::remove_file(&path);
+	let db = Db::init(&path)?;
+	db.insert(&Settings { id: 0, log_level: "info".to_string() })?;
+
+	let settings = db.watch_settings::<Settings>(Duration::from_millis(10))?;
+	let timeout = Duration::from_secs(5);
+	assert_eq!(settings.recv_timeout(timeout).unwrap().log_level, "info");
+
+	// another connection, as if it was another process
+	let other = Db::open(&path)?;
+	other.update(&Settings { id: 0, log_level: "debug".to_string() })?;
+	assert_eq!(settings.recv_timeout(timeout).unwrap().log_level, "debug");
+
+	settings.stop();
+	drop((db, other));
+	let _ = fs::remove_file(&path);
+	Ok(())
+}
+
+#[test]
+fn stops_without_changes() -> SqlResult<()> {
+	let path = std::env::temp_dir().join("liter_watch_settings_stop.db");
+	let _ = fs::remove_file(&path);
+	let db = Db::init(&path)?;
+
+	// nothing is ever sent, so the thread can't notice the receiver is gone by sending
+	let settings = db.watch_settings::<Settings>(Duration::from_millis(10))?;
+	assert!(settings.try_recv().is_err());
+	settings.stop();
+
+	drop(db);
+	let _ = fs::remove_file(&path);
+	Ok(())
+}
+
+#[test]
+fn in_memory_is_rejected() -> SqlResult<()> {
+	let db = Db::create_in_memory()?;
+	assert!(db.watch_settings::<Settings>(Duration::from_secs(1)).is_err());
+	Ok(())
+}